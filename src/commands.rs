@@ -1,6 +1,20 @@
 use crate::{
     config::Config,
-    entity::{Entity, find::FindParameters, owner::EntityOwners, permission::EntityPermissions},
+    entity::{
+        Entity, EntityDetails,
+        acl::EntityAcl,
+        filter::EntitysFilter,
+        find::{FindParameters, FindProgress},
+        owner::EntityOwners,
+        permission::{EntityPermissions, EntityPermissionsExpression},
+        rename::RenameParameters,
+        size::EntitySize,
+        transfer::{ConflictPolicy, TransferKind},
+        trash::TrashEntity,
+        usage::UsageProgress,
+        xattr::XattrChange,
+    },
+    job::Job,
     pin::Pin,
     tab::{SortBy, Tab},
 };
@@ -31,6 +45,8 @@ pub enum Command {
     CreateView(PathBuf),
     CreateEditor(PathBuf),
     CreateFinder(FindParameters),
+    CreateTrash,
+    CreateUsage(PathBuf),
     UpdateTab(usize),
     DeleteTab(usize, Tab),
     ChangeTabName(usize, Tab, String),
@@ -38,18 +54,73 @@ pub enum Command {
     ChangeTabPosition(usize, usize, Tab),
     NewTab(Option<usize>, Tab),
     ChangeEntitysSortBy(usize, Tab, SortBy),
+    ChangeTabFilter(usize, Tab, EntitysFilter),
 
     // Entity
     CreateEntity(usize, Tab, Entity),
     DeleteEntitys(usize, Tab, Vec<Entity>),
-    CopyEntitys(usize, Tab, usize, Vec<Entity>, PathBuf),
-    MoveEntitys(usize, Tab, usize, Vec<Entity>, PathBuf),
+    RemoveEntitys(usize, Tab, Vec<Entity>),
+    CopyEntitys(usize, Tab, usize, Vec<Entity>, PathBuf, ConflictPolicy),
+    MoveEntitys(usize, Tab, usize, Vec<Entity>, PathBuf, ConflictPolicy),
     ChangeEntityName(usize, Tab, usize, Entity, String),
     ChangeEntityExtension(usize, Tab, usize, Entity, String),
+    RenameEntitys(usize, Tab, Vec<Entity>, RenameParameters),
     ChangeEntityPermissions(usize, Tab, usize, Entity, EntityPermissions),
+    ChangeEntitysPermissions(usize, Tab, Vec<Entity>, EntityPermissionsExpression, bool),
     ChangeEntityOwners(usize, Tab, usize, Entity, EntityOwners),
+    // (uid, gid) to set, recursive, follow symlinks
+    ChangeEntitysOwners(
+        usize,
+        Tab,
+        Vec<Entity>,
+        (Option<u32>, Option<u32>),
+        bool,
+        bool,
+    ),
+    ChangeEntityAcl(usize, Tab, usize, Entity, EntityAcl),
+    ChangeEntityLink(usize, Tab, usize, Entity, PathBuf),
+    ChangeEntityXattrs(usize, Tab, usize, Entity, Vec<XattrChange>),
     ChangeEntityContent(usize, Tab, Entity, String),
+    EntitysConflict(
+        TransferKind,
+        usize,
+        Tab,
+        usize,
+        Vec<Entity>,
+        PathBuf,
+        Vec<PathBuf>,
+    ),
 
     // Find
     UpdateFind(usize, Tab, FindParameters),
+    StopFind(usize, Tab),
+    FindEntitys(usize, Vec<Entity>, FindProgress),
+
+    // Usage
+    StopUsage(usize, Tab),
+    UsageEntitys(usize, Vec<Entity>, UsageProgress),
+
+    // Directory size, the paths still unmeasured replace the ones asked before
+    ComputeDirSizes(Vec<PathBuf>),
+    InvalidateDirSizes(Vec<PathBuf>),
+    // path, (apparent size, allocated size)
+    DirSizes(Vec<(PathBuf, (EntitySize, EntitySize))>),
+
+    // Details, the entitys still missing some replace the ones asked before
+    ReadDetails(Vec<(PathBuf, EntityDetails)>),
+    Details(Vec<Entity>),
+
+    // Journal
+    Undo,
+    Redo,
+
+    // Trash
+    RestoreEntitys(usize, Tab, Vec<TrashEntity>),
+    PurgeEntitys(usize, Tab, Vec<TrashEntity>),
+
+    // Job
+    PauseJob(usize),
+    ResumeJob(usize),
+    CancelJob(usize),
+    JobProgress(Job),
 }
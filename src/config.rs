@@ -1,4 +1,5 @@
 use crate::{
+    entity::format::EntitysFormat,
     error::JujikError,
     pin::Pin,
     tab::Tab,
@@ -16,6 +17,8 @@ pub struct Config {
     pub tabs: Vec<Tab>,
     pub current_tab_idx: usize,
     pub entitys_show: EntitysShowColumn,
+    #[serde(default)]
+    pub entitys_format: EntitysFormat,
 }
 
 impl Config {
@@ -25,6 +28,7 @@ impl Config {
         tabs: Vec<Tab>,
         current_tab_idx: usize,
         entitys_show: EntitysShowColumn,
+        entitys_format: EntitysFormat,
     ) -> Self {
         Self {
             style,
@@ -32,6 +36,7 @@ impl Config {
             tabs,
             current_tab_idx,
             entitys_show,
+            entitys_format,
         }
     }
 
@@ -58,6 +63,7 @@ impl Default for Config {
             tabs: Vec::new(),
             current_tab_idx: 0,
             entitys_show: EntitysShowColumn::default(),
+            entitys_format: EntitysFormat::default(),
         }
     }
 }
@@ -1,14 +1,34 @@
-use crate::{commands::Command, config::Config, error::JujikError};
+use crate::{
+    commands::Command,
+    config::Config,
+    entity::{Entity, EntityDetails, size::EntitySize, watch::EntitysWatcher},
+    error::JujikError,
+    tab::TabContent,
+};
 use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::PathBuf,
     sync::mpsc::{Receiver, Sender},
     thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
+// Changes are gathered this long before the tabs are updated,
+// a file being written sends a flood of them
+const WATCH_COALESCE_INTERVAL: Duration = Duration::from_millis(200);
+
 pub struct JujikController {
     model: Sender<Command>,
     view: Sender<Command>,
     controller: Receiver<Command>,
     config: Config,
+    watcher: Option<EntitysWatcher>,
+    // changed paths not applied yet, with the time of the first one
+    watch_pending: (BTreeSet<PathBuf>, Option<Instant>),
+    // directories the model is measuring
+    dir_sizes: Vec<PathBuf>,
+    // entitys the model is reading details of
+    details: Vec<(PathBuf, EntityDetails)>,
 }
 impl JujikController {
     pub fn new(
@@ -27,11 +47,26 @@ impl JujikController {
             }
         };
 
+        let watcher = match EntitysWatcher::new() {
+            Ok(watcher) => Some(watcher),
+            Err(err) => {
+                view.send(Command::Error(Box::new(format!(
+                    "Can not watch directories at Controller start\n{}",
+                    err
+                ))))?;
+                None
+            }
+        };
+
         Ok(Self {
             model,
             view,
             controller,
             config: config,
+            watcher,
+            watch_pending: (BTreeSet::new(), None),
+            dir_sizes: Vec::new(),
+            details: Vec::new(),
         })
     }
 
@@ -41,6 +76,7 @@ impl JujikController {
             .spawn(move || -> Result<(), JujikError> {
                 // self.update_tabs()?;
                 self.view.send(Command::SetConfig(self.config.clone()))?;
+                self.watch_tabs();
 
                 'event_loop: loop {
                     if let Ok(command) = self.controller.try_recv() {
@@ -137,6 +173,18 @@ impl JujikController {
                                     ))))?;
                                 }
                             }
+                            Command::CreateTrash => {
+                                self.model.send(Command::CreateTrash)?;
+                            }
+                            Command::CreateUsage(pathbuf) => {
+                                if pathbuf.is_dir() {
+                                    self.model.send(Command::CreateUsage(pathbuf))?
+                                } else {
+                                    self.view.send(Command::Error(Box::new(JujikError::Other(
+                                        format!("Path {:?} is not a directory", pathbuf),
+                                    ))))?;
+                                }
+                            }
                             Command::DeleteTab(idx_d, tab_d) => {
                                 for (idx, tab) in self.config.tabs.clone().iter().enumerate() {
                                     if idx == idx_d && tab.eq(&tab_d) {
@@ -144,6 +192,12 @@ impl JujikController {
                                     }
                                 }
 
+                                if tab_d.finder().is_some() {
+                                    self.model.send(Command::StopFind(idx_d, tab_d))?;
+                                } else if tab_d.usage().is_some() {
+                                    self.model.send(Command::StopUsage(idx_d, tab_d))?;
+                                }
+
                                 self.sync_view()?;
                             }
                             Command::UpdateTab(idx) => {
@@ -189,11 +243,21 @@ impl JujikController {
                             Command::DeleteEntitys(idx, tab, entitys) => {
                                 self.model.send(Command::DeleteEntitys(idx, tab, entitys))?;
                             }
-                            Command::CopyEntitys(idx_tab, tab, idx_entity, entitys, pathbuf) => {
+                            Command::RemoveEntitys(idx, tab, entitys) => {
+                                self.model.send(Command::RemoveEntitys(idx, tab, entitys))?;
+                            }
+                            Command::CopyEntitys(
+                                idx_tab,
+                                tab,
+                                idx_entity,
+                                entitys,
+                                pathbuf,
+                                policy,
+                            ) => {
                                 if pathbuf.exists() {
                                     if pathbuf.is_dir() {
                                         self.model.send(Command::CopyEntitys(
-                                            idx_tab, tab, idx_entity, entitys, pathbuf,
+                                            idx_tab, tab, idx_entity, entitys, pathbuf, policy,
                                         ))?;
                                     } else {
                                         self.view.send(Command::Error(Box::new(
@@ -209,11 +273,18 @@ impl JujikController {
                                     ))))?;
                                 }
                             }
-                            Command::MoveEntitys(idx_tab, tab, idx_entity, entitys, pathbuf) => {
+                            Command::MoveEntitys(
+                                idx_tab,
+                                tab,
+                                idx_entity,
+                                entitys,
+                                pathbuf,
+                                policy,
+                            ) => {
                                 if pathbuf.exists() {
                                     if pathbuf.is_dir() {
                                         self.model.send(Command::MoveEntitys(
-                                            idx_tab, tab, idx_entity, entitys, pathbuf,
+                                            idx_tab, tab, idx_entity, entitys, pathbuf, policy,
                                         ))?;
                                     } else {
                                         self.view.send(Command::Error(Box::new(
@@ -249,6 +320,11 @@ impl JujikController {
                                     ))?;
                                 }
                             }
+                            Command::RenameEntitys(idx_tab, tab, entitys, parameters) => {
+                                self.model.send(Command::RenameEntitys(
+                                    idx_tab, tab, entitys, parameters,
+                                ))?;
+                            }
                             Command::ChangeEntityPermissions(
                                 idx_tab,
                                 tab,
@@ -266,6 +342,17 @@ impl JujikController {
                                     ))?;
                                 }
                             }
+                            Command::ChangeEntitysPermissions(
+                                idx_tab,
+                                tab,
+                                entitys,
+                                expression,
+                                recursive,
+                            ) => {
+                                self.model.send(Command::ChangeEntitysPermissions(
+                                    idx_tab, tab, entitys, expression, recursive,
+                                ))?;
+                            }
                             Command::ChangeEntityOwners(
                                 idx_tab,
                                 tab,
@@ -279,6 +366,43 @@ impl JujikController {
                                     ))?;
                                 }
                             }
+                            Command::ChangeEntityAcl(idx_tab, tab, idx_entity, entity, acl)
+                                if entity.exists() =>
+                            {
+                                self.model.send(Command::ChangeEntityAcl(
+                                    idx_tab, tab, idx_entity, entity, acl,
+                                ))?;
+                            }
+                            Command::ChangeEntityLink(idx_tab, tab, idx_entity, entity, text)
+                                if entity.exists() =>
+                            {
+                                self.model.send(Command::ChangeEntityLink(
+                                    idx_tab, tab, idx_entity, entity, text,
+                                ))?;
+                            }
+                            Command::ChangeEntityXattrs(
+                                idx_tab,
+                                tab,
+                                idx_entity,
+                                entity,
+                                xattrs,
+                            ) if entity.exists() => {
+                                self.model.send(Command::ChangeEntityXattrs(
+                                    idx_tab, tab, idx_entity, entity, xattrs,
+                                ))?;
+                            }
+                            Command::ChangeEntitysOwners(
+                                idx_tab,
+                                tab,
+                                entitys,
+                                ids,
+                                recursive,
+                                follow,
+                            ) => {
+                                self.model.send(Command::ChangeEntitysOwners(
+                                    idx_tab, tab, entitys, ids, recursive, follow,
+                                ))?;
+                            }
                             Command::ChangeEntityContent(idx, tab, entity, content) => {
                                 if entity.exists() {
                                     self.model.send(Command::ChangeEntityContent(
@@ -286,13 +410,153 @@ impl JujikController {
                                     ))?;
                                 }
                             }
+                            Command::EntitysConflict(
+                                kind,
+                                idx_tab,
+                                tab,
+                                idx_entity,
+                                entitys,
+                                pathbuf,
+                                conflicts,
+                            ) => {
+                                self.view.send(Command::EntitysConflict(
+                                    kind, idx_tab, tab, idx_entity, entitys, pathbuf, conflicts,
+                                ))?;
+                            }
                             Command::ChangeEntitysSortBy(idx, tab, sordby) => {
                                 self.model
                                     .send(Command::ChangeEntitysSortBy(idx, tab, sordby))?;
                             }
+                            Command::ChangeTabFilter(idx, tab, filter) => {
+                                self.model
+                                    .send(Command::ChangeTabFilter(idx, tab, filter))?;
+                            }
 
                             // Find
-                            Command::UpdateFind(idx_tab, tab, parameters) => {}
+                            Command::UpdateFind(idx_tab, tab, parameters) => {
+                                if parameters.path.exists() {
+                                    self.model
+                                        .send(Command::UpdateFind(idx_tab, tab, parameters))?
+                                } else {
+                                    self.view.send(Command::Error(Box::new(JujikError::Other(
+                                        format!("Path {:?} does not exist", parameters.path),
+                                    ))))?;
+                                }
+                            }
+                            Command::StopFind(idx, tab) => {
+                                self.model.send(Command::StopFind(idx, tab))?;
+                            }
+
+                            // Journal
+                            Command::Undo => {
+                                self.model.send(Command::Undo)?;
+                            }
+                            Command::Redo => {
+                                self.model.send(Command::Redo)?;
+                            }
+
+                            // Trash
+                            Command::RestoreEntitys(idx, tab, trash_entitys) => {
+                                self.model.send(Command::RestoreEntitys(
+                                    idx,
+                                    tab,
+                                    trash_entitys,
+                                ))?;
+                            }
+                            Command::PurgeEntitys(idx, tab, trash_entitys) => {
+                                self.model
+                                    .send(Command::PurgeEntitys(idx, tab, trash_entitys))?;
+                            }
+                            Command::FindEntitys(id, entitys, progress) => {
+                                if let Some(finder) = self
+                                    .config
+                                    .tabs
+                                    .iter_mut()
+                                    .filter_map(|t| t.finder_mut())
+                                    .find(|f| f.id() == id)
+                                {
+                                    finder.push(entitys, progress);
+                                }
+
+                                self.sync_view()?;
+                            }
+
+                            // Usage
+                            Command::StopUsage(idx, tab) => {
+                                self.model.send(Command::StopUsage(idx, tab))?;
+                            }
+                            Command::UsageEntitys(id, entitys, progress) => {
+                                if let Some(usage) = self
+                                    .config
+                                    .tabs
+                                    .iter_mut()
+                                    .filter_map(|t| t.usage_mut())
+                                    .find(|u| u.id() == id)
+                                {
+                                    usage.push(entitys, progress);
+                                }
+
+                                self.sync_view()?;
+                            }
+
+                            // Directory size
+                            Command::DirSizes(sizes) => {
+                                let sizes: HashMap<PathBuf, (EntitySize, EntitySize)> =
+                                    sizes.into_iter().collect();
+
+                                for entity in self
+                                    .config
+                                    .tabs
+                                    .iter_mut()
+                                    .filter_map(|t| t.entitys_mut())
+                                    .flatten()
+                                {
+                                    if let Some((size, allocated)) = sizes.get(&entity.path()) {
+                                        entity.set_size(size.clone());
+                                        entity.set_allocated(allocated.clone());
+                                    }
+                                }
+
+                                self.dir_sizes.retain(|p| !sizes.contains_key(p));
+                                self.sync_view()?;
+                            }
+
+                            // Details
+                            Command::Details(read) => {
+                                let read: HashMap<PathBuf, Entity> =
+                                    read.into_iter().map(|e| (e.path(), e)).collect();
+
+                                for tab in &mut self.config.tabs {
+                                    let entitys = if tab.finder().is_some() {
+                                        tab.finder_mut().map(|f| f.entitys_mut())
+                                    } else {
+                                        tab.entitys_mut()
+                                    };
+
+                                    for entity in entitys.into_iter().flatten() {
+                                        if let Some(other) = read.get(&entity.path()) {
+                                            entity.set_details(other);
+                                        }
+                                    }
+                                }
+
+                                self.details.retain(|(p, _)| !read.contains_key(p));
+                                self.sync_view()?;
+                            }
+
+                            // Job
+                            Command::PauseJob(id) => {
+                                self.model.send(Command::PauseJob(id))?;
+                            }
+                            Command::ResumeJob(id) => {
+                                self.model.send(Command::ResumeJob(id))?;
+                            }
+                            Command::CancelJob(id) => {
+                                self.model.send(Command::CancelJob(id))?;
+                            }
+                            Command::JobProgress(job) => {
+                                self.view.send(Command::JobProgress(job))?;
+                            }
 
                             // Config
                             Command::SetConfig(config) => {
@@ -314,8 +578,14 @@ impl JujikController {
                             }
                             _ => {}
                         }
+
+                        self.watch_tabs();
+                        self.measure_dirs()?;
+                        self.read_details()?;
                     };
 
+                    self.watch_changes()?;
+
                     std::thread::sleep(std::time::Duration::from_millis(10));
                 }
 
@@ -343,6 +613,136 @@ impl JujikController {
         Ok(())
     }
 
+    fn watch_tabs(&mut self) {
+        if let Some(watcher) = &mut self.watcher {
+            let pathbufs: HashSet<_> = self
+                .config
+                .tabs
+                .iter()
+                .filter_map(|tab| match tab.content() {
+                    TabContent::Entitys(_, pathbuf, _) => Some(pathbuf.clone()),
+                    _ => None,
+                })
+                .collect();
+
+            watcher.watch(&pathbufs);
+        }
+    }
+
+    fn watch_changes(&mut self) -> Result<(), JujikError> {
+        let (pending, since) = &mut self.watch_pending;
+
+        match &mut self.watcher {
+            Some(watcher) => pending.extend(watcher.changes()),
+            None => return Ok(()),
+        }
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        if since.get_or_insert_with(Instant::now).elapsed() < WATCH_COALESCE_INTERVAL {
+            return Ok(());
+        }
+
+        let pathbufs: Vec<PathBuf> = std::mem::take(pending).into_iter().collect();
+        *since = None;
+
+        let mut changed = false;
+
+        for tab in &mut self.config.tabs {
+            changed |= tab.update_paths(&pathbufs);
+        }
+
+        if self.config.entitys_show.dir_size() {
+            // A change deep inside a directory makes its total wrong as well
+            for entity in self
+                .config
+                .tabs
+                .iter_mut()
+                .filter_map(|t| t.entitys_mut())
+                .flatten()
+                .filter(|e| e.is_dir() && pathbufs.iter().any(|p| p.starts_with(e.path())))
+            {
+                entity.set_size(EntitySize::default());
+                changed = true;
+            }
+
+            self.model.send(Command::InvalidateDirSizes(pathbufs))?;
+        }
+
+        if changed {
+            self.sync_view()?;
+            self.measure_dirs()?;
+            self.read_details()?;
+        }
+
+        Ok(())
+    }
+
+    // Asks for the directories of the open tabs that have no size yet,
+    // only when they differ from the last request
+    fn measure_dirs(&mut self) -> Result<(), JujikError> {
+        let mut pathbufs: Vec<PathBuf> = Vec::new();
+
+        if self.config.entitys_show.dir_size() {
+            for tab in &self.config.tabs {
+                if let TabContent::Entitys(_, _, entitys) = tab.content() {
+                    pathbufs.extend(
+                        entitys
+                            .iter()
+                            .filter(|e| e.is_dir() && e.size().is_none())
+                            .map(|e| e.path()),
+                    );
+                }
+            }
+        }
+
+        // The same directory can be listed in several tabs
+        pathbufs.sort();
+        pathbufs.dedup();
+
+        if pathbufs != self.dir_sizes {
+            self.dir_sizes.clone_from(&pathbufs);
+            self.model.send(Command::ComputeDirSizes(pathbufs))?;
+        }
+
+        Ok(())
+    }
+
+    // Asks for the details the shown columns need of the entitys and finds of the open tabs,
+    // only when they differ from the last request
+    fn read_details(&mut self) -> Result<(), JujikError> {
+        let details = self.config.entitys_show.details();
+        let mut entitys: Vec<(PathBuf, EntityDetails)> = Vec::new();
+
+        for tab in &self.config.tabs {
+            let tab_entitys = match tab.content() {
+                TabContent::Entitys(_, _, entitys) => entitys.clone(),
+                TabContent::Find(finder) => finder.entitys(),
+                _ => continue,
+            };
+
+            entitys.extend(
+                tab_entitys
+                    .iter()
+                    .map(|e| (e.path(), e.missing_details(details)))
+                    .filter(|(_, missing)| *missing != EntityDetails::default()),
+            );
+        }
+
+        // The same entity can be listed in several tabs
+        entitys.sort_by(|(p1, _), (p2, _)| p1.cmp(p2));
+        entitys.dedup();
+
+        if entitys != self.details {
+            self.details.clone_from(&entitys);
+            self.model.send(Command::ReadDetails(entitys))?;
+        }
+
+        Ok(())
+    }
+
     fn root_access(&self) -> Result<(), JujikError> {
         Ok(())
     }
@@ -1,28 +1,50 @@
+pub mod acl;
 pub mod date;
+pub mod dir_size;
+pub mod filter;
 pub mod find;
+pub mod format;
 pub mod kind;
+pub mod link;
+pub mod mime;
 pub mod owner;
 pub mod permission;
+pub mod rename;
 pub mod size;
+pub mod transfer;
+pub mod trash;
+pub mod usage;
+pub mod watch;
+pub mod xattr;
 
 use crate::error::JujikError;
 use date::EntityDate;
 use kind::EntityKind;
+use link::EntityLink;
+use mime::EntityMime;
+use nix::sys::stat::{major, minor};
 use owner::EntityOwners;
 use permission::EntityPermissions;
 use serde::{Deserialize, Serialize};
 use size::EntitySize;
-#[cfg(feature = "dir_size")]
-use std::collections::LinkedList;
-#[cfg(feature = "dir_size")]
-use std::fs;
 use std::{
     fmt::Display,
-    fs::{File, canonicalize, symlink_metadata},
+    fs::{File, Metadata, canonicalize, symlink_metadata},
     io::Read,
-    os::{linux::fs::MetadataExt, unix::fs::FileTypeExt},
-    path::{Path, PathBuf},
+    os::linux::fs::MetadataExt,
+    path::{Component, Path, PathBuf, absolute},
 };
+use xattr::{EntityXattr, EntityXattrs};
+
+/// What `from_metadata` leaves out because the entity has to be opened,
+/// read for the columns that show it.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct EntityDetails {
+    // media type from the content, the name alone tells it otherwise
+    pub mime: bool,
+    // extended attributes, and the ACL they hold
+    pub xattrs: bool,
+}
 
 #[derive(Debug, Default, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct Entity {
@@ -30,30 +52,117 @@ pub struct Entity {
     name: String,
     extension: Option<String>,
     kind: EntityKind,
+    #[serde(default)]
+    mime: EntityMime,
+    #[serde(default)]
+    link: Option<EntityLink>,
     permissions: EntityPermissions,
+    #[serde(default)]
+    acl: bool,
     owners: EntityOwners,
     size: EntitySize,
+    // st_blocks * 512, less than `size` for sparse files
+    #[serde(default)]
+    allocated: EntitySize,
+    #[serde(default)]
+    links: u64,
+    #[serde(default)]
+    inode: u64,
+    #[serde(default)]
+    device: u64,
     modification: EntityDate,
     creation: EntityDate,
+    #[serde(default)]
+    access: EntityDate,
+    #[serde(default)]
+    change: EntityDate,
+    #[serde(default)]
+    xattrs: Vec<EntityXattr>,
+    // `xattrs` and `acl` stay empty until the attributes are read
+    #[serde(default)]
+    xattrs_read: bool,
 }
 
 impl Entity {
     pub fn new(pathbuf: PathBuf) -> Result<Self, JujikError> {
         let path = pathbuf.as_path();
 
+        Self::from_metadata(path, &symlink_metadata(path)?)
+    }
+
+    /// Builds the entity from one `lstat` of `path`, as `read_dir` and the finder get it.
+    pub fn from_metadata(path: &Path, metadata: &Metadata) -> Result<Self, JujikError> {
+        let kind = EntityKind::from(metadata.file_type());
+
         Ok(Self {
             global_path: Self::get_global_path(path)?,
             name: Self::get_name(path)?,
             extension: Self::get_extension(path)?,
-            kind: Self::get_kind(path)?,
-            permissions: Self::get_permissions(path)?,
-            owners: Self::get_owners(path)?,
-            size: Self::get_size(path)?,
-            modification: Self::get_modification(path)?,
-            creation: Self::get_creation(path)?,
+            mime: EntityMime::new(path, &kind),
+            kind,
+            link: Self::get_link(path, metadata)?,
+            permissions: EntityPermissions::new(metadata.st_mode()),
+            acl: false,
+            owners: EntityOwners::new(metadata.st_uid(), metadata.st_gid())?,
+            size: Self::get_size(metadata),
+            allocated: EntitySize::new(metadata.st_blocks() * 512),
+            links: metadata.st_nlink(),
+            inode: metadata.st_ino(),
+            device: metadata.st_dev(),
+            modification: Self::get_modification(metadata),
+            creation: Self::get_creation(metadata),
+            access: Self::get_access(metadata),
+            change: Self::get_change(metadata),
+            xattrs: Vec::new(),
+            xattrs_read: false,
         })
     }
 
+    pub fn with_details(mut self, details: EntityDetails) -> Self {
+        self.read_details(details);
+        self
+    }
+
+    /// Reads the `details` that are not there yet.
+    pub fn read_details(&mut self, details: EntityDetails) {
+        let missing = self.missing_details(details);
+
+        if missing.mime {
+            self.mime = EntityMime::sniffed(&self.global_path, &self.kind);
+        }
+
+        if missing.xattrs {
+            self.xattrs = EntityXattrs::list(&self.global_path).unwrap_or_default();
+            // ACLs are stored as attributes, no need to ask for them again
+            self.acl = self
+                .xattrs
+                .iter()
+                .any(|(name, _)| EntityXattrs::is_acl(name));
+            self.xattrs_read = true;
+        }
+    }
+
+    /// The ones of `details` that were not read yet.
+    pub fn missing_details(&self, details: EntityDetails) -> EntityDetails {
+        EntityDetails {
+            mime: details.mime && !self.mime.is_sniffed(),
+            xattrs: details.xattrs && !self.xattrs_read,
+        }
+    }
+
+    /// Takes the details `other`, the same entity read again, has.
+    pub fn set_details(&mut self, other: &Entity) {
+        if other.mime.is_sniffed() {
+            self.mime.clone_from(&other.mime);
+        }
+
+        if other.xattrs_read {
+            self.xattrs.clone_from(&other.xattrs);
+            self.acl = other.acl;
+            self.xattrs_read = true;
+        }
+    }
+
     pub fn ghost(pathbuf: PathBuf, name: String, extension: String) -> Result<Self, JujikError> {
         let extension = if extension.eq("") || extension.eq("None") {
             None
@@ -81,12 +190,23 @@ impl Entity {
             global_path: pathbuf,
             name,
             extension,
+            mime: EntityMime::default(),
             kind,
+            link: None,
             permissions,
+            acl: false,
             owners: EntityOwners::current()?,
             size: EntitySize::default(),
+            allocated: EntitySize::default(),
+            links: 0,
+            inode: 0,
+            device: 0,
             modification: EntityDate::now(),
             creation: EntityDate::now(),
+            access: EntityDate::now(),
+            change: EntityDate::now(),
+            xattrs: Vec::new(),
+            xattrs_read: false,
         })
     }
 }
@@ -157,10 +277,32 @@ impl Entity {
         &self.kind
     }
 
+    pub fn mime(&self) -> &EntityMime {
+        &self.mime
+    }
+
+    pub fn link(&self) -> &Option<EntityLink> {
+        &self.link
+    }
+
     pub fn permissions(&self) -> &EntityPermissions {
         &self.permissions
     }
 
+    pub fn has_acl(&self) -> bool {
+        self.acl
+    }
+
+    /// Permissions as `ls -l` shows them, `+` when there is an ACL.
+    pub fn permissions_str(&self) -> String {
+        format!(
+            "{}{} ({:o})",
+            self.permissions.symbolic(),
+            if self.acl { "+" } else { "" },
+            self.permissions.mode()
+        )
+    }
+
     pub fn owners(&self) -> &EntityOwners {
         &self.owners
     }
@@ -169,6 +311,35 @@ impl Entity {
         &self.size
     }
 
+    pub fn set_size(&mut self, size: EntitySize) {
+        self.size = size;
+    }
+
+    pub fn allocated(&self) -> &EntitySize {
+        &self.allocated
+    }
+
+    pub fn set_allocated(&mut self, allocated: EntitySize) {
+        self.allocated = allocated;
+    }
+
+    pub fn links(&self) -> u64 {
+        self.links
+    }
+
+    pub fn inode(&self) -> u64 {
+        self.inode
+    }
+
+    pub fn device(&self) -> u64 {
+        self.device
+    }
+
+    /// Device number as `major:minor`, the way `stat` prints it.
+    pub fn device_str(&self) -> String {
+        format!("{}:{}", major(self.device), minor(self.device))
+    }
+
     pub fn modification(&self) -> &EntityDate {
         &self.modification
     }
@@ -177,8 +348,28 @@ impl Entity {
         &self.creation
     }
 
+    pub fn access(&self) -> &EntityDate {
+        &self.access
+    }
+
+    pub fn change(&self) -> &EntityDate {
+        &self.change
+    }
+
+    pub fn xattrs(&self) -> &Vec<EntityXattr> {
+        &self.xattrs
+    }
+
+    pub fn xattr(&self, name: &str) -> Option<&Vec<u8>> {
+        self.xattrs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value)
+    }
+
+    // A broken link still exists
     pub fn exists(&self) -> bool {
-        self.path().exists()
+        symlink_metadata(self.path()).is_ok()
     }
 
     pub fn is_file(&self) -> bool {
@@ -197,6 +388,23 @@ impl Entity {
         }
     }
 
+    pub fn is_symlink(&self) -> bool {
+        self.link.is_some()
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.link.as_ref().is_some_and(|l| !l.exists())
+    }
+
+    /// A directory, or a link to one.
+    pub fn points_to_dir(&self) -> bool {
+        self.is_dir()
+            || self
+                .link
+                .as_ref()
+                .is_some_and(|l| *l.target_kind() == EntityKind::Directory)
+    }
+
     pub fn content(&self) -> Result<String, JujikError> {
         let mut file = File::open(self.path())?;
         let mut content = String::new();
@@ -205,13 +413,27 @@ impl Entity {
 
         Ok(content)
     }
+
+    /// First `len` bytes of the content.
+    pub fn head(&self, len: u64) -> Result<Vec<u8>, JujikError> {
+        let mut head = Vec::new();
+
+        File::open(self.path())?.take(len).read_to_end(&mut head)?;
+
+        Ok(head)
+    }
 }
 
 impl Entity {
+    // Symlinks are not resolved so that a link keeps its own path
     fn get_global_path(path: &Path) -> Result<PathBuf, JujikError> {
-        match canonicalize(path) {
-            Ok(global_path) => Ok(global_path),
-            Err(err) => Err(JujikError::from(err)),
+        if path
+            .components()
+            .any(|c| matches!(c, Component::CurDir | Component::ParentDir))
+        {
+            Ok(canonicalize(path)?)
+        } else {
+            Ok(absolute(path)?)
         }
     }
     pub fn get_name(path: &Path) -> Result<String, JujikError> {
@@ -240,115 +462,51 @@ impl Entity {
         }
     }
 
-    fn get_kind(path: &Path) -> Result<EntityKind, JujikError> {
-        let file_type = symlink_metadata(path)?.file_type();
-
-        Ok({
-            if file_type.is_file() {
-                EntityKind::File
-            } else if file_type.is_dir() {
-                EntityKind::Directory
-            } else if file_type.is_symlink() {
-                EntityKind::Symlink
-            } else if file_type.is_block_device() {
-                EntityKind::Block
-            } else if file_type.is_char_device() {
-                EntityKind::Character
-            } else if file_type.is_fifo() {
-                EntityKind::Pipe
-            } else if file_type.is_socket() {
-                EntityKind::Socket
-            } else {
-                EntityKind::Unknown
-            }
-        })
-    }
-
-    fn get_permissions(path: &Path) -> Result<EntityPermissions, JujikError> {
-        let mode = symlink_metadata(path)?.st_mode();
-
-        Ok(EntityPermissions::new(mode))
-    }
-
-    fn get_owners(path: &Path) -> Result<EntityOwners, JujikError> {
-        let metadata = symlink_metadata(path)?;
-        let (uid, gid) = (metadata.st_uid(), metadata.st_gid());
-
-        EntityOwners::new(uid, gid)
-    }
-
-    fn get_size(path: &Path) -> Result<EntitySize, JujikError> {
-        #[cfg(feature = "dir_size")]
-        if path.is_dir() {
-            let mut entity_size = EntitySize::new(0);
-            let mut pathbuf_stack: LinkedList<PathBuf> = LinkedList::new();
-
-            if let Ok(read_dir) = fs::read_dir(path) {
-                pathbuf_stack = read_dir
-                    .map(|f| {
-                        if let Ok(dir_entry) = f {
-                            dir_entry.path()
-                        } else {
-                            PathBuf::new()
-                        }
-                    })
-                    .collect()
-            }
-
-            while !pathbuf_stack.is_empty() {
-                let pathbuf = pathbuf_stack.front().unwrap();
-
-                if let Ok(metadata) = symlink_metadata(pathbuf.as_path()) {
-                    if pathbuf.is_dir() && !metadata.is_symlink() {
-                        if let Ok(read_dir) = fs::read_dir(pathbuf.as_path()) {
-                            for dir_entry in read_dir {
-                                if let Ok(dir_entry) = dir_entry {
-                                    pathbuf_stack.push_back(dir_entry.path());
-                                }
-                            }
-                        }
-                    } else {
-                        if let Ok(metadata) = symlink_metadata(pathbuf) {
-                            entity_size.add(metadata.len());
-                        }
-                    }
-                }
-
-                pathbuf_stack.pop_front();
-            }
-
-            Ok(entity_size)
+    fn get_link(path: &Path, metadata: &Metadata) -> Result<Option<EntityLink>, JujikError> {
+        if metadata.is_symlink() {
+            Ok(Some(EntityLink::new(path)?))
         } else {
-            Ok(EntitySize::new(symlink_metadata(path)?.len()))
+            Ok(None)
         }
+    }
 
-        #[cfg(not(feature = "dir_size"))]
-        if path.is_dir() {
-            Ok(EntitySize::default())
+    // Directory sizes are measured later, see `dir_size`
+    fn get_size(metadata: &Metadata) -> EntitySize {
+        if metadata.is_dir() {
+            EntitySize::default()
         } else {
-            Ok(EntitySize::new(symlink_metadata(path)?.len()))
+            EntitySize::new(metadata.len())
         }
     }
 
-    fn get_modification(path: &Path) -> Result<EntityDate, JujikError> {
-        let metadata = symlink_metadata(path)?;
-
+    fn get_modification(metadata: &Metadata) -> EntityDate {
         if let Ok(date_modification) = metadata.modified() {
-            Ok(EntityDate::new(date_modification))
+            EntityDate::new(date_modification)
         } else {
-            Ok(EntityDate::default())
+            EntityDate::default()
         }
     }
 
-    fn get_creation(path: &Path) -> Result<EntityDate, JujikError> {
-        let metadata = symlink_metadata(path)?;
+    // Birth time from statx, unknown when the filesystem does not record it
+    fn get_creation(metadata: &Metadata) -> EntityDate {
+        if let Ok(date_creation) = metadata.created() {
+            EntityDate::new(date_creation)
+        } else {
+            EntityDate::default()
+        }
+    }
 
-        if let Ok(date_creation) = metadata.modified() {
-            Ok(EntityDate::new(date_creation))
+    fn get_access(metadata: &Metadata) -> EntityDate {
+        if let Ok(date_access) = metadata.accessed() {
+            EntityDate::new(date_access)
         } else {
-            Ok(EntityDate::default())
+            EntityDate::default()
         }
     }
+
+    fn get_change(metadata: &Metadata) -> EntityDate {
+        EntityDate::from_timestamp(metadata.st_ctime(), metadata.st_ctime_nsec())
+    }
 }
 
 impl Display for Entity {
@@ -358,11 +516,21 @@ impl Display for Entity {
             .field("name", &self.name)
             .field("extension", &self.extension_str())
             .field("kind", &self.kind)
+            .field("mime", &self.mime)
+            .field("link", &self.link)
             .field("permissions", &self.permissions)
+            .field("acl", &self.acl)
             .field("owners", &self.owners)
             .field("size", &self.size)
+            .field("allocated", &self.allocated)
+            .field("links", &self.links)
+            .field("inode", &self.inode)
+            .field("device", &self.device_str())
             .field("modification", &self.modification)
             .field("creation", &self.creation)
+            .field("access", &self.access)
+            .field("change", &self.change)
+            .field("xattrs", &self.xattrs)
             .finish()
     }
 }
@@ -0,0 +1,253 @@
+use crate::error::JujikError;
+use nix::unistd::{Gid, Group, Uid, User};
+use rustix::{
+    fs::{XattrFlags, lgetxattr, lremovexattr, lsetxattr},
+    io::Errno,
+};
+use std::{fmt::Display, fs::symlink_metadata, os::linux::fs::MetadataExt, path::Path};
+
+const ACL_ACCESS: &str = "system.posix_acl_access";
+const ACL_DEFAULT: &str = "system.posix_acl_default";
+const ACL_VERSION: u32 = 2;
+const ACL_UNDEFINED_ID: u32 = u32::MAX;
+
+// Ordered as the kernel wants the entries
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum AclTag {
+    UserObj,
+    User(u32),
+    GroupObj,
+    Group(u32),
+    Mask,
+    Other,
+}
+
+/// One entry of an ACL, `permissions` are the rwx bits (0o0..=0o7).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct AclEntry {
+    pub tag: AclTag,
+    pub permissions: u32,
+}
+
+/// Access and default POSIX ACLs of an entity.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Hash)]
+pub struct EntityAcl {
+    pub access: Vec<AclEntry>,
+    pub default: Vec<AclEntry>,
+}
+
+impl EntityAcl {
+    /// Without an access ACL the mode gives the base entries, the default ACL may be empty.
+    pub fn new(path: &Path) -> Result<Self, JujikError> {
+        let mut access = Self::read(path, ACL_ACCESS)?;
+
+        if access.is_empty() {
+            access = Self::from_mode(symlink_metadata(path)?.st_mode());
+        }
+
+        Ok(Self {
+            access,
+            default: Self::read(path, ACL_DEFAULT)?,
+        })
+    }
+
+    pub fn from_mode(mode: u32) -> Vec<AclEntry> {
+        vec![
+            AclEntry::new(AclTag::UserObj, mode >> 6),
+            AclEntry::new(AclTag::GroupObj, mode >> 3),
+            AclEntry::new(AclTag::Other, mode),
+        ]
+    }
+
+    /// Whether the entity has more than its mode, `ls` marks it with `+`.
+    pub fn exists(path: &Path) -> bool {
+        [ACL_ACCESS, ACL_DEFAULT]
+            .iter()
+            .any(|name| lgetxattr(path, *name, &mut [0u8; 0][..]).is_ok())
+    }
+
+    /// Writes both ACLs, an empty default ACL is removed.
+    ///
+    /// A mask is added when there are named entries without one, as `setfacl` does.
+    pub fn write(&self, path: &Path) -> Result<(), JujikError> {
+        lsetxattr(
+            path,
+            ACL_ACCESS,
+            &Self::bytes(&Self::normalized(&self.access)),
+            XattrFlags::empty(),
+        )?;
+
+        if self.default.is_empty() {
+            match lremovexattr(path, ACL_DEFAULT) {
+                Ok(()) | Err(Errno::NODATA) => {}
+                Err(err) => return Err(JujikError::from(err)),
+            }
+        } else {
+            lsetxattr(
+                path,
+                ACL_DEFAULT,
+                &Self::bytes(&Self::normalized(&self.default)),
+                XattrFlags::empty(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn read(path: &Path, name: &str) -> Result<Vec<AclEntry>, JujikError> {
+        let size = match lgetxattr(path, name, &mut [0u8; 0][..]) {
+            Ok(size) => size,
+            Err(Errno::NODATA | Errno::NOTSUP) => return Ok(Vec::new()),
+            Err(err) => return Err(JujikError::from(err)),
+        };
+
+        let mut bytes = vec![0u8; size];
+        let size = lgetxattr(path, name, &mut bytes[..])?;
+
+        Self::parse(&bytes[..size])
+    }
+
+    fn parse(bytes: &[u8]) -> Result<Vec<AclEntry>, JujikError> {
+        let invalid = || JujikError::Other("Invalid ACL".to_string());
+
+        let (version, entries) = bytes.split_at_checked(4).ok_or_else(invalid)?;
+
+        if u32::from_le_bytes(version.try_into().map_err(|_| invalid())?) != ACL_VERSION
+            || entries.len() % 8 != 0
+        {
+            return Err(invalid());
+        }
+
+        entries
+            .chunks_exact(8)
+            .map(|entry| {
+                let tag = u16::from_le_bytes([entry[0], entry[1]]);
+                let permissions = u16::from_le_bytes([entry[2], entry[3]]) as u32;
+                let id = u32::from_le_bytes([entry[4], entry[5], entry[6], entry[7]]);
+
+                let tag = match tag {
+                    0x01 => AclTag::UserObj,
+                    0x02 => AclTag::User(id),
+                    0x04 => AclTag::GroupObj,
+                    0x08 => AclTag::Group(id),
+                    0x10 => AclTag::Mask,
+                    0x20 => AclTag::Other,
+                    _ => return Err(invalid()),
+                };
+
+                Ok(AclEntry::new(tag, permissions))
+            })
+            .collect()
+    }
+
+    fn bytes(entries: &[AclEntry]) -> Vec<u8> {
+        let mut bytes = ACL_VERSION.to_le_bytes().to_vec();
+
+        for entry in entries {
+            let (tag, id): (u16, u32) = match entry.tag {
+                AclTag::UserObj => (0x01, ACL_UNDEFINED_ID),
+                AclTag::User(uid) => (0x02, uid),
+                AclTag::GroupObj => (0x04, ACL_UNDEFINED_ID),
+                AclTag::Group(gid) => (0x08, gid),
+                AclTag::Mask => (0x10, ACL_UNDEFINED_ID),
+                AclTag::Other => (0x20, ACL_UNDEFINED_ID),
+            };
+
+            bytes.extend(tag.to_le_bytes());
+            bytes.extend((entry.permissions as u16).to_le_bytes());
+            bytes.extend(id.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    fn normalized(entries: &[AclEntry]) -> Vec<AclEntry> {
+        let mut entries = entries.to_vec();
+        entries.sort_by_key(|e| e.tag);
+        entries.dedup_by_key(|e| e.tag);
+
+        let named = entries
+            .iter()
+            .any(|e| matches!(e.tag, AclTag::User(_) | AclTag::Group(_)));
+
+        if named && !entries.iter().any(|e| e.tag == AclTag::Mask) {
+            let mask = entries
+                .iter()
+                .filter(|e| !matches!(e.tag, AclTag::UserObj | AclTag::Other))
+                .fold(0, |mask, e| mask | e.permissions);
+
+            entries.push(AclEntry::new(AclTag::Mask, mask));
+            entries.sort_by_key(|e| e.tag);
+        }
+
+        entries
+    }
+}
+
+impl AclEntry {
+    pub fn new(tag: AclTag, permissions: u32) -> Self {
+        Self {
+            tag,
+            permissions: permissions & 0o7,
+        }
+    }
+
+    pub fn is_named(&self) -> bool {
+        matches!(self.tag, AclTag::User(_) | AclTag::Group(_))
+    }
+}
+
+/// Same as `getfacl`: `user:name:rwx`, numeric when the id has no name.
+impl Display for AclEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}{}{}",
+            self.tag,
+            if self.permissions & 0o4 != 0 {
+                "r"
+            } else {
+                "-"
+            },
+            if self.permissions & 0o2 != 0 {
+                "w"
+            } else {
+                "-"
+            },
+            if self.permissions & 0o1 != 0 {
+                "x"
+            } else {
+                "-"
+            },
+        )
+    }
+}
+
+impl Display for AclTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AclTag::UserObj => write!(f, "user:"),
+            AclTag::User(uid) => write!(
+                f,
+                "user:{}",
+                User::from_uid(Uid::from_raw(*uid))
+                    .ok()
+                    .flatten()
+                    .map(|u| u.name)
+                    .unwrap_or(uid.to_string())
+            ),
+            AclTag::GroupObj => write!(f, "group:"),
+            AclTag::Group(gid) => write!(
+                f,
+                "group:{}",
+                Group::from_gid(Gid::from_raw(*gid))
+                    .ok()
+                    .flatten()
+                    .map(|g| g.name)
+                    .unwrap_or(gid.to_string())
+            ),
+            AclTag::Mask => write!(f, "mask:"),
+            AclTag::Other => write!(f, "other:"),
+        }
+    }
+}
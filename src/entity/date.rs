@@ -14,10 +14,27 @@ impl EntityDate {
         }
     }
 
+    pub fn from_datetime(date: DateTime<Utc>) -> Self {
+        Self { date }
+    }
+
+    pub fn from_timestamp(secs: i64, nsecs: i64) -> Self {
+        Self {
+            date: Utc
+                .timestamp_opt(secs, nsecs as u32)
+                .single()
+                .unwrap_or_default(),
+        }
+    }
+
     pub fn now() -> Self {
         Self { date: Utc::now() }
     }
 
+    pub fn is_none(&self) -> bool {
+        self.date == DateTime::<Utc>::default()
+    }
+
     fn system_time_to_datetime(t: SystemTime) -> DateTime<Utc> {
         if let Some(date_time) = t.duration_since(SystemTime::UNIX_EPOCH).ok().and_then(|d| {
             Utc.timestamp_opt(d.as_secs() as i64, d.subsec_nanos())
@@ -29,8 +46,17 @@ impl EntityDate {
         }
     }
 
+    pub fn date(&self) -> DateTime<Utc> {
+        self.date
+    }
+
+    /// "Unknown" when the filesystem does not record the date.
     pub fn date_str(&self) -> String {
-        self.date.format("%d/%m/%Y").to_string()
+        if self.is_none() {
+            "Unknown".to_string()
+        } else {
+            self.date.format("%d/%m/%Y").to_string()
+        }
     }
 }
 
@@ -0,0 +1,200 @@
+use crate::entity::size::EntitySize;
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    fs::{Metadata, read_dir, symlink_metadata},
+    os::linux::fs::MetadataExt,
+    path::Path,
+};
+
+// dev, ino
+type DirSizeId = (u64, u64);
+// mtime, mtime_nsec
+type DirSizeStamp = (i64, i64);
+// apparent size, allocated size
+type DirSizeTotal = (u64, u64);
+
+#[derive(Debug)]
+struct DirSizeEntry {
+    stamp: DirSizeStamp,
+    // files right in the directory with a single link, and the ones with more
+    files: DirSizeTotal,
+    file_links: Vec<(DirSizeId, DirSizeTotal)>,
+    // names of the subdirectories on the same filesystem
+    dirs: Vec<OsString>,
+    // a file with more than one link counts in the first directory the measurement met it
+    total: DirSizeTotal,
+}
+
+/// Sizes of directory trees, kept until a directory in the tree is modified or invalidated.
+///
+/// Every directory met during a walk is remembered, so opening a subdirectory
+/// of a measured one costs nothing. A directory is read again only when its
+/// own mtime changed, the others are checked with one `lstat` each.
+#[derive(Debug, Default)]
+pub struct EntitysDirSizes {
+    sizes: HashMap<DirSizeId, DirSizeEntry>,
+}
+
+impl EntitysDirSizes {
+    /// Apparent and allocated size of `path` if no directory under it changed since it was measured.
+    pub fn cached(&self, path: &Path) -> Option<(EntitySize, EntitySize)> {
+        let metadata = symlink_metadata(path).ok()?;
+
+        if self.fresh(path, &metadata) {
+            self.sizes
+                .get(&Self::id(&metadata))
+                .map(|entry| Self::sizes(entry.total))
+        } else {
+            None
+        }
+    }
+
+    /// Apparent and allocated size of the files under `path`, `None` when it is not a directory.
+    ///
+    /// Mount points under `path` are not crossed.
+    pub fn size(&mut self, path: &Path) -> Option<(EntitySize, EntitySize)> {
+        let metadata = symlink_metadata(path).ok()?;
+
+        if metadata.is_dir() {
+            // One set for the whole measurement, each hard linked file counts once in it
+            let total = self.walk(path, &metadata, &mut HashSet::new());
+
+            Some(Self::sizes(total))
+        } else {
+            None
+        }
+    }
+
+    /// Forgets `path` and every directory above it, their totals include it.
+    pub fn invalidate(&mut self, path: &Path) {
+        for ancestor in path.ancestors() {
+            if let Ok(metadata) = symlink_metadata(ancestor) {
+                self.sizes.remove(&Self::id(&metadata));
+            }
+        }
+    }
+
+    // Whether the directory and every subdirectory it had keep the mtime they were read with
+    fn fresh(&self, path: &Path, metadata: &Metadata) -> bool {
+        let Some(entry) = self.sizes.get(&Self::id(metadata)) else {
+            return false;
+        };
+
+        entry.stamp == Self::stamp(metadata)
+            && entry.dirs.iter().all(|name| {
+                let path = path.join(name);
+
+                symlink_metadata(&path).is_ok_and(|metadata| self.fresh(&path, &metadata))
+            })
+    }
+
+    // Links are not followed, unreadable directories count as empty.
+    // `seen` holds the files with more than one link met so far in the measurement.
+    fn walk(
+        &mut self,
+        path: &Path,
+        metadata: &Metadata,
+        seen: &mut HashSet<DirSizeId>,
+    ) -> DirSizeTotal {
+        let (id, stamp) = (Self::id(metadata), Self::stamp(metadata));
+
+        // A file changed deep down only touches the mtime of its own directory,
+        // so the subdirectories are walked even when this one is unchanged
+        let (files, file_links, dirs) = match self.sizes.get(&id) {
+            Some(entry) if entry.stamp == stamp => {
+                (entry.files, entry.file_links.clone(), entry.dirs.clone())
+            }
+            _ => Self::read(path, metadata.st_dev()),
+        };
+
+        let mut total = files;
+
+        for (link, size) in &file_links {
+            if seen.insert(*link) {
+                total.0 += size.0;
+                total.1 += size.1;
+            }
+        }
+
+        for name in &dirs {
+            let path = path.join(name);
+
+            let Ok(metadata) = symlink_metadata(&path) else {
+                continue;
+            };
+
+            if !metadata.is_dir() {
+                continue;
+            }
+
+            let child = self.walk(&path, &metadata, seen);
+
+            total.0 += child.0;
+            total.1 += child.1;
+        }
+
+        self.sizes.insert(
+            id,
+            DirSizeEntry {
+                stamp,
+                files,
+                file_links,
+                dirs,
+                total,
+            },
+        );
+
+        total
+    }
+
+    // Files with a single link, files with more and the subdirectories on `dev`
+    fn read(
+        path: &Path,
+        dev: u64,
+    ) -> (DirSizeTotal, Vec<(DirSizeId, DirSizeTotal)>, Vec<OsString>) {
+        let mut files: DirSizeTotal = (0, 0);
+        let mut links = Vec::new();
+        let mut dirs = Vec::new();
+
+        if let Ok(read_dir) = read_dir(path) {
+            for dir_entry in read_dir.flatten() {
+                let Ok(metadata) = dir_entry.metadata() else {
+                    continue;
+                };
+
+                // Another filesystem mounted here, like `du -x`
+                if metadata.st_dev() != dev {
+                    continue;
+                }
+
+                if metadata.is_dir() {
+                    dirs.push(dir_entry.file_name());
+                } else {
+                    let size = (metadata.len(), metadata.st_blocks() * 512);
+
+                    if metadata.st_nlink() < 2 {
+                        files.0 += size.0;
+                        files.1 += size.1;
+                    } else {
+                        links.push((Self::id(&metadata), size));
+                    }
+                }
+            }
+        }
+
+        (files, links, dirs)
+    }
+
+    fn sizes((size, allocated): DirSizeTotal) -> (EntitySize, EntitySize) {
+        (EntitySize::new(size), EntitySize::new(allocated))
+    }
+
+    fn id(metadata: &Metadata) -> DirSizeId {
+        (metadata.st_dev(), metadata.st_ino())
+    }
+
+    fn stamp(metadata: &Metadata) -> DirSizeStamp {
+        (metadata.st_mtime(), metadata.st_mtime_nsec())
+    }
+}
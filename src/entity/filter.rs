@@ -0,0 +1,100 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const IGNORE_FILES: [&str; 2] = [".gitignore", ".ignore"];
+
+/// Which entitys of a directory are listed.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct EntitysFilter {
+    pub show_hidden: bool,
+    // `.gitignore` and `.ignore` files
+    pub ignore: bool,
+}
+
+/// Ignore files that apply to a directory, the deepest first.
+#[derive(Debug, Default, Clone)]
+pub struct EntitysIgnore {
+    gitignores: Vec<Gitignore>,
+}
+
+impl EntitysFilter {
+    /// Ignore files of `dir` and of its parents up to the repository root.
+    pub fn ignore_files(&self, dir: &Path) -> EntitysIgnore {
+        if !self.ignore {
+            return EntitysIgnore::default();
+        }
+
+        let mut gitignores = Vec::new();
+
+        for dir in dir.ancestors() {
+            if let Some(gitignore) = EntitysIgnore::read(dir) {
+                gitignores.push(gitignore);
+            }
+
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+
+        EntitysIgnore { gitignores }
+    }
+
+    pub fn allows(&self, path: &Path, is_dir: bool, ignore: &EntitysIgnore) -> bool {
+        (self.show_hidden || !Self::is_hidden(path))
+            && !(self.ignore && ignore.ignores(path, is_dir))
+    }
+
+    pub fn is_hidden(path: &Path) -> bool {
+        path.file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+    }
+}
+
+impl EntitysIgnore {
+    /// Adds the ignore files of `dir`, a subdirectory met during a walk.
+    pub fn child(&self, dir: &Path) -> Self {
+        let mut gitignores = self.gitignores.clone();
+
+        if let Some(gitignore) = Self::read(dir) {
+            gitignores.insert(0, gitignore);
+        }
+
+        Self { gitignores }
+    }
+
+    // The deepest file that says something decides, `!pattern` lets a path back in
+    pub fn ignores(&self, path: &Path, is_dir: bool) -> bool {
+        self.gitignores
+            .iter()
+            .map(|gitignore| gitignore.matched(path, is_dir))
+            .find(|m| !m.is_none())
+            .is_some_and(|m| m.is_ignore())
+    }
+
+    fn read(dir: &Path) -> Option<Gitignore> {
+        let mut builder = GitignoreBuilder::new(dir);
+        let mut found = false;
+
+        for name in IGNORE_FILES {
+            let file = dir.join(name);
+
+            if file.is_file() {
+                found = true;
+                // A bad line does not drop the rest of the file
+                let _ = builder.add(file);
+            }
+        }
+
+        if found { builder.build().ok() } else { None }
+    }
+}
+
+impl Default for EntitysFilter {
+    fn default() -> Self {
+        Self {
+            show_hidden: true,
+            ignore: false,
+        }
+    }
+}
@@ -1,17 +1,34 @@
 use std::{
     collections::VecDeque,
-    fs::{read_dir, symlink_metadata},
-    path::PathBuf,
+    fs::{Metadata, ReadDir, read_dir, symlink_metadata},
+    mem,
+    os::linux::fs::MetadataExt,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
 };
 
 use crate::error::JujikError;
 
 use super::{
-    Entity, date::EntityDate, kind::EntityKind, owner::EntityOwners, permission::EntityPermissions,
+    Entity,
+    date::EntityDate,
+    filter::{EntitysFilter, EntitysIgnore},
+    kind::EntityKind,
+    owner::EntityOwners,
+    permission::EntityPermissions,
     size::EntitySize,
 };
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+/// Search parameters of a Find tab.
+///
+/// Every filter has an "unset" value that matches any entity:
+/// an empty string for `regex`, `name` and `extension`, `EntityKind::Unknown` for `kind`,
+/// mode `0` for `permissions`, an empty username/groupname for `owners`,
+/// `EntitySize::default()` and `EntityDate::default()` for either bound of `size` and `date`.
+/// `filter` also keeps the walk out of ignored directories.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct FindParameters {
     pub regex: String,
@@ -23,49 +40,236 @@ pub struct FindParameters {
     pub owners: EntityOwners,
     pub size: (EntitySize, EntitySize),
     pub date: (EntityDate, EntityDate),
+    #[serde(default)]
+    pub filter: EntitysFilter,
+    // mount points under `path` are skipped
+    #[serde(default)]
+    pub same_filesystem: bool,
+}
+
+const FIND_BATCH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Compiled form of `FindParameters` used to test entitys during the walk.
+pub struct EntitysMatcher {
+    parameters: FindParameters,
+    regex: Option<Regex>,
+}
+
+/// Breadth first walk under a directory, yields every entry the filter allows
+/// with its `lstat`. Links are not followed and unreadable directories are skipped.
+pub struct EntitysWalk {
+    filter: EntitysFilter,
+    // every directory keeps the ignore files of its parents
+    read_dirs: VecDeque<(PathBuf, EntitysIgnore)>,
+    current: Option<(ReadDir, EntitysIgnore)>,
+    dirs: usize,
+    // device of the root when the walk stays on its filesystem
+    dev: Option<u64>,
 }
 
+#[derive(Default, Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct FindProgress {
+    pub dirs: usize,
+    pub matches: usize,
+    #[serde(skip)]
+    pub running: bool,
+}
+
+// `id` ties the batches of a background search to its tab, it is not kept across restarts
 #[derive(Default, Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct EntitysFinder {
+    #[serde(skip)]
+    id: usize,
     parameters: FindParameters,
+    progress: FindProgress,
     entitys: Vec<Entity>,
 }
 
+impl EntitysMatcher {
+    pub fn new(parameters: FindParameters) -> Result<Self, JujikError> {
+        let regex = if parameters.regex.is_empty() {
+            None
+        } else {
+            Some(Regex::new(parameters.regex.as_str())?)
+        };
+
+        Ok(Self { parameters, regex })
+    }
+
+    pub fn matches(&self, entity: &Entity) -> bool {
+        self.match_regex(entity)
+            && self.match_name(entity)
+            && self.match_extension(entity)
+            && self.match_kind(entity)
+            && self.match_permissions(entity)
+            && self.match_owners(entity)
+            && self.match_size(entity)
+            && self.match_date(entity)
+    }
+
+    // Matched against the full file name, extension included
+    fn match_regex(&self, entity: &Entity) -> bool {
+        if let Some(regex) = &self.regex {
+            regex.is_match(entity.name_with_extension().as_str())
+        } else {
+            true
+        }
+    }
+
+    fn match_name(&self, entity: &Entity) -> bool {
+        self.parameters.name.is_empty() || entity.name().contains(&self.parameters.name)
+    }
+
+    // "None" selects entitys without extension, the same way `Entity::ghost` reads it
+    fn match_extension(&self, entity: &Entity) -> bool {
+        let extension = self.parameters.extension.trim_start_matches('.');
+
+        extension.is_empty() || entity.extension_str().eq_ignore_ascii_case(extension)
+    }
+
+    fn match_kind(&self, entity: &Entity) -> bool {
+        self.parameters.kind == EntityKind::Unknown || self.parameters.kind.eq(entity.kind())
+    }
+
+    // Every bit set in the parameters must be set on the entity, like `find -perm -mode`
+    fn match_permissions(&self, entity: &Entity) -> bool {
+        let mode = self.parameters.permissions.mode();
+
+        entity.permissions().mode() & mode == mode
+    }
+
+    fn match_owners(&self, entity: &Entity) -> bool {
+        let owners = &self.parameters.owners;
+
+        (owners.username().is_empty() || owners.uid() == entity.owners().uid())
+            && (owners.groupname().is_empty() || owners.gid() == entity.owners().gid())
+    }
+
+    fn match_size(&self, entity: &Entity) -> bool {
+        let (from, to) = &self.parameters.size;
+
+        (from.is_none() || entity.size() >= from) && (to.is_none() || entity.size() <= to)
+    }
+
+    fn match_date(&self, entity: &Entity) -> bool {
+        let (from, to) = &self.parameters.date;
+
+        (from.is_none() || entity.modification() >= from)
+            && (to.is_none() || entity.modification() <= to)
+    }
+}
+
 impl EntitysFinder {
+    pub fn new(id: usize, parameters: FindParameters) -> Result<Self, JujikError> {
+        EntitysMatcher::new(parameters.clone())?;
+        Self::check_path(&parameters)?;
+
+        Ok(Self {
+            id,
+            parameters,
+            progress: FindProgress {
+                running: true,
+                ..Default::default()
+            },
+            entitys: Vec::new(),
+        })
+    }
+
     pub fn find(parameters: FindParameters) -> Result<Self, JujikError> {
         let mut entitys: Vec<Entity> = Vec::new();
-        let mut read_dirs: VecDeque<PathBuf> = VecDeque::new();
-
-        entitys.push(Entity::new(parameters.path.clone())?);
-        read_dirs.push_back(parameters.path.clone());
-
-        while let Some(pathbuf) = read_dirs.pop_front() {
-            if let Ok(metadata) = symlink_metadata(pathbuf.as_path()) {
-                if metadata.is_dir() {
-                    if let Ok(entity) = Entity::new(pathbuf.clone()) {
-                        entitys.push(entity);
-                    }
-                    if let Ok(read_dir) = read_dir(pathbuf.as_path()) {
-                        for dir_entry in read_dir {
-                            if let Ok(dir_entry) = dir_entry {
-                                read_dirs.push_back(dir_entry.path());
-                            }
-                        }
-                    }
-                } else if metadata.is_file() {
-                    if let Ok(entity) = Entity::new(pathbuf) {
-                        entitys.push(entity);
-                    }
-                }
-            }
-        }
+
+        let progress = Self::search(&parameters, &AtomicBool::new(false), |batch, _| {
+            entitys.extend(batch);
+            true
+        })?;
 
         Ok(Self {
+            id: 0,
             parameters,
+            progress,
             entitys,
         })
     }
 
+    /// Walks `parameters.path` breadth first and hands the matches over in batches.
+    ///
+    /// The walk ends when `stop` is set or `on_batch` returns `false`,
+    /// the last batch always has `running` unset.
+    pub fn search<F>(
+        parameters: &FindParameters,
+        stop: &AtomicBool,
+        mut on_batch: F,
+    ) -> Result<FindProgress, JujikError>
+    where
+        F: FnMut(Vec<Entity>, FindProgress) -> bool,
+    {
+        let matcher = EntitysMatcher::new(parameters.clone())?;
+        let mut progress = FindProgress {
+            running: true,
+            ..Default::default()
+        };
+        let mut batch: Vec<Entity> = Vec::new();
+        let mut batch_time = Instant::now();
+
+        Self::check_path(parameters)?;
+        let mut walk = EntitysWalk::new(&parameters.path, &parameters.filter)
+            .with_same_filesystem(parameters.same_filesystem);
+
+        while let Some((pathbuf, metadata)) = walk.next() {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            if let Some(entity) = Entity::from_metadata(&pathbuf, &metadata)
+                .ok()
+                .filter(|entity| matcher.matches(entity))
+            {
+                progress.matches += 1;
+                batch.push(entity);
+            }
+
+            if batch_time.elapsed() >= FIND_BATCH_INTERVAL {
+                progress.dirs = walk.dirs();
+
+                if !on_batch(mem::take(&mut batch), progress.clone()) {
+                    break;
+                }
+
+                batch_time = Instant::now();
+            }
+        }
+
+        progress.dirs = walk.dirs();
+        progress.running = false;
+        on_batch(batch, progress.clone());
+
+        Ok(progress)
+    }
+
+    fn check_path(parameters: &FindParameters) -> Result<(), JujikError> {
+        if symlink_metadata(parameters.path.as_path())?.is_dir() {
+            Ok(())
+        } else {
+            Err(JujikError::Other(format!(
+                "Path is not directory:\n{:?}",
+                parameters.path
+            )))
+        }
+    }
+
+    pub fn push(&mut self, entitys: Vec<Entity>, progress: FindProgress) {
+        self.entitys.extend(entitys);
+        self.progress = progress;
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn progress(&self) -> &FindProgress {
+        &self.progress
+    }
+
     pub fn parameters(&self) -> FindParameters {
         self.parameters.clone()
     }
@@ -73,6 +277,88 @@ impl EntitysFinder {
     pub fn entitys(&self) -> Vec<Entity> {
         self.entitys.clone()
     }
+
+    pub fn entitys_mut(&mut self) -> &mut Vec<Entity> {
+        &mut self.entitys
+    }
+}
+
+impl EntitysWalk {
+    pub fn new(path: &Path, filter: &EntitysFilter) -> Self {
+        Self {
+            filter: filter.clone(),
+            read_dirs: VecDeque::from([(path.to_path_buf(), filter.ignore_files(path))]),
+            current: None,
+            dirs: 0,
+            dev: None,
+        }
+    }
+
+    /// Skips the entries on another filesystem than the root, like `find -xdev`.
+    pub fn with_same_filesystem(mut self, same_filesystem: bool) -> Self {
+        self.dev = self
+            .read_dirs
+            .front()
+            .filter(|_| same_filesystem)
+            .and_then(|(pathbuf, _)| symlink_metadata(pathbuf).ok())
+            .map(|metadata| metadata.st_dev());
+        self
+    }
+
+    /// Directories opened so far, the root included.
+    pub fn dirs(&self) -> usize {
+        self.dirs
+    }
+}
+
+impl Iterator for EntitysWalk {
+    type Item = (PathBuf, Metadata);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some((read_dir, ignore)) = &mut self.current else {
+                let (pathbuf, ignore) = self.read_dirs.pop_front()?;
+                self.dirs += 1;
+                self.current = read_dir(pathbuf).ok().map(|read_dir| (read_dir, ignore));
+                continue;
+            };
+
+            let Some(dir_entry) = read_dir.next() else {
+                self.current = None;
+                continue;
+            };
+
+            let Ok(dir_entry) = dir_entry else {
+                continue;
+            };
+
+            let pathbuf = dir_entry.path();
+            let Ok(metadata) = symlink_metadata(&pathbuf) else {
+                continue;
+            };
+
+            if !self.filter.allows(&pathbuf, metadata.is_dir(), ignore) {
+                continue;
+            }
+
+            // A mount point is on the filesystem mounted there
+            if self.dev.is_some_and(|dev| metadata.st_dev() != dev) {
+                continue;
+            }
+
+            if metadata.is_dir() {
+                let ignore = if self.filter.ignore {
+                    ignore.child(&pathbuf)
+                } else {
+                    EntitysIgnore::default()
+                };
+
+                self.read_dirs.push_back((pathbuf.clone(), ignore));
+            }
+
+            return Some((pathbuf, metadata));
+        }
+    }
 }
 
 impl Default for FindParameters {
@@ -87,6 +373,8 @@ impl Default for FindParameters {
             owners: EntityOwners::default(),
             size: (EntitySize::default(), EntitySize::default()),
             date: (EntityDate::default(), EntityDate::default()),
+            filter: EntitysFilter::default(),
+            same_filesystem: false,
         }
     }
 }
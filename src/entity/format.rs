@@ -0,0 +1,205 @@
+use super::{date::EntityDate, size::EntitySize};
+use crate::error::JujikError;
+use chrono::{DateTime, Local, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Write};
+
+const DEFAULT_DATE_PATTERN: &str = "%d/%m/%Y";
+const IEC_UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const SI_UNITS: [&str; 6] = ["kB", "MB", "GB", "TB", "PB", "EB"];
+const RELATIVE_UNITS: [(i64, &str); 6] = [
+    (365 * 24 * 60 * 60, "year"),
+    (30 * 24 * 60 * 60, "month"),
+    (24 * 60 * 60, "day"),
+    (60 * 60, "hour"),
+    (60, "minute"),
+    (1, "second"),
+];
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum SizeUnits {
+    // powers of 1024, KiB
+    #[default]
+    Iec,
+    // powers of 1000, kB
+    Si,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum DateZone {
+    #[default]
+    Utc,
+    Local,
+}
+
+/// How sizes and dates of entitys are written.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntitysFormat {
+    pub units: SizeUnits,
+    pub exact_bytes: bool,
+    // strftime pattern, an invalid one falls back to the default
+    pub date_pattern: String,
+    pub date_zone: DateZone,
+    pub relative_dates: bool,
+}
+
+impl EntitysFormat {
+    pub fn size(&self, size: &EntitySize) -> String {
+        if size.is_none() {
+            return "None".to_string();
+        }
+
+        let size_byte = size.size_byte();
+        let (base, units) = match self.units {
+            SizeUnits::Iec => (1024.0, IEC_UNITS),
+            SizeUnits::Si => (1000.0, SI_UNITS),
+        };
+
+        if self.exact_bytes || (size_byte as f64) < base {
+            return format!("{} B", size_byte);
+        }
+
+        let mut value = size_byte as f64 / base;
+        let mut unit = 0;
+
+        while value >= base && unit + 1 < units.len() {
+            value /= base;
+            unit += 1;
+        }
+
+        format!("{:.2} {}", value, units[unit])
+    }
+
+    /// `size` in the largest unit that holds it whole, for fields that are read back
+    /// with `parse_size`. Empty when there is no size.
+    pub fn size_exact(&self, size: &EntitySize) -> String {
+        if size.is_none() {
+            return String::new();
+        }
+
+        let size_byte = size.size_byte();
+        let (base, units) = match self.units {
+            SizeUnits::Iec => (1024, IEC_UNITS),
+            SizeUnits::Si => (1000, SI_UNITS),
+        };
+
+        let mut value = size_byte;
+        let mut unit = None;
+
+        while value != 0 && value.is_multiple_of(base) && unit.is_none_or(|u| u + 1 < units.len()) {
+            value /= base;
+            unit = Some(unit.map_or(0, |u| u + 1));
+        }
+
+        match unit {
+            Some(unit) => format!("{} {}", value, units[unit]),
+            None => format!("{} B", size_byte),
+        }
+    }
+
+    /// Reads a size like "1.5 MiB" or "200 kB", case does not matter.
+    ///
+    /// IEC suffixes are powers of 1024 and SI ones powers of 1000,
+    /// a bare "K", "M"... follows `units`. An empty value is no size.
+    pub fn parse_size(&self, value: &str) -> Result<EntitySize, JujikError> {
+        let value = value.trim();
+
+        if value.is_empty() {
+            return Ok(EntitySize::default());
+        }
+
+        let split = value
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(split);
+        let unit = unit.trim().to_lowercase();
+
+        let find = |units: [&str; 6]| units.iter().position(|u| u.to_lowercase() == unit);
+        let prefix = |u: &&str| u[..1].to_lowercase() == unit;
+
+        let multiplier = if unit.is_empty() || unit == "b" {
+            1.0
+        } else if let Some(power) = find(IEC_UNITS) {
+            1024f64.powi(power as i32 + 1)
+        } else if let Some(power) = find(SI_UNITS) {
+            1000f64.powi(power as i32 + 1)
+        } else if let Some(power) = SI_UNITS.iter().position(prefix) {
+            match self.units {
+                SizeUnits::Iec => 1024f64.powi(power as i32 + 1),
+                SizeUnits::Si => 1000f64.powi(power as i32 + 1),
+            }
+        } else {
+            return Err(JujikError::Other(format!(
+                "Can not parse size unit: {}",
+                unit
+            )));
+        };
+
+        match number.parse::<f64>() {
+            Ok(number) => Ok(EntitySize::new((number * multiplier).round() as u64)),
+            Err(_) => Err(JujikError::Other(format!("Can not parse size: {}", value))),
+        }
+    }
+
+    /// "Unknown" when the filesystem does not record the date.
+    pub fn date(&self, date: &EntityDate) -> String {
+        if date.is_none() {
+            "Unknown".to_string()
+        } else if self.relative_dates {
+            Self::relative(date.date(), Utc::now())
+        } else {
+            match self.date_zone {
+                DateZone::Utc => self.pattern(&date.date()),
+                DateZone::Local => self.pattern(&date.date().with_timezone(&Local)),
+            }
+        }
+    }
+
+    // A bad pattern only shows up while formatting, so the default is tried after it
+    fn pattern<Tz: TimeZone>(&self, date: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: Display,
+    {
+        let mut text = String::new();
+
+        if write!(text, "{}", date.format(&self.date_pattern)).is_err() {
+            text = date.format(DEFAULT_DATE_PATTERN).to_string();
+        }
+
+        text
+    }
+
+    fn relative(date: DateTime<Utc>, now: DateTime<Utc>) -> String {
+        let secs = (now - date).num_seconds();
+
+        if secs.abs() < 60 {
+            return "just now".to_string();
+        }
+
+        let (count, unit) = RELATIVE_UNITS
+            .iter()
+            .map(|(unit_secs, unit)| (secs.abs() / unit_secs, unit))
+            .find(|(count, _)| *count > 0)
+            .unwrap_or((0, &"second"));
+        let plural = if count == 1 { "" } else { "s" };
+
+        if secs > 0 {
+            format!("{} {}{} ago", count, unit, plural)
+        } else {
+            format!("in {} {}{}", count, unit, plural)
+        }
+    }
+}
+
+impl Default for EntitysFormat {
+    fn default() -> Self {
+        Self {
+            units: SizeUnits::default(),
+            exact_bytes: false,
+            date_pattern: DEFAULT_DATE_PATTERN.to_string(),
+            date_zone: DateZone::default(),
+            relative_dates: false,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
+use std::{fmt::Display, fs::FileType, os::unix::fs::FileTypeExt};
 
 #[derive(Debug, Default, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub enum EntityKind {
@@ -32,3 +32,25 @@ impl Display for EntityKind {
         )
     }
 }
+
+impl From<FileType> for EntityKind {
+    fn from(file_type: FileType) -> Self {
+        if file_type.is_file() {
+            EntityKind::File
+        } else if file_type.is_dir() {
+            EntityKind::Directory
+        } else if file_type.is_symlink() {
+            EntityKind::Symlink
+        } else if file_type.is_block_device() {
+            EntityKind::Block
+        } else if file_type.is_char_device() {
+            EntityKind::Character
+        } else if file_type.is_fifo() {
+            EntityKind::Pipe
+        } else if file_type.is_socket() {
+            EntityKind::Socket
+        } else {
+            EntityKind::Unknown
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use super::{kind::EntityKind, transfer::EntitysTransfer};
+use crate::error::JujikError;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, canonicalize, metadata, symlink_metadata},
+    os::unix,
+    path::{Path, PathBuf},
+};
+
+/// Where a symbolic link points.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct EntityLink {
+    // as written in the link
+    text: PathBuf,
+    // resolved when it exists, joined to the link directory otherwise
+    target: PathBuf,
+    target_kind: EntityKind,
+    exists: bool,
+}
+
+impl EntityLink {
+    pub fn new(path: &Path) -> Result<Self, JujikError> {
+        let text = fs::read_link(path)?;
+        let joined = path.parent().unwrap_or(Path::new("/")).join(&text);
+
+        match canonicalize(&joined).and_then(|t| metadata(&t).map(|m| (t, m))) {
+            Ok((target, metadata)) => Ok(Self {
+                text,
+                target,
+                target_kind: EntityKind::from(metadata.file_type()),
+                exists: true,
+            }),
+            Err(_) => Ok(Self {
+                text,
+                target: joined,
+                target_kind: EntityKind::Unknown,
+                exists: false,
+            }),
+        }
+    }
+
+    pub fn text(&self) -> &PathBuf {
+        &self.text
+    }
+
+    pub fn text_str(&self) -> String {
+        self.text.to_string_lossy().to_string()
+    }
+
+    pub fn target(&self) -> &PathBuf {
+        &self.target
+    }
+
+    pub fn target_kind(&self) -> &EntityKind {
+        &self.target_kind
+    }
+
+    pub fn exists(&self) -> bool {
+        self.exists
+    }
+
+    /// Points the link at `text`, the new link replaces the old one in a single rename.
+    pub fn retarget(pathbuf: &Path, text: &Path) -> Result<(), JujikError> {
+        if !symlink_metadata(pathbuf)?.is_symlink() {
+            return Err(JujikError::Other(format!(
+                "Path is not a symlink:\n{:?}",
+                pathbuf
+            )));
+        }
+
+        let temp = EntitysTransfer::free_path(&pathbuf.with_file_name(format!(
+            ".{}.link",
+            pathbuf
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default()
+        )));
+
+        unix::fs::symlink(text, &temp)?;
+
+        if let Err(err) = fs::rename(&temp, pathbuf) {
+            let _ = fs::remove_file(&temp);
+
+            return Err(JujikError::from(err));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,413 @@
+use super::kind::EntityKind;
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, File, metadata},
+    io::Read,
+    path::Path,
+    sync::OnceLock,
+};
+
+const MIME_GLOBS: &str = "/usr/share/mime/globs2";
+const SNIFF_LEN: u64 = 512;
+
+const OCTET_STREAM: &str = "application/octet-stream";
+const PLAIN_TEXT: &str = "text/plain";
+
+// offset, bytes, mime
+const MAGIC: &[(usize, &[u8], &str)] = &[
+    (0, b"\x89PNG\r\n\x1a\n", "image/png"),
+    (0, b"\xff\xd8\xff", "image/jpeg"),
+    (0, b"GIF87a", "image/gif"),
+    (0, b"GIF89a", "image/gif"),
+    (8, b"WEBP", "image/webp"),
+    (0, b"\x00\x00\x01\x00", "image/vnd.microsoft.icon"),
+    (0, b"II*\x00", "image/tiff"),
+    (0, b"MM\x00*", "image/tiff"),
+    (0, b"%PDF-", "application/pdf"),
+    (0, b"%!PS", "application/postscript"),
+    (0, b"{\\rtf", "application/rtf"),
+    (0, b"PK\x03\x04", "application/zip"),
+    (0, b"PK\x05\x06", "application/zip"),
+    (0, b"\x1f\x8b", "application/gzip"),
+    (0, b"BZh", "application/x-bzip2"),
+    (0, b"\xfd7zXZ\x00", "application/x-xz"),
+    (0, b"\x28\xb5\x2f\xfd", "application/zstd"),
+    (0, b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (0, b"Rar!\x1a\x07", "application/vnd.rar"),
+    (257, b"ustar", "application/x-tar"),
+    (0, b"\x7fELF", "application/x-executable"),
+    (0, b"\xca\xfe\xba\xbe", "application/x-java"),
+    (0, b"\x00asm", "application/wasm"),
+    (0, b"SQLite format 3\x00", "application/vnd.sqlite3"),
+    (0, b"OggS", "audio/ogg"),
+    (0, b"fLaC", "audio/flac"),
+    (0, b"ID3", "audio/mpeg"),
+    (8, b"WAVE", "audio/x-wav"),
+    (8, b"AVI ", "video/x-msvideo"),
+    (4, b"ftyp", "video/mp4"),
+    (0, b"\x1a\x45\xdf\xa3", "video/x-matroska"),
+    (0, b"wOFF", "font/woff"),
+    (0, b"wOF2", "font/woff2"),
+    (0, b"OTTO", "font/otf"),
+    (0, b"\x00\x01\x00\x00\x00", "font/ttf"),
+];
+
+// Too short to tell text apart, only checked on binary content
+const BINARY_MAGIC: &[(usize, &[u8], &str)] = &[
+    (0, b"BM", "image/bmp"),
+    (0, b"MZ", "application/x-msdownload"),
+];
+
+// Containers whose real type is better told by the name, e.g. `.docx` or `.jar`
+const CONTAINERS: &[&str] = &["application/zip", "application/xml"];
+
+/// A glob of the shared-mime-info database.
+#[derive(Debug, Clone)]
+struct MimeGlob {
+    order: usize,
+    weight: u32,
+    mime: String,
+    pattern: String,
+    case_sensitive: bool,
+}
+
+// Globs without wildcards and `*suffix` ones are looked up, the rest are matched
+#[derive(Debug, Default)]
+struct MimeGlobs {
+    literals: HashMap<String, Vec<MimeGlob>>,
+    suffixes: HashMap<String, Vec<MimeGlob>>,
+    patterns: Vec<MimeGlob>,
+}
+
+/// Media type of an entity, told by its name until the content is sniffed.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
+pub struct EntityMime {
+    mime: String,
+    // the content was read, or the kind alone tells the type
+    #[serde(default)]
+    sniffed: bool,
+}
+
+impl EntityMime {
+    /// From the name only, nothing is opened or followed.
+    pub fn new(path: &Path, kind: &EntityKind) -> Self {
+        match kind {
+            EntityKind::File | EntityKind::Symlink => {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let fallback = if *kind == EntityKind::File {
+                    OCTET_STREAM
+                } else {
+                    "inode/symlink"
+                };
+
+                Self {
+                    mime: Self::glob(&name).unwrap_or_else(|| fallback.to_string()),
+                    sniffed: false,
+                }
+            }
+            _ => Self::sniffed(path, kind),
+        }
+    }
+
+    /// Links are followed, special files get the `inode/*` types.
+    pub fn sniffed(path: &Path, kind: &EntityKind) -> Self {
+        let mime = match kind {
+            EntityKind::Directory => "inode/directory".to_string(),
+            EntityKind::Block => "inode/blockdevice".to_string(),
+            EntityKind::Character => "inode/chardevice".to_string(),
+            EntityKind::Pipe => "inode/fifo".to_string(),
+            EntityKind::Socket => "inode/socket".to_string(),
+            EntityKind::Symlink => match metadata(path) {
+                Ok(metadata) if metadata.is_dir() => "inode/directory".to_string(),
+                Ok(metadata) if metadata.is_file() => Self::sniff(path),
+                Ok(_) => OCTET_STREAM.to_string(),
+                Err(_) => "inode/symlink".to_string(),
+            },
+            EntityKind::File => Self::sniff(path),
+            EntityKind::Unknown => OCTET_STREAM.to_string(),
+        };
+
+        Self {
+            mime,
+            sniffed: true,
+        }
+    }
+
+    pub fn is_sniffed(&self) -> bool {
+        self.sniffed
+    }
+
+    pub fn mime(&self) -> &str {
+        &self.mime
+    }
+
+    /// Whether the content can be shown and edited as text.
+    pub fn is_text(&self) -> bool {
+        self.mime.starts_with("text/")
+            || self.mime.ends_with("+xml")
+            || self.mime.ends_with("+json")
+            || [
+                "application/json",
+                "application/xml",
+                "application/javascript",
+                "application/x-shellscript",
+                "application/x-zerosize",
+            ]
+            .contains(&self.mime.as_str())
+    }
+
+    /// Content first, the name when the content says nothing or only tells a container.
+    fn sniff(path: &Path) -> String {
+        let mut head = Vec::new();
+
+        if let Ok(file) = File::open(path) {
+            let _ = file.take(SNIFF_LEN).read_to_end(&mut head);
+        }
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let glob = Self::glob(&name);
+
+        if head.is_empty() {
+            return glob.unwrap_or_else(|| "application/x-zerosize".to_string());
+        }
+
+        let text = Self::is_text_content(&head);
+
+        match (Self::magic(&head, text), glob) {
+            (Some(magic), Some(glob))
+                if CONTAINERS.contains(&magic)
+                    && (text || !Self::from(glob.as_str()).is_text()) =>
+            {
+                glob
+            }
+            (Some(magic), _) => magic.to_string(),
+            // A binary named as text is still binary
+            (None, Some(glob)) if !text && Self::from(glob.as_str()).is_text() => {
+                OCTET_STREAM.to_string()
+            }
+            (None, Some(glob)) => glob,
+            (None, None) if text => PLAIN_TEXT.to_string(),
+            (None, None) => OCTET_STREAM.to_string(),
+        }
+    }
+
+    fn magic(head: &[u8], text: bool) -> Option<&'static str> {
+        if let Some(mime) = MAGIC
+            .iter()
+            .chain(if text { &[][..] } else { BINARY_MAGIC })
+            .find_map(|(offset, bytes, mime)| {
+                head.get(*offset..offset + bytes.len())
+                    .filter(|h| h == bytes)
+                    .map(|_| *mime)
+            })
+        {
+            return Some(mime);
+        }
+
+        let start = String::from_utf8_lossy(head)
+            .trim_start_matches('\u{feff}')
+            .trim_start()
+            .to_lowercase();
+
+        if start.starts_with("<!doctype html") || start.starts_with("<html") {
+            Some("text/html")
+        } else if start.starts_with("<svg")
+            || (start.starts_with("<?xml") && start.contains("<svg"))
+        {
+            Some("image/svg+xml")
+        } else if start.starts_with("<?xml") {
+            Some("application/xml")
+        } else if start.lines().next().is_some_and(|line| {
+            line.starts_with("#!") && (line.ends_with("sh") || line.contains("sh "))
+        }) {
+            Some("application/x-shellscript")
+        } else {
+            None
+        }
+    }
+
+    fn is_text_content(head: &[u8]) -> bool {
+        if head.starts_with(b"\xef\xbb\xbf")
+            || head.starts_with(b"\xff\xfe")
+            || head.starts_with(b"\xfe\xff")
+        {
+            return true;
+        }
+
+        if head.contains(&0) {
+            return false;
+        }
+
+        match std::str::from_utf8(head) {
+            Ok(_) => true,
+            // Cut in the middle of a character
+            Err(err) => err.error_len().is_none(),
+        }
+    }
+
+    /// Highest weight first, then the longest pattern, then the first in the database.
+    fn glob(name: &str) -> Option<String> {
+        let globs = Self::globs();
+        let lower = name.to_lowercase();
+
+        let literals = globs
+            .literals
+            .get(&lower)
+            .into_iter()
+            .flatten()
+            .filter(|g| !g.case_sensitive || g.pattern == name);
+
+        let suffixes = lower
+            .char_indices()
+            .filter_map(|(idx, _)| globs.suffixes.get(&lower[idx..]))
+            .flatten()
+            .filter(|g| !g.case_sensitive || name.ends_with(&g.pattern[1..]));
+
+        let patterns = globs.patterns.iter().filter(|g| {
+            if g.case_sensitive {
+                Self::matches(&g.pattern, name)
+            } else {
+                Self::matches(&g.pattern, &lower)
+            }
+        });
+
+        literals
+            .chain(suffixes)
+            .chain(patterns)
+            .max_by_key(|g| (g.weight, g.pattern.len(), Reverse(g.order)))
+            .map(|g| g.mime.clone())
+    }
+
+    fn globs() -> &'static MimeGlobs {
+        static GLOBS: OnceLock<MimeGlobs> = OnceLock::new();
+
+        GLOBS.get_or_init(|| {
+            let mut globs = MimeGlobs::default();
+
+            for glob in fs::read_to_string(MIME_GLOBS)
+                .map(|globs| Self::parse_globs(&globs))
+                .unwrap_or_default()
+            {
+                let wildcard = |c: char| matches!(c, '*' | '?' | '[');
+
+                if !glob.pattern.contains(wildcard) {
+                    globs
+                        .literals
+                        .entry(glob.pattern.to_lowercase())
+                        .or_default()
+                        .push(glob);
+                } else if let Some(suffix) = glob.pattern.strip_prefix('*')
+                    && !suffix.contains(wildcard)
+                {
+                    globs
+                        .suffixes
+                        .entry(suffix.to_lowercase())
+                        .or_default()
+                        .push(glob);
+                } else {
+                    globs.patterns.push(glob);
+                }
+            }
+
+            globs
+        })
+    }
+
+    // weight:mime:pattern[:flags]
+    fn parse_globs(globs: &str) -> Vec<MimeGlob> {
+        globs
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .enumerate()
+            .filter_map(|(order, line)| {
+                let mut fields = line.splitn(4, ':');
+
+                let weight = fields.next()?.parse().ok()?;
+                let mime = fields.next()?.to_string();
+                let pattern = fields.next()?.to_string();
+                let case_sensitive = fields
+                    .next()
+                    .is_some_and(|flags| flags.split(',').any(|f| f == "cs"));
+
+                Some(MimeGlob {
+                    order,
+                    weight,
+                    pattern: if case_sensitive {
+                        pattern
+                    } else {
+                        pattern.to_lowercase()
+                    },
+                    mime,
+                    case_sensitive,
+                })
+            })
+            .collect()
+    }
+
+    /// Shell wildcards: `*`, `?` and `[...]` classes with ranges.
+    fn matches(pattern: &str, name: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let name: Vec<char> = name.chars().collect();
+
+        Self::matches_from(&pattern, &name)
+    }
+
+    fn matches_from(pattern: &[char], name: &[char]) -> bool {
+        match pattern.first() {
+            None => name.is_empty(),
+            Some('*') => {
+                (0..=name.len()).any(|skip| Self::matches_from(&pattern[1..], &name[skip..]))
+            }
+            Some('?') => !name.is_empty() && Self::matches_from(&pattern[1..], &name[1..]),
+            Some('[') => {
+                let Some(end) = pattern.iter().skip(1).position(|c| *c == ']') else {
+                    return false;
+                };
+                let class = &pattern[1..end + 1];
+
+                let Some(c) = name.first() else {
+                    return false;
+                };
+
+                let mut matched = false;
+                let mut idx = 0;
+                while idx < class.len() {
+                    if idx + 2 < class.len() && class[idx + 1] == '-' {
+                        matched |= (class[idx]..=class[idx + 2]).contains(c);
+                        idx += 3;
+                    } else {
+                        matched |= class[idx] == *c;
+                        idx += 1;
+                    }
+                }
+
+                matched && Self::matches_from(&pattern[end + 2..], &name[1..])
+            }
+            Some(p) => name.first() == Some(p) && Self::matches_from(&pattern[1..], &name[1..]),
+        }
+    }
+}
+
+impl From<&str> for EntityMime {
+    fn from(mime: &str) -> Self {
+        Self {
+            mime: mime.to_string(),
+            sniffed: true,
+        }
+    }
+}
+
+impl Display for EntityMime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.mime)
+    }
+}
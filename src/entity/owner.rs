@@ -1,9 +1,29 @@
-use std::fmt::{Debug, Display};
+use std::{
+    collections::HashMap,
+    ffi::{CStr, c_char},
+    fmt::{Debug, Display},
+    fs::{self, canonicalize, metadata, symlink_metadata},
+    os::{linux::fs::MetadataExt, unix},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
 
 use crate::error::JujikError;
-use nix::unistd::{Gid, Group, Uid, User, getgid, getuid};
+use nix::{
+    libc,
+    unistd::{Gid, Group, Uid, User, getgid, getuid},
+};
 use serde::{Deserialize, Serialize};
 
+// Names already resolved, a big directory usually has a handful of owners
+static USERNAMES: OnceLock<Mutex<HashMap<u32, String>>> = OnceLock::new();
+static GROUPNAMES: OnceLock<Mutex<HashMap<u32, String>>> = OnceLock::new();
+// getpwent and getgrent walk a cursor shared by the whole process
+static DATABASE: Mutex<()> = Mutex::new(());
+
+// path, (uid, gid) before, (uid, gid) after
+pub type OwnersChange = (PathBuf, (u32, u32), (u32, u32));
+
 #[derive(Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
 pub struct EntityOwners {
     uid: u32,
@@ -14,21 +34,17 @@ pub struct EntityOwners {
 
 impl EntityOwners {
     pub fn new(uid: u32, gid: u32) -> Result<Self, JujikError> {
-        let username = {
-            if let Some(user) = User::from_uid(Uid::from_raw(uid))? {
-                user.name
-            } else {
-                String::new()
-            }
-        };
+        let username = Self::cached(&USERNAMES, uid, |uid| {
+            Ok(User::from_uid(Uid::from_raw(uid))?
+                .map(|user| user.name)
+                .unwrap_or_default())
+        })?;
 
-        let groupname = {
-            if let Some(group) = Group::from_gid(Gid::from_raw(gid))? {
-                group.name
-            } else {
-                String::new()
-            }
-        };
+        let groupname = Self::cached(&GROUPNAMES, gid, |gid| {
+            Ok(Group::from_gid(Gid::from_raw(gid))?
+                .map(|group| group.name)
+                .unwrap_or_default())
+        })?;
 
         Ok(Self {
             uid,
@@ -38,6 +54,27 @@ impl EntityOwners {
         })
     }
 
+    // Failed lookups are not remembered, the next entity asks again
+    fn cached(
+        cache: &OnceLock<Mutex<HashMap<u32, String>>>,
+        id: u32,
+        lookup: impl FnOnce(u32) -> Result<String, JujikError>,
+    ) -> Result<String, JujikError> {
+        let cache = cache.get_or_init(|| Mutex::new(HashMap::new()));
+
+        if let Some(name) = cache.lock().map_err(JujikError::from)?.get(&id) {
+            return Ok(name.clone());
+        }
+
+        let name = lookup(id)?;
+        cache
+            .lock()
+            .map_err(JujikError::from)?
+            .insert(id, name.clone());
+
+        Ok(name)
+    }
+
     pub fn current() -> Result<Self, JujikError> {
         if let Some(user) = User::from_uid(getuid())? {
             if let Some(group) = Group::from_gid(getgid())? {
@@ -98,6 +135,132 @@ impl EntityOwners {
     }
 }
 
+impl EntityOwners {
+    /// (uid, name) of the users NSS knows about, LDAP and SSSD included, sorted by name.
+    pub fn users() -> Vec<(u32, String)> {
+        let _database = DATABASE.lock().unwrap_or_else(|err| err.into_inner());
+        let mut entries = Vec::new();
+
+        // SAFETY: the cursor only moves under `DATABASE`, every entry is copied
+        // before the next call frees it
+        unsafe {
+            libc::setpwent();
+
+            while let Some(passwd) = libc::getpwent().as_ref() {
+                entries.push((passwd.pw_uid, Self::name(passwd.pw_name)));
+            }
+
+            libc::endpwent();
+        }
+
+        Self::sorted(entries)
+    }
+
+    /// (gid, name) of the groups NSS knows about, sorted by name.
+    pub fn groups() -> Vec<(u32, String)> {
+        let _database = DATABASE.lock().unwrap_or_else(|err| err.into_inner());
+        let mut entries = Vec::new();
+
+        // SAFETY: as in `users`
+        unsafe {
+            libc::setgrent();
+
+            while let Some(group) = libc::getgrent().as_ref() {
+                entries.push((group.gr_gid, Self::name(group.gr_name)));
+            }
+
+            libc::endgrent();
+        }
+
+        Self::sorted(entries)
+    }
+
+    // SAFETY: `name` is null or a nul terminated string of a passwd or group entry
+    unsafe fn name(name: *const c_char) -> String {
+        if name.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(name) }
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+
+    // Several sources may list the same entry
+    fn sorted(mut entries: Vec<(u32, String)>) -> Vec<(u32, String)> {
+        entries.sort_by(|e1, e2| e1.1.cmp(&e2.1).then(e1.0.cmp(&e2.0)));
+        entries.dedup();
+
+        entries
+    }
+
+    /// Changes the owner and/or the group of every path, and of everything under
+    /// the directories when `recursive`.
+    ///
+    /// With `follow` a link changes its target, otherwise the link itself.
+    /// Links are never walked into. Returns the changes and the paths that failed.
+    pub fn apply_paths(
+        pathbufs: &[PathBuf],
+        uid: Option<u32>,
+        gid: Option<u32>,
+        recursive: bool,
+        follow: bool,
+    ) -> (Vec<OwnersChange>, Vec<(PathBuf, JujikError)>) {
+        let (mut changes, mut failures) = (Vec::new(), Vec::new());
+        let mut stack: Vec<PathBuf> = pathbufs.iter().rev().cloned().collect();
+
+        while let Some(pathbuf) = stack.pop() {
+            match Self::apply_path(&pathbuf, uid, gid, follow) {
+                Ok((change, is_dir)) => {
+                    if change.1 != change.2 {
+                        changes.push(change);
+                    }
+
+                    if recursive && is_dir {
+                        match fs::read_dir(&pathbuf) {
+                            Ok(read_dir) => {
+                                let mut children: Vec<PathBuf> =
+                                    read_dir.flatten().map(|d| d.path()).collect();
+                                children.sort();
+                                stack.extend(children.into_iter().rev());
+                            }
+                            Err(err) => failures.push((pathbuf, JujikError::from(err))),
+                        }
+                    }
+                }
+                Err(err) => failures.push((pathbuf, err)),
+            }
+        }
+
+        (changes, failures)
+    }
+
+    // The change is made on the path it really touched, so that it can be undone with lchown
+    fn apply_path(
+        pathbuf: &Path,
+        uid: Option<u32>,
+        gid: Option<u32>,
+        follow: bool,
+    ) -> Result<(OwnersChange, bool), JujikError> {
+        let own = symlink_metadata(pathbuf)?;
+        let is_dir = own.is_dir();
+        let (pathbuf, metadata) = if follow && own.is_symlink() {
+            (canonicalize(pathbuf)?, metadata(pathbuf)?)
+        } else {
+            (pathbuf.to_path_buf(), own)
+        };
+
+        let before = (metadata.st_uid(), metadata.st_gid());
+        let after = (uid.unwrap_or(before.0), gid.unwrap_or(before.1));
+
+        if before != after {
+            unix::fs::lchown(&pathbuf, Some(after.0), Some(after.1))?;
+        }
+
+        Ok(((pathbuf, before, after), is_dir))
+    }
+}
+
 impl Display for EntityOwners {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -1,8 +1,10 @@
+use crate::error::JujikError;
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::{Debug, Display},
-    fs::Permissions,
+    fs::{self, Permissions, metadata, symlink_metadata},
     os::unix::fs::PermissionsExt,
+    path::PathBuf,
 };
 
 #[derive(PartialEq, Eq, Clone, Copy)]
@@ -19,6 +21,42 @@ pub enum EntityPermissionsCategory {
     Other,
 }
 
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum EntityPermissionsSpecial {
+    Setuid,
+    Setgid,
+    Sticky,
+}
+
+// path, mode before, mode after
+pub type PermissionsChange = (PathBuf, u32, u32);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ExpressionOperator {
+    Add,
+    Remove,
+    Set,
+}
+
+// who, operator, permissions, `X` given
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ExpressionClause {
+    who: u32,
+    operator: ExpressionOperator,
+    permissions: u32,
+    search: bool,
+}
+
+/// A chmod expression, octal (`0750`) or symbolic (`u+x,go-w`, `a=rX`).
+///
+/// `X` sets execute on directories and on files that already have an execute bit.
+/// Without `u`, `g`, `o` or `a` the clause applies to all, the umask is not used.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EntityPermissionsExpression {
+    Octal(u32),
+    Symbolic(Vec<ExpressionClause>),
+}
+
 #[derive(Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
 pub struct EntityPermissions {
     mode: u32,
@@ -26,7 +64,9 @@ pub struct EntityPermissions {
 
 impl EntityPermissions {
     pub fn new(mode: u32) -> Self {
-        Self { mode: mode & 0o777 }
+        Self {
+            mode: mode & 0o7777,
+        }
     }
 
     pub fn has(
@@ -59,25 +99,54 @@ impl EntityPermissions {
         self.mode &= !(p << s);
     }
 
+    pub fn has_special(&self, special: EntityPermissionsSpecial) -> bool {
+        self.mode & special.mask() != 0
+    }
+
+    pub fn set_special(&mut self, special: EntityPermissionsSpecial) {
+        self.mode |= special.mask();
+    }
+
+    pub fn unset_special(&mut self, special: EntityPermissionsSpecial) {
+        self.mode &= !special.mask();
+    }
+
+    /// Same as `ls -l`, a special bit shows in the execute place, uppercase without execute.
     pub fn symbolic(&self) -> String {
         let u = EntityPermissionsCategory::User;
         let g = EntityPermissionsCategory::Group;
         let o = EntityPermissionsCategory::Other;
         let r = EntityPermissionsKind::Read;
         let w = EntityPermissionsKind::Write;
-        let e = EntityPermissionsKind::Execute;
 
         let ur = if self.has(u, r) { "r" } else { "-" };
         let uw = if self.has(u, w) { "w" } else { "-" };
-        let ue = if self.has(u, e) { "e" } else { "-" };
+        let ux = self.execute(u, EntityPermissionsSpecial::Setuid);
         let gr = if self.has(g, r) { "r" } else { "-" };
         let gw = if self.has(g, w) { "w" } else { "-" };
-        let ge = if self.has(g, e) { "e" } else { "-" };
+        let gx = self.execute(g, EntityPermissionsSpecial::Setgid);
         let or = if self.has(o, r) { "r" } else { "-" };
         let ow = if self.has(o, w) { "w" } else { "-" };
-        let oe = if self.has(o, e) { "e" } else { "-" };
+        let ox = self.execute(o, EntityPermissionsSpecial::Sticky);
 
-        format!("{}{}{}{}{}{}{}{}{}", ur, uw, ue, gr, gw, ge, or, ow, oe)
+        format!("{}{}{}{}{}{}{}{}{}", ur, uw, ux, gr, gw, gx, or, ow, ox)
+    }
+
+    fn execute(
+        &self,
+        categoty: EntityPermissionsCategory,
+        special: EntityPermissionsSpecial,
+    ) -> &'static str {
+        let x = self.has(categoty, EntityPermissionsKind::Execute);
+
+        match (self.has_special(special), x, special) {
+            (false, true, _) => "x",
+            (false, false, _) => "-",
+            (true, true, EntityPermissionsSpecial::Sticky) => "t",
+            (true, false, EntityPermissionsSpecial::Sticky) => "T",
+            (true, true, _) => "s",
+            (true, false, _) => "S",
+        }
     }
 
     pub fn mode(&self) -> u32 {
@@ -85,6 +154,160 @@ impl EntityPermissions {
     }
 }
 
+impl EntityPermissionsExpression {
+    pub fn parse(expression: &str) -> Result<Self, JujikError> {
+        let expression = expression.trim();
+        let invalid =
+            || JujikError::Other(format!("Invalid permissions expression:\n{:?}", expression));
+
+        if !expression.is_empty() && expression.chars().all(|c| c.is_digit(8)) {
+            let mode = u32::from_str_radix(expression, 8).map_err(|_| invalid())?;
+
+            return if mode <= 0o7777 {
+                Ok(Self::Octal(mode))
+            } else {
+                Err(invalid())
+            };
+        }
+
+        let mut clauses = Vec::new();
+
+        for clause in expression.split(',') {
+            let mut chars = clause.chars().peekable();
+            let mut who = 0;
+
+            while let Some(c) = chars.next_if(|c| "ugoa".contains(*c)) {
+                who |= match c {
+                    'u' => 0o4700,
+                    'g' => 0o2070,
+                    'o' => 0o1007,
+                    _ => 0o7777,
+                };
+            }
+
+            if who == 0 {
+                who = 0o7777;
+            }
+
+            if chars.peek().is_none() {
+                return Err(invalid());
+            }
+
+            while let Some(operator) = chars.next() {
+                let operator = match operator {
+                    '+' => ExpressionOperator::Add,
+                    '-' => ExpressionOperator::Remove,
+                    '=' => ExpressionOperator::Set,
+                    _ => return Err(invalid()),
+                };
+                let (mut permissions, mut search) = (0, false);
+
+                while let Some(c) = chars.next_if(|c| "rwxXst".contains(*c)) {
+                    match c {
+                        'r' => permissions |= 0o444,
+                        'w' => permissions |= 0o222,
+                        'x' => permissions |= 0o111,
+                        'X' => search = true,
+                        's' => permissions |= 0o6000,
+                        _ => permissions |= 0o1000,
+                    }
+                }
+
+                clauses.push(ExpressionClause {
+                    who,
+                    operator,
+                    permissions,
+                    search,
+                });
+            }
+        }
+
+        Ok(Self::Symbolic(clauses))
+    }
+
+    pub fn apply(&self, mode: u32, is_dir: bool) -> u32 {
+        match self {
+            Self::Octal(mode) => *mode,
+            Self::Symbolic(clauses) => clauses.iter().fold(mode & 0o7777, |mode, clause| {
+                let mut permissions = clause.permissions;
+
+                if clause.search && (is_dir || mode & 0o111 != 0) {
+                    permissions |= 0o111;
+                }
+
+                let permissions = permissions & clause.who;
+
+                match clause.operator {
+                    ExpressionOperator::Add => mode | permissions,
+                    ExpressionOperator::Remove => mode & !permissions,
+                    ExpressionOperator::Set => (mode & !clause.who) | permissions,
+                }
+            }),
+        }
+    }
+
+    /// Changes the mode of every path, and of everything under the directories when `recursive`.
+    ///
+    /// Links met while walking are left alone. Returns the changes and the paths that failed.
+    pub fn apply_paths(
+        &self,
+        pathbufs: &[PathBuf],
+        recursive: bool,
+    ) -> (Vec<PermissionsChange>, Vec<(PathBuf, JujikError)>) {
+        let (mut changes, mut failures) = (Vec::new(), Vec::new());
+        // path, met while walking
+        let mut stack: Vec<(PathBuf, bool)> =
+            pathbufs.iter().rev().map(|p| (p.clone(), false)).collect();
+
+        while let Some((pathbuf, walked)) = stack.pop() {
+            match self.apply_path(&pathbuf, walked) {
+                Ok(Some((before, after, is_dir))) => {
+                    if before != after {
+                        changes.push((pathbuf.clone(), before, after));
+                    }
+
+                    if recursive && is_dir {
+                        match fs::read_dir(&pathbuf) {
+                            Ok(read_dir) => {
+                                let mut children: Vec<PathBuf> =
+                                    read_dir.flatten().map(|d| d.path()).collect();
+                                children.sort();
+                                stack.extend(children.into_iter().rev().map(|c| (c, true)));
+                            }
+                            Err(err) => failures.push((pathbuf, JujikError::from(err))),
+                        }
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => failures.push((pathbuf, err)),
+            }
+        }
+
+        (changes, failures)
+    }
+
+    // `None` for a link met while walking
+    fn apply_path(
+        &self,
+        pathbuf: &PathBuf,
+        walked: bool,
+    ) -> Result<Option<(u32, u32, bool)>, JujikError> {
+        if walked && symlink_metadata(pathbuf)?.is_symlink() {
+            return Ok(None);
+        }
+
+        let metadata = metadata(pathbuf)?;
+        let before = metadata.permissions().mode() & 0o7777;
+        let after = self.apply(before, metadata.is_dir());
+
+        if before != after {
+            fs::set_permissions(pathbuf, Permissions::from_mode(after))?;
+        }
+
+        Ok(Some((before, after, metadata.is_dir())))
+    }
+}
+
 impl EntityPermissionsCategory {
     pub fn mask(&self) -> u32 {
         match self {
@@ -113,6 +336,16 @@ impl EntityPermissionsKind {
     }
 }
 
+impl EntityPermissionsSpecial {
+    pub fn mask(&self) -> u32 {
+        match self {
+            EntityPermissionsSpecial::Setuid => 0o4000,
+            EntityPermissionsSpecial::Setgid => 0o2000,
+            EntityPermissionsSpecial::Sticky => 0o1000,
+        }
+    }
+}
+
 impl Into<Permissions> for EntityPermissions {
     fn into(self) -> Permissions {
         Permissions::from_mode(self.mode)
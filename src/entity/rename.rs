@@ -0,0 +1,301 @@
+use super::{Entity, transfer::EntitysTransfer};
+use crate::error::JujikError;
+use chrono::Local;
+use regex::{Captures, NoExpand, Regex};
+use std::{
+    collections::HashSet,
+    fmt::{Display, Write},
+    fs::{self, symlink_metadata},
+    path::PathBuf,
+};
+
+const RENAME_DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum RenameCase {
+    #[default]
+    Keep,
+    Lower,
+    Upper,
+    Title,
+}
+
+/// Parameters of a batch rename, applied to the name without extension.
+///
+/// `search` is replaced by `replace` everywhere in the name, an empty `search` replaces
+/// the whole name unless `replace` is empty too. `replace` may contain the tokens
+/// `{n}` / `{n:03}` (counter from `start` by `step`), `{date}` / `{date:%Y%m%d}`
+/// (modification date), `{name}` and `{ext}` (original name and extension).
+/// `extension` is kept when `None`, removed when empty.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RenameParameters {
+    pub search: String,
+    pub replace: String,
+    pub regex: bool,
+    pub case: RenameCase,
+    pub extension: Option<String>,
+    pub start: usize,
+    pub step: usize,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct RenamePreview {
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    pub conflict: Option<String>,
+}
+
+/// Compiled form of `RenameParameters`.
+pub struct EntitysRename {
+    parameters: RenameParameters,
+    search: Regex,
+    tokens: Regex,
+}
+
+impl EntitysRename {
+    pub fn new(parameters: RenameParameters) -> Result<Self, JujikError> {
+        let search = if parameters.regex {
+            Regex::new(&parameters.search)?
+        } else {
+            Regex::new(&regex::escape(&parameters.search))?
+        };
+
+        Ok(Self {
+            parameters,
+            search,
+            tokens: Regex::new(r"\{(n|date|name|ext)(?::([^}]*))?\}")?,
+        })
+    }
+
+    /// New name of the `idx`th entity of the batch.
+    pub fn name(&self, entity: &Entity, idx: usize) -> String {
+        let stem = entity.name();
+
+        let name = if !self.parameters.search.is_empty() {
+            if self.parameters.regex {
+                self.search
+                    .replace_all(&stem, self.parameters.replace.as_str())
+                    .to_string()
+            } else {
+                self.search
+                    .replace_all(&stem, NoExpand(&self.parameters.replace))
+                    .to_string()
+            }
+        } else if !self.parameters.replace.is_empty() {
+            self.parameters.replace.clone()
+        } else {
+            stem.clone()
+        };
+
+        let name = self
+            .tokens
+            .replace_all(&name, |caps: &Captures| {
+                self.token(entity, idx, &caps[1], caps.get(2).map(|m| m.as_str()))
+                    .unwrap_or(caps[0].to_string())
+            })
+            .to_string();
+
+        let name = match self.parameters.case {
+            RenameCase::Keep => name,
+            RenameCase::Lower => name.to_lowercase(),
+            RenameCase::Upper => name.to_uppercase(),
+            RenameCase::Title => Self::title(&name),
+        };
+
+        let extension = match &self.parameters.extension {
+            None => entity.extension().clone(),
+            Some(extension) if extension.is_empty() => None,
+            Some(extension) => Some(extension.clone()),
+        };
+
+        match extension {
+            Some(extension) => name + "." + extension.as_str(),
+            None => name,
+        }
+    }
+
+    /// New paths of `entitys`, in the order of the batch.
+    pub fn preview(&self, entitys: &[Entity]) -> Vec<RenamePreview> {
+        let names: Vec<String> = entitys
+            .iter()
+            .enumerate()
+            .map(|(idx, entity)| self.name(entity, idx))
+            .collect();
+
+        let sources: HashSet<PathBuf> = entitys.iter().map(|e| e.path()).collect();
+        let destinations: Vec<PathBuf> = entitys
+            .iter()
+            .zip(&names)
+            .map(|(entity, name)| entity.path_dir().join(name))
+            .collect();
+
+        entitys
+            .iter()
+            .zip(names.iter().zip(&destinations))
+            .map(|(entity, (name, destination))| {
+                let conflict = if name.is_empty() || name == "." || name == ".." {
+                    Some("Invalid name".to_string())
+                } else if name.contains('/') || name.contains('\0') {
+                    Some("Invalid character".to_string())
+                } else if destinations.iter().filter(|d| *d == destination).count() > 1 {
+                    Some("Duplicate name".to_string())
+                } else if !sources.contains(destination) && symlink_metadata(destination).is_ok() {
+                    Some("Already exists".to_string())
+                } else {
+                    None
+                };
+
+                RenamePreview {
+                    source: entity.path(),
+                    destination: destination.clone(),
+                    conflict,
+                }
+            })
+            .collect()
+    }
+
+    /// Renames every source to its destination as one operation.
+    ///
+    /// Sources are first moved to temporary names so that names can be swapped
+    /// within the batch, a failure renames everything back.
+    pub fn rename(pairs: &[(PathBuf, PathBuf)]) -> Result<(), JujikError> {
+        let pairs: Vec<&(PathBuf, PathBuf)> = pairs.iter().filter(|(s, d)| s != d).collect();
+        let mut done: Vec<(PathBuf, PathBuf)> = Vec::new();
+
+        if let Err(err) = Self::rename_steps(&pairs, &mut done) {
+            for (from, to) in done.iter().rev() {
+                fs::rename(to, from)?;
+            }
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    fn rename_steps(
+        pairs: &[&(PathBuf, PathBuf)],
+        done: &mut Vec<(PathBuf, PathBuf)>,
+    ) -> Result<(), JujikError> {
+        let mut temps = Vec::new();
+
+        for (source, _) in pairs {
+            let temp = EntitysTransfer::free_path(&source.with_file_name(format!(
+                ".{}.rename",
+                source
+                    .file_name()
+                    .map(|n| n.to_string_lossy())
+                    .unwrap_or_default()
+            )));
+
+            fs::rename(source, &temp)?;
+            done.push((source.clone(), temp.clone()));
+            temps.push(temp);
+        }
+
+        for ((_, destination), temp) in pairs.iter().zip(temps) {
+            if symlink_metadata(destination).is_ok() {
+                return Err(JujikError::Other(format!(
+                    "Destination already exists:\n{:?}",
+                    destination
+                )));
+            }
+
+            fs::rename(&temp, destination)?;
+            done.push((temp, destination.clone()));
+        }
+
+        Ok(())
+    }
+
+    // `None` keeps the token as it was written
+    fn token(
+        &self,
+        entity: &Entity,
+        idx: usize,
+        token: &str,
+        format: Option<&str>,
+    ) -> Option<String> {
+        match token {
+            "n" => {
+                let n = self.parameters.start + idx * self.parameters.step;
+                let width = match format {
+                    Some(format) => format.parse::<usize>().ok()?,
+                    None => 0,
+                };
+
+                if format.is_some_and(|f| f.starts_with('0')) {
+                    Some(format!("{:0width$}", n))
+                } else {
+                    Some(format!("{:width$}", n))
+                }
+            }
+            "date" => {
+                let mut date = String::new();
+
+                // An invalid format fails here instead of panicking in `to_string`
+                write!(
+                    date,
+                    "{}",
+                    entity
+                        .modification()
+                        .date()
+                        .with_timezone(&Local)
+                        .format(format.unwrap_or(RENAME_DATE_FORMAT))
+                )
+                .ok()?;
+
+                Some(date)
+            }
+            "name" => Some(entity.name()),
+            "ext" => Some(entity.extension().clone().unwrap_or_default()),
+            _ => None,
+        }
+    }
+
+    fn title(name: &str) -> String {
+        let mut title = String::with_capacity(name.len());
+        let mut word_start = true;
+
+        for c in name.chars() {
+            if word_start {
+                title.extend(c.to_uppercase());
+            } else {
+                title.extend(c.to_lowercase());
+            }
+
+            word_start = !c.is_alphanumeric();
+        }
+
+        title
+    }
+}
+
+impl Default for RenameParameters {
+    fn default() -> Self {
+        Self {
+            search: String::new(),
+            replace: String::new(),
+            regex: false,
+            case: RenameCase::Keep,
+            extension: None,
+            start: 1,
+            step: 1,
+        }
+    }
+}
+
+impl Display for RenameCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                RenameCase::Keep => "Keep",
+                RenameCase::Lower => "lower",
+                RenameCase::Upper => "UPPER",
+                RenameCase::Title => "Title",
+            }
+        )
+    }
+}
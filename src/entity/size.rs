@@ -61,6 +61,10 @@ impl EntitySize {
     pub fn size_byte(&self) -> u64 {
         self.size_byte
     }
+
+    pub fn is_none(&self) -> bool {
+        self.kind == EntitySizeKind::None
+    }
 }
 
 impl Eq for EntitySize {}
@@ -125,9 +129,10 @@ impl Display for EntitySizeKind {
             match self {
                 EntitySizeKind::None => "None",
                 EntitySizeKind::Byte => "B",
-                EntitySizeKind::KiloByte => "KB",
-                EntitySizeKind::MegaByte => "MB",
-                EntitySizeKind::GigaByte => "GB",
+                // powers of 1024, see `EntitysFormat` for the units shown to the user
+                EntitySizeKind::KiloByte => "KiB",
+                EntitySizeKind::MegaByte => "MiB",
+                EntitySizeKind::GigaByte => "GiB",
             }
         )
     }
@@ -0,0 +1,627 @@
+use super::{Entity, kind::EntityKind, trash::EntitysTrash};
+use crate::{error::JujikError, job::JobControl};
+use nix::{
+    errno::Errno,
+    sys::{
+        stat::{UtimensatFlags, utimensat},
+        time::TimeSpec,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, Metadata, symlink_metadata},
+    io::{Read, Write},
+    mem,
+    os::{linux::fs::MetadataExt, unix},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+const TRANSFER_BUFFER_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum ConflictPolicy {
+    #[default]
+    Ask,
+    Overwrite,
+    Skip,
+    Rename,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum TransferKind {
+    #[default]
+    Copy,
+    Move,
+}
+
+pub struct EntitysTransfer {
+    policy: ConflictPolicy,
+    done: Vec<(PathBuf, PathBuf)>,
+    failures: Vec<(PathBuf, JujikError)>,
+    control: Option<Arc<JobControl>>,
+    trash: Option<EntitysTrash>,
+    // original, trashed file, trash info
+    trashed: Vec<(PathBuf, PathBuf, PathBuf)>,
+    removed: Vec<PathBuf>,
+}
+
+impl EntitysTransfer {
+    pub fn new(policy: ConflictPolicy) -> Self {
+        Self {
+            policy,
+            done: Vec::new(),
+            failures: Vec::new(),
+            control: None,
+            trash: None,
+            trashed: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    /// Reports progress to `control` and stops when it is paused or cancelled.
+    pub fn with_control(mut self, control: Arc<JobControl>) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// Trash for the destinations a copy or move overwrites, the user trash by default.
+    pub fn with_trash(mut self, trash: EntitysTrash) -> Self {
+        self.trash = Some(trash);
+        self
+    }
+
+    /// Sources with the destinations that were created or replaced for them,
+    /// merged directories and skipped entitys are left out.
+    pub fn done(&self) -> &[(PathBuf, PathBuf)] {
+        &self.done
+    }
+
+    /// Destinations a copy or move replaced, with where they went in the trash.
+    pub fn trashed(&self) -> &[(PathBuf, PathBuf, PathBuf)] {
+        &self.trashed
+    }
+
+    /// Source directories a move merged into the destination and removed once empty,
+    /// the deepest first.
+    pub fn removed(&self) -> &[PathBuf] {
+        &self.removed
+    }
+
+    /// Destinations in `pathbuf` that are already taken by one of `entitys`.
+    pub fn conflicts(entitys: &[Entity], pathbuf: &Path) -> Vec<PathBuf> {
+        entitys
+            .iter()
+            .map(|entity| pathbuf.join(entity.name_with_extension()))
+            .filter(|destination| symlink_metadata(destination).is_ok())
+            .collect()
+    }
+
+    pub fn copy(&mut self, entitys: &[Entity], pathbuf: &Path) -> Result<(), JujikError> {
+        for entity in entitys {
+            let source = entity.path();
+            let destination = pathbuf.join(entity.name_with_extension());
+
+            if let Err(err) = self.checkpoint(&source) {
+                self.failures.push((source, err));
+                break;
+            }
+
+            if entity.is_dir() && destination.starts_with(&source) {
+                self.failures.push((
+                    source,
+                    JujikError::Other("Can not copy directory into itself".to_string()),
+                ));
+                continue;
+            }
+
+            // Replaced destinations are recorded by `copy_path`
+            let existed = symlink_metadata(&destination).is_ok();
+
+            if let Some(destination) = self.copy_path(&source, destination)
+                && (!existed || self.policy == ConflictPolicy::Rename)
+            {
+                self.done.push((source, destination));
+            }
+        }
+
+        self.finish()
+    }
+
+    /// Copies every source to its destination, the destinations must not exist.
+    pub fn copy_pairs(&mut self, pairs: &[(PathBuf, PathBuf)]) -> Result<(), JujikError> {
+        for (source, destination) in pairs {
+            if let Err(err) = self.checkpoint(source) {
+                self.failures.push((source.clone(), err));
+                break;
+            }
+
+            if let Some(destination) = self.copy_path(source, destination.clone()) {
+                self.done.push((source.clone(), destination));
+            }
+        }
+
+        self.finish()
+    }
+
+    pub fn relocate(&mut self, entitys: &[Entity], pathbuf: &Path) -> Result<(), JujikError> {
+        for entity in entitys {
+            let source = entity.path();
+            let destination = pathbuf.join(entity.name_with_extension());
+
+            if let Err(err) = self.checkpoint(&source) {
+                self.failures.push((source, err));
+                break;
+            }
+
+            if entity.is_dir() && destination.starts_with(&source) {
+                self.failures.push((
+                    source,
+                    JujikError::Other("Can not move directory into itself".to_string()),
+                ));
+                continue;
+            }
+
+            match self.move_path(&source, destination) {
+                Ok(Some(destination)) => self.done.push((source, destination)),
+                Ok(None) => {}
+                Err(err) => self.failures.push((source, err)),
+            }
+        }
+
+        self.finish()
+    }
+
+    // Conflicts are resolved as `resolve` does for a copy, directories on both sides
+    // are merged and a replaced destination goes to the trash.
+    fn move_path(
+        &mut self,
+        source: &Path,
+        destination: PathBuf,
+    ) -> Result<Option<PathBuf>, JujikError> {
+        let existing = match symlink_metadata(&destination) {
+            Ok(existing) => existing,
+            Err(_) => {
+                self.rename_path(source, &destination)?;
+
+                return Ok(Some(destination));
+            }
+        };
+
+        if destination.eq(source) {
+            return Err(JujikError::Other(
+                "Source and destination are the same".to_string(),
+            ));
+        }
+
+        let merge = symlink_metadata(source)?.is_dir() && existing.is_dir();
+
+        match self.policy {
+            ConflictPolicy::Ask => Err(JujikError::Other(format!(
+                "Destination already exists:\n{:?}",
+                destination
+            ))),
+            ConflictPolicy::Skip | ConflictPolicy::Overwrite if merge => {
+                self.merge(source, &destination)?;
+
+                Ok(None)
+            }
+            ConflictPolicy::Skip => Ok(None),
+            ConflictPolicy::Rename => {
+                let destination = Self::free_path(&destination);
+                self.rename_path(source, &destination)?;
+
+                Ok(Some(destination))
+            }
+            ConflictPolicy::Overwrite => {
+                self.replace(&destination, |transfer| {
+                    transfer.rename_path(source, &destination)
+                })?;
+
+                Ok(Some(destination))
+            }
+        }
+    }
+
+    // Children are moved one by one, the source is removed when nothing was left behind
+    fn merge(&mut self, source: &Path, destination: &Path) -> Result<(), JujikError> {
+        for dir_entry in fs::read_dir(source)? {
+            let child = match dir_entry {
+                Ok(dir_entry) => dir_entry.path(),
+                Err(err) => {
+                    self.failures.push((source.to_path_buf(), err.into()));
+                    continue;
+                }
+            };
+
+            if let Err(err) = self.checkpoint(&child) {
+                self.failures.push((child, err));
+                break;
+            }
+
+            let child_destination = destination.join(child.file_name().unwrap_or_default());
+
+            match self.move_path(&child, child_destination) {
+                Ok(Some(child_destination)) => self.done.push((child, child_destination)),
+                Ok(None) => {}
+                Err(err) => self.failures.push((child, err)),
+            }
+        }
+
+        if fs::read_dir(source)?.next().is_none() {
+            fs::remove_dir(source)?;
+            self.removed.push(source.to_path_buf());
+        }
+
+        Ok(())
+    }
+
+    /// `fs::rename` that falls back to `move_across` between filesystems.
+    pub fn rename(source: &Path, destination: &Path) -> Result<(), JujikError> {
+        Self::new(ConflictPolicy::Ask).rename_path(source, destination)
+    }
+
+    fn rename_path(&self, source: &Path, destination: &Path) -> Result<(), JujikError> {
+        // Measured before the rename, the whole subtree is done at once
+        let measured = self
+            .control
+            .as_ref()
+            .map(|_| JobControl::measure(&[source.to_path_buf()]));
+
+        match fs::rename(source, destination) {
+            Err(err) if err.raw_os_error() == Some(Errno::EXDEV as i32) => {
+                self.move_across_path(source, destination)
+            }
+            Ok(()) => {
+                if let (Some(control), Some((files, bytes))) = (&self.control, measured) {
+                    control.add(files, bytes);
+                }
+
+                Ok(())
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Moves `source` to `destination` on another filesystem by copy, verify and delete.
+    ///
+    /// The copy is made under a temporary name next to `destination` and only renamed into
+    /// place once it matches the source, a failed copy is removed and the source is kept.
+    pub fn move_across(source: &Path, destination: &Path) -> Result<(), JujikError> {
+        Self::new(ConflictPolicy::Ask).move_across_path(source, destination)
+    }
+
+    fn move_across_path(&self, source: &Path, destination: &Path) -> Result<(), JujikError> {
+        let partial = Self::partial_path(destination);
+
+        let mut transfer = Self::new(ConflictPolicy::Ask);
+        transfer.control = self.control.clone();
+        transfer.copy_path(source, partial.clone());
+
+        let res = transfer
+            .finish()
+            .and_then(|_| Self::verify(source, &partial))
+            .and_then(|_| Ok(fs::rename(&partial, destination)?));
+
+        if let Err(err) = res {
+            if symlink_metadata(&partial).is_ok() {
+                Self::remove_path(&partial)?;
+            }
+
+            return Err(err);
+        }
+
+        Self::remove_path(source)
+    }
+
+    // Hidden name next to `destination` for a copy that is not complete yet
+    fn partial_path(destination: &Path) -> PathBuf {
+        Self::free_path(&destination.with_file_name(format!(
+            ".{}.part",
+            destination
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default()
+        )))
+    }
+
+    // Sends `destination` to the trash and runs `put` to fill its place,
+    // the trashed entity comes back when `put` fails
+    fn replace<F>(&mut self, destination: &Path, put: F) -> Result<(), JujikError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), JujikError>,
+    {
+        let trash = match &self.trash {
+            Some(trash) => trash.clone(),
+            None => EntitysTrash::new()?,
+        };
+        let (files, info) = trash.trash_path(destination)?;
+
+        if let Err(err) = put(self) {
+            EntitysTrash::restore_path(&files, &info, destination)?;
+
+            return Err(err);
+        }
+
+        self.trashed.push((destination.to_path_buf(), files, info));
+
+        Ok(())
+    }
+
+    fn verify(source: &Path, copy: &Path) -> Result<(), JujikError> {
+        let source_metadata = symlink_metadata(source)?;
+        let copy_metadata = symlink_metadata(copy)?;
+        let kind = EntityKind::from(source_metadata.file_type());
+
+        let same = kind == EntityKind::from(copy_metadata.file_type())
+            && match kind {
+                EntityKind::File => source_metadata.len() == copy_metadata.len(),
+                EntityKind::Symlink => fs::read_link(source)? == fs::read_link(copy)?,
+                _ => true,
+            };
+
+        if !same {
+            return Err(JujikError::Other(format!(
+                "Copy does not match source:\n{:?}",
+                copy
+            )));
+        }
+
+        if kind == EntityKind::Directory {
+            for dir_entry in fs::read_dir(source)? {
+                let dir_entry = dir_entry?;
+                Self::verify(&dir_entry.path(), &copy.join(dir_entry.file_name()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_path(pathbuf: &Path) -> Result<(), JujikError> {
+        if symlink_metadata(pathbuf)?.is_dir() {
+            Ok(fs::remove_dir_all(pathbuf)?)
+        } else {
+            Ok(fs::remove_file(pathbuf)?)
+        }
+    }
+
+    fn finish(&mut self) -> Result<(), JujikError> {
+        if self.failures.is_empty() {
+            Ok(())
+        } else {
+            Err(JujikError::Paths(mem::take(&mut self.failures)))
+        }
+    }
+
+    fn checkpoint(&self, source: &Path) -> Result<(), JujikError> {
+        match &self.control {
+            Some(control) => {
+                control.checkpoint()?;
+                control.start(source);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.control.as_ref().is_some_and(|c| c.is_cancelled())
+    }
+
+    // Returns the destination when it was copied, `None` when skipped or failed
+    fn copy_path(&mut self, source: &Path, destination: PathBuf) -> Option<PathBuf> {
+        if let Err(err) = self.checkpoint(source) {
+            self.failures.push((source.to_path_buf(), err));
+            return None;
+        }
+
+        let metadata = match symlink_metadata(source) {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                self.failures.push((source.to_path_buf(), err.into()));
+                return None;
+            }
+        };
+        let kind = EntityKind::from(metadata.file_type());
+
+        let (destination, replace) = match self.resolve(source, &kind, destination) {
+            Ok(Some(resolved)) => resolved,
+            Ok(None) => return None,
+            Err(err) => {
+                self.failures.push((source.to_path_buf(), err));
+                return None;
+            }
+        };
+
+        // A destination to overwrite stays in place until its replacement is complete
+        let target = if replace {
+            Self::partial_path(&destination)
+        } else {
+            destination.clone()
+        };
+        let failures = self.failures.len();
+
+        let res = match kind {
+            EntityKind::Directory => self.copy_dir(source, &target, &metadata),
+            EntityKind::File => self.copy_file(source, &target, &metadata),
+            EntityKind::Symlink => fs::read_link(source)
+                .and_then(|target_link| unix::fs::symlink(target_link, &target))
+                .map_err(JujikError::from)
+                .map(|_| self.add(1, metadata.len())),
+            _ => Err(JujikError::Other(format!(
+                "Can not copy entity of kind: {}",
+                kind
+            ))),
+        }
+        .and_then(|_| Self::copy_times(&target, &metadata));
+
+        let res = match res {
+            Ok(()) if replace && self.failures.len() > failures => Err(JujikError::Other(format!(
+                "Copy is not complete, kept the destination:\n{:?}",
+                destination
+            ))),
+            Ok(()) if replace => {
+                self.replace(&destination, |_| Ok(fs::rename(&target, &destination)?))
+            }
+            res => res,
+        };
+
+        match res {
+            Ok(()) => {
+                // Unlike a merged directory, a replaced destination can be undone
+                if replace {
+                    self.done.push((source.to_path_buf(), destination.clone()));
+                }
+
+                Some(destination)
+            }
+            Err(err) => {
+                if replace && symlink_metadata(&target).is_ok() {
+                    let _ = Self::remove_path(&target);
+                }
+
+                self.failures.push((source.to_path_buf(), err));
+                None
+            }
+        }
+    }
+
+    fn copy_dir(
+        &mut self,
+        source: &Path,
+        destination: &Path,
+        metadata: &Metadata,
+    ) -> Result<(), JujikError> {
+        if !destination.is_dir() {
+            fs::create_dir(destination)?;
+        }
+
+        for dir_entry in fs::read_dir(source)? {
+            if self.is_cancelled() {
+                break;
+            }
+
+            match dir_entry {
+                Ok(dir_entry) => {
+                    self.copy_path(&dir_entry.path(), destination.join(dir_entry.file_name()));
+                }
+                Err(err) => self.failures.push((source.to_path_buf(), err.into())),
+            }
+        }
+
+        Ok(fs::set_permissions(destination, metadata.permissions())?)
+    }
+
+    // Copied in chunks so a job can report bytes and stop in the middle of a big file,
+    // a file that was not copied whole is removed.
+    fn copy_file(
+        &self,
+        source: &Path,
+        destination: &Path,
+        metadata: &Metadata,
+    ) -> Result<(), JujikError> {
+        let mut reader = File::open(source)?;
+        let mut writer = File::create_new(destination)?;
+
+        let res = self
+            .copy_content(&mut reader, &mut writer)
+            .and_then(|_| Ok(writer.set_permissions(metadata.permissions())?));
+
+        if res.is_err() {
+            fs::remove_file(destination)?;
+        } else {
+            self.add(1, 0);
+        }
+
+        res
+    }
+
+    fn copy_content(&self, reader: &mut File, writer: &mut File) -> Result<(), JujikError> {
+        let mut buffer = vec![0; TRANSFER_BUFFER_SIZE];
+
+        loop {
+            let n = reader.read(&mut buffer)?;
+
+            if n == 0 {
+                return Ok(());
+            }
+
+            writer.write_all(&buffer[..n])?;
+            self.add(0, n as u64);
+
+            if let Some(control) = &self.control {
+                control.checkpoint()?;
+            }
+        }
+    }
+
+    fn add(&self, files: u64, bytes: u64) {
+        if let Some(control) = &self.control {
+            control.add(files, bytes);
+        }
+    }
+
+    // Returns the path to copy to and whether the entity there is replaced,
+    // `None` when the entity is skipped.
+    // Directories that exist on both sides are merged unless the policy renames them.
+    fn resolve(
+        &self,
+        source: &Path,
+        kind: &EntityKind,
+        destination: PathBuf,
+    ) -> Result<Option<(PathBuf, bool)>, JujikError> {
+        let existing = match symlink_metadata(&destination) {
+            Ok(existing) => existing,
+            Err(_) => return Ok(Some((destination, false))),
+        };
+
+        if destination.eq(source) && self.policy != ConflictPolicy::Rename {
+            return Err(JujikError::Other(
+                "Source and destination are the same".to_string(),
+            ));
+        }
+
+        let merge = *kind == EntityKind::Directory && existing.is_dir();
+
+        match self.policy {
+            ConflictPolicy::Ask => Err(JujikError::Other(format!(
+                "Destination already exists:\n{:?}",
+                destination
+            ))),
+            ConflictPolicy::Skip if merge => Ok(Some((destination, false))),
+            ConflictPolicy::Skip => Ok(None),
+            ConflictPolicy::Overwrite if merge => Ok(Some((destination, false))),
+            // Trashed once the copy is complete
+            ConflictPolicy::Overwrite => Ok(Some((destination, true))),
+            ConflictPolicy::Rename => Ok(Some((Self::free_path(&destination), false))),
+        }
+    }
+
+    // "name.ext" -> "name (1).ext", "name (2).ext", ...
+    pub fn free_path(destination: &Path) -> PathBuf {
+        let parent = destination.parent().unwrap_or(Path::new(""));
+        let name = Entity::get_name(destination).unwrap_or_default();
+        let extension = destination
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default();
+
+        (1..)
+            .map(|n| parent.join(format!("{} ({}){}", name, n, extension)))
+            .find(|pathbuf| symlink_metadata(pathbuf).is_err())
+            .unwrap_or(destination.to_path_buf())
+    }
+
+    fn copy_times(destination: &Path, metadata: &Metadata) -> Result<(), JujikError> {
+        let atime = TimeSpec::new(metadata.st_atime(), metadata.st_atime_nsec());
+        let mtime = TimeSpec::new(metadata.st_mtime(), metadata.st_mtime_nsec());
+
+        Ok(utimensat(
+            None,
+            destination,
+            &atime,
+            &mtime,
+            UtimensatFlags::NoFollowSymlink,
+        )?)
+    }
+}
@@ -0,0 +1,404 @@
+use super::{Entity, date::EntityDate, transfer::EntitysTransfer};
+use crate::error::JujikError;
+use chrono::{Local, NaiveDateTime};
+use nix::unistd::getuid;
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    ffi::OsString,
+    fs::{self, DirBuilder, File, symlink_metadata},
+    io::Write,
+    os::{
+        linux::fs::MetadataExt,
+        unix::{
+            ffi::{OsStrExt, OsStringExt},
+            fs::DirBuilderExt,
+        },
+    },
+    path::{Path, PathBuf},
+};
+
+const TRASH_INFO_EXTENSION: &str = "trashinfo";
+const TRASH_INFO_DATE: &str = "%Y-%m-%dT%H:%M:%S";
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct TrashEntity {
+    entity: Entity,
+    info: PathBuf,
+    original: PathBuf,
+    deletion: EntityDate,
+}
+
+/// Trash directories of the XDG Trash spec.
+///
+/// Entitys on the device of the home trash (`$XDG_DATA_HOME/Trash`) go there,
+/// others go to `$topdir/.Trash/$uid` or `$topdir/.Trash-$uid` of their mount.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EntitysTrash {
+    home: PathBuf,
+}
+
+impl TrashEntity {
+    pub fn entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    pub fn original(&self) -> &PathBuf {
+        &self.original
+    }
+
+    pub fn original_str(&self) -> String {
+        self.original.to_string_lossy().to_string()
+    }
+
+    pub fn deletion(&self) -> &EntityDate {
+        &self.deletion
+    }
+
+    pub fn name(&self) -> String {
+        self.original
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl EntitysTrash {
+    pub fn new() -> Result<Self, JujikError> {
+        let data_home = env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")))
+            .ok_or(JujikError::Other("Can not find home directory".to_string()))?;
+
+        Ok(Self::from_path(data_home.join("Trash")))
+    }
+
+    pub fn from_path(home: PathBuf) -> Self {
+        Self { home }
+    }
+
+    pub fn home(&self) -> PathBuf {
+        self.home.clone()
+    }
+
+    pub fn trash(&self, entitys: &[Entity]) -> Result<(), JujikError> {
+        let mut failures = Vec::new();
+
+        for entity in entitys {
+            if let Err(err) = self.trash_path(&entity.path()) {
+                failures.push((entity.path(), err));
+            }
+        }
+
+        Self::finish(failures)
+    }
+
+    pub fn restore(&self, trash_entitys: &[TrashEntity]) -> Result<(), JujikError> {
+        let mut failures = Vec::new();
+
+        for trash_entity in trash_entitys {
+            if let Err(err) = Self::restore_path(
+                &trash_entity.entity.path(),
+                &trash_entity.info,
+                &trash_entity.original,
+            ) {
+                failures.push((trash_entity.original.clone(), err));
+            }
+        }
+
+        Self::finish(failures)
+    }
+
+    pub fn purge(&self, trash_entitys: &[TrashEntity]) -> Result<(), JujikError> {
+        let mut failures = Vec::new();
+
+        for trash_entity in trash_entitys {
+            let res = EntitysTransfer::remove_path(&trash_entity.entity.path())
+                .and_then(|_| Ok(fs::remove_file(&trash_entity.info)?));
+
+            if let Err(err) = res {
+                failures.push((trash_entity.original.clone(), err));
+            }
+        }
+
+        Self::finish(failures)
+    }
+
+    pub fn list(&self) -> Vec<TrashEntity> {
+        let mut trash_dirs = vec![(self.home.clone(), PathBuf::from("/"))];
+
+        for topdir in Self::mounts() {
+            for trash in [
+                topdir.join(".Trash").join(getuid().to_string()),
+                topdir.join(format!(".Trash-{}", getuid())),
+            ] {
+                if trash.is_dir() && trash != self.home {
+                    trash_dirs.push((trash, topdir.clone()));
+                }
+            }
+        }
+
+        let mut trash_entitys = Vec::new();
+
+        for (trash, topdir) in trash_dirs {
+            if let Ok(read_dir) = fs::read_dir(trash.join("info")) {
+                trash_entitys.extend(
+                    read_dir
+                        .flatten()
+                        .filter_map(|dir_entry| Self::read_info(&dir_entry.path(), &topdir).ok()),
+                );
+            }
+        }
+
+        trash_entitys
+    }
+
+    fn finish(failures: Vec<(PathBuf, JujikError)>) -> Result<(), JujikError> {
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(JujikError::Paths(failures))
+        }
+    }
+
+    /// Moves `source` to the trash, returns the trashed file and its info file.
+    pub fn trash_path(&self, source: &Path) -> Result<(PathBuf, PathBuf), JujikError> {
+        let trash = self.trash_dir(source)?;
+        let file_name = source
+            .file_name()
+            .ok_or(JujikError::Other(format!(
+                "Can not trash path:\n{:?}",
+                source
+            )))?
+            .to_os_string();
+
+        let (info, destination) = Self::create_info(&trash, &file_name, source)?;
+
+        if let Err(err) = EntitysTransfer::rename(source, &destination) {
+            fs::remove_file(info)?;
+
+            return Err(err);
+        }
+
+        Ok((destination, info))
+    }
+
+    pub fn restore_path(files: &Path, info: &Path, original: &Path) -> Result<(), JujikError> {
+        if symlink_metadata(original).is_ok() {
+            return Err(JujikError::Other(format!(
+                "Destination already exists:\n{:?}",
+                original
+            )));
+        }
+
+        if let Some(parent) = original.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        EntitysTransfer::rename(files, original)?;
+
+        Ok(fs::remove_file(info)?)
+    }
+
+    // A topdir trash that can not be used falls back to the home trash,
+    // the entity is then copied across devices.
+    fn trash_dir(&self, source: &Path) -> Result<PathBuf, JujikError> {
+        let dev = symlink_metadata(source)?.st_dev();
+
+        Self::create_trash(&self.home)?;
+
+        if symlink_metadata(&self.home)?.st_dev() == dev {
+            return Ok(self.home.clone());
+        }
+
+        let topdir = Self::topdir(source, dev);
+        let shared = topdir.join(".Trash");
+
+        if let Ok(metadata) = symlink_metadata(&shared)
+            && metadata.is_dir()
+            && metadata.st_mode() & 0o1000 != 0
+        {
+            let trash = shared.join(getuid().to_string());
+
+            if Self::create_trash(&trash).is_ok() {
+                return Ok(trash);
+            }
+        }
+
+        let trash = topdir.join(format!(".Trash-{}", getuid()));
+
+        Ok(Self::create_trash(&trash)
+            .map(|_| trash)
+            .unwrap_or(self.home.clone()))
+    }
+
+    fn create_trash(trash: &Path) -> Result<(), JujikError> {
+        let mut dir_builder = DirBuilder::new();
+        dir_builder.recursive(true).mode(0o700);
+
+        dir_builder.create(trash.join("files"))?;
+        dir_builder.create(trash.join("info"))?;
+
+        Ok(())
+    }
+
+    fn topdir(source: &Path, dev: u64) -> PathBuf {
+        source
+            .ancestors()
+            .skip(1)
+            .take_while(|p| symlink_metadata(p).is_ok_and(|m| m.st_dev() == dev))
+            .last()
+            .unwrap_or(Path::new("/"))
+            .to_path_buf()
+    }
+
+    // The info file is created first and exclusively, it reserves the name in "files"
+    fn create_info(
+        trash: &Path,
+        file_name: &OsString,
+        source: &Path,
+    ) -> Result<(PathBuf, PathBuf), JujikError> {
+        for n in 1.. {
+            let mut name = file_name.clone();
+
+            if n > 1 {
+                name.push(format!(".{}", n));
+            }
+
+            let destination = trash.join("files").join(&name);
+            name.push(format!(".{}", TRASH_INFO_EXTENSION));
+            let info = trash.join("info").join(&name);
+
+            if symlink_metadata(&destination).is_ok() {
+                continue;
+            }
+
+            match File::options().write(true).create_new(true).open(&info) {
+                Ok(mut file) => {
+                    write!(
+                        file,
+                        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+                        Self::encode(source),
+                        Local::now().format(TRASH_INFO_DATE)
+                    )?;
+
+                    return Ok((info, destination));
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        unreachable!()
+    }
+
+    fn read_info(info: &Path, topdir: &Path) -> Result<TrashEntity, JujikError> {
+        if info.extension().is_none_or(|e| e != TRASH_INFO_EXTENSION) {
+            return Err(JujikError::Other(format!(
+                "Not a trash info file:\n{:?}",
+                info
+            )));
+        }
+
+        let content = fs::read_to_string(info)?;
+        let mut original = None;
+        let mut deletion = EntityDate::default();
+
+        for line in content.lines() {
+            if let Some(path) = line.strip_prefix("Path=") {
+                original = Some(topdir.join(Self::decode(path)));
+            } else if let Some(date) = line.strip_prefix("DeletionDate=")
+                && let Ok(date) = NaiveDateTime::parse_from_str(date, TRASH_INFO_DATE)
+                && let Some(date) = date.and_local_timezone(Local).earliest()
+            {
+                deletion = EntityDate::from_datetime(date.to_utc());
+            }
+        }
+
+        let files = info
+            .parent()
+            .and_then(|p| p.parent())
+            .unwrap_or(Path::new(""))
+            .join("files")
+            .join(info.file_stem().unwrap_or_default());
+
+        Ok(TrashEntity {
+            entity: Entity::new(files)?,
+            info: info.to_path_buf(),
+            original: original.ok_or(JujikError::Other(format!(
+                "Trash info without path:\n{:?}",
+                info
+            )))?,
+            deletion,
+        })
+    }
+
+    fn mounts() -> Vec<PathBuf> {
+        fs::read_to_string("/proc/self/mounts")
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.split(' ').nth(1))
+            .map(Self::unescape_mount)
+            .collect()
+    }
+
+    // The kernel writes space, tab, newline and backslash as "\040", "\011", "\012", "\134"
+    fn unescape_mount(mount: &str) -> PathBuf {
+        let bytes = mount.as_bytes();
+        let mut unescaped = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match (bytes[i], mount.get(i + 1..i + 4)) {
+                (b'\\', Some(octal))
+                    if octal.bytes().all(|b| matches!(b, b'0'..=b'7'))
+                        && u8::from_str_radix(octal, 8).is_ok() =>
+                {
+                    unescaped.push(u8::from_str_radix(octal, 8).unwrap_or_default());
+                    i += 4;
+                }
+                (b, _) => {
+                    unescaped.push(b);
+                    i += 1;
+                }
+            }
+        }
+
+        PathBuf::from(OsString::from_vec(unescaped))
+    }
+
+    fn encode(path: &Path) -> String {
+        path.as_os_str()
+            .as_bytes()
+            .iter()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                    (*b as char).to_string()
+                }
+                _ => format!("%{:02X}", b),
+            })
+            .collect()
+    }
+
+    fn decode(value: &str) -> PathBuf {
+        let bytes = value.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match (bytes[i], value.get(i + 1..i + 3)) {
+                (b'%', Some(hex)) if u8::from_str_radix(hex, 16).is_ok() => {
+                    decoded.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                    i += 3;
+                }
+                (b, _) => {
+                    decoded.push(b);
+                    i += 1;
+                }
+            }
+        }
+
+        PathBuf::from(OsString::from_vec(decoded))
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::symlink_metadata,
+    os::linux::fs::MetadataExt,
+    path::{Component, Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::error::JujikError;
+
+use super::{Entity, filter::EntitysFilter, find::EntitysWalk, size::EntitySize};
+use serde::{Deserialize, Serialize};
+
+const USAGE_BATCH_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct UsageProgress {
+    pub dirs: usize,
+    pub files: usize,
+    // bytes taken on disk by everything under the directory
+    pub total: u64,
+    #[serde(skip)]
+    pub running: bool,
+}
+
+/// Children of a directory with the size of everything under them, largest first.
+///
+/// Sizes are what the entries take on disk, a file with several hard links counts once.
+// `id` ties the batches of a background scan to its tab, it is not kept across restarts
+#[derive(Default, Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct EntitysUsage {
+    #[serde(skip)]
+    id: usize,
+    path: PathBuf,
+    progress: UsageProgress,
+    entitys: Vec<Entity>,
+    // scans stay on the filesystem of `path` unless this is set, like `ncdu -x`
+    #[serde(default)]
+    cross_filesystems: bool,
+}
+
+impl EntitysUsage {
+    pub fn new(id: usize, path: PathBuf) -> Result<Self, JujikError> {
+        Self::check_path(&path)?;
+
+        Ok(Self {
+            id,
+            path,
+            progress: UsageProgress {
+                running: true,
+                ..Default::default()
+            },
+            entitys: Vec::new(),
+            cross_filesystems: false,
+        })
+    }
+
+    /// Walks `path` and hands over the children with their sizes so far.
+    /// With `same_filesystem` the mount points under `path` are skipped.
+    ///
+    /// The walk ends when `stop` is set or `on_batch` returns `false`,
+    /// the last batch always has `running` unset.
+    pub fn scan<F>(
+        path: &Path,
+        filter: &EntitysFilter,
+        same_filesystem: bool,
+        stop: &AtomicBool,
+        mut on_batch: F,
+    ) -> Result<UsageProgress, JujikError>
+    where
+        F: FnMut(Vec<Entity>, UsageProgress) -> bool,
+    {
+        let mut progress = UsageProgress {
+            running: true,
+            ..Default::default()
+        };
+        let mut children: Vec<(Entity, u64)> = Vec::new();
+        let mut idxs: HashMap<PathBuf, usize> = HashMap::new();
+        // (dev, ino) of the files with more than one link already counted
+        let mut links: HashSet<(u64, u64)> = HashSet::new();
+        let mut batch_time = Instant::now();
+
+        Self::check_path(path)?;
+        let mut walk = EntitysWalk::new(path, filter).with_same_filesystem(same_filesystem);
+
+        while let Some((pathbuf, metadata)) = walk.next() {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            if pathbuf.parent() == Some(path)
+                && let Ok(entity) = Entity::from_metadata(&pathbuf, &metadata)
+            {
+                idxs.insert(pathbuf.clone(), children.len());
+                children.push((entity, 0));
+            }
+
+            if !metadata.is_dir() {
+                progress.files += 1;
+            }
+
+            let counted = metadata.st_nlink() < 2
+                || metadata.is_dir()
+                || links.insert((metadata.st_dev(), metadata.st_ino()));
+
+            if counted && let Some(idx) = Self::child(path, &pathbuf).and_then(|c| idxs.get(&c)) {
+                let size = metadata.st_blocks() * 512;
+
+                children[*idx].1 += size;
+                progress.total += size;
+            }
+
+            if batch_time.elapsed() >= USAGE_BATCH_INTERVAL {
+                progress.dirs = walk.dirs();
+
+                if !on_batch(Self::sorted(&children), progress.clone()) {
+                    break;
+                }
+
+                batch_time = Instant::now();
+            }
+        }
+
+        progress.dirs = walk.dirs();
+        progress.running = false;
+        on_batch(Self::sorted(&children), progress.clone());
+
+        Ok(progress)
+    }
+
+    /// Squarified treemap of `sizes`, sorted from the largest, inside `rect`.
+    ///
+    /// Rectangles are `[x, y, width, height]`, an empty size gets an empty one.
+    pub fn treemap(sizes: &[u64], rect: [f32; 4]) -> Vec<[f32; 4]> {
+        let [mut x, mut y, mut width, mut height] = rect.map(f64::from);
+        let mut rects = vec![[rect[0], rect[1], 0.0, 0.0]; sizes.len()];
+        let total: u64 = sizes.iter().sum();
+
+        if total == 0 || width <= 0.0 || height <= 0.0 {
+            return rects;
+        }
+
+        let scale = width * height / total as f64;
+        let areas: Vec<f64> = sizes
+            .iter()
+            .take_while(|size| **size > 0)
+            .map(|size| *size as f64 * scale)
+            .collect();
+        let mut start = 0;
+
+        while start < areas.len() {
+            let side = width.min(height);
+            let mut end = start + 1;
+
+            // The row grows while its worst aspect ratio gets better
+            while end < areas.len()
+                && Self::worst(&areas[start..=end], side) <= Self::worst(&areas[start..end], side)
+            {
+                end += 1;
+            }
+
+            let row: f64 = areas[start..end].iter().sum();
+
+            if width >= height {
+                let row_width = row / height;
+                let mut row_y = y;
+
+                for idx in start..end {
+                    let cell = areas[idx] / row_width;
+                    rects[idx] = [x, row_y, row_width, cell].map(|v| v as f32);
+                    row_y += cell;
+                }
+
+                x += row_width;
+                width -= row_width;
+            } else {
+                let row_height = row / width;
+                let mut row_x = x;
+
+                for idx in start..end {
+                    let cell = areas[idx] / row_height;
+                    rects[idx] = [row_x, y, cell, row_height].map(|v| v as f32);
+                    row_x += cell;
+                }
+
+                y += row_height;
+                height -= row_height;
+            }
+
+            start = end;
+        }
+
+        rects
+    }
+
+    // Largest aspect ratio of a row laid along `side`
+    fn worst(row: &[f64], side: f64) -> f64 {
+        let sum: f64 = row.iter().sum();
+        let max = row.iter().cloned().fold(f64::MIN, f64::max);
+        let min = row.iter().cloned().fold(f64::MAX, f64::min);
+
+        f64::max(
+            side * side * max / (sum * sum),
+            sum * sum / (side * side * min),
+        )
+    }
+
+    // The child of `root` that `pathbuf` is under
+    fn child(root: &Path, pathbuf: &Path) -> Option<PathBuf> {
+        match pathbuf.strip_prefix(root).ok()?.components().next()? {
+            Component::Normal(name) => Some(root.join(name)),
+            _ => None,
+        }
+    }
+
+    fn sorted(children: &[(Entity, u64)]) -> Vec<Entity> {
+        let mut entitys: Vec<Entity> = children
+            .iter()
+            .map(|(entity, size)| {
+                let mut entity = entity.clone();
+                entity.set_size(EntitySize::new(*size));
+                entity
+            })
+            .collect();
+
+        entitys.sort_by(|e1, e2| e2.size().cmp(e1.size()));
+        entitys
+    }
+
+    fn check_path(path: &Path) -> Result<(), JujikError> {
+        if symlink_metadata(path)?.is_dir() {
+            Ok(())
+        } else {
+            Err(JujikError::Other(format!(
+                "Path is not directory:\n{:?}",
+                path
+            )))
+        }
+    }
+
+    pub fn push(&mut self, entitys: Vec<Entity>, progress: UsageProgress) {
+        self.entitys = entitys;
+        self.progress = progress;
+    }
+
+    /// Drops the children that were deleted or moved away.
+    pub fn retain_existing(&mut self) {
+        let before = self.entitys.len();
+
+        self.entitys.retain(|entity| entity.exists());
+
+        if self.entitys.len() != before {
+            self.progress.total = self.entitys.iter().map(|e| e.size().size_byte()).sum();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entitys.clear();
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.path.clone()
+    }
+
+    pub fn progress(&self) -> &UsageProgress {
+        &self.progress
+    }
+
+    pub fn same_filesystem(&self) -> bool {
+        !self.cross_filesystems
+    }
+
+    pub fn set_same_filesystem(&mut self, same_filesystem: bool) {
+        self.cross_filesystems = !same_filesystem;
+    }
+
+    pub fn entitys(&self) -> Vec<Entity> {
+        self.entitys.clone()
+    }
+}
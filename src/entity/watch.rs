@@ -0,0 +1,87 @@
+use crate::error::JujikError;
+use nix::sys::inotify::{AddWatchFlags, InitFlags, Inotify, WatchDescriptor};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+/// Inotify watches on the directories of the open tabs.
+///
+/// Reading never blocks, the controller polls `changes` from its loop.
+#[derive(Debug)]
+pub struct EntitysWatcher {
+    inotify: Inotify,
+    watches: HashMap<WatchDescriptor, PathBuf>,
+}
+
+impl EntitysWatcher {
+    pub fn new() -> Result<Self, JujikError> {
+        Ok(Self {
+            inotify: Inotify::init(InitFlags::IN_NONBLOCK | InitFlags::IN_CLOEXEC)?,
+            watches: HashMap::new(),
+        })
+    }
+
+    /// Watches exactly the given directories, the ones that can not be watched are left out.
+    pub fn watch(&mut self, pathbufs: &HashSet<PathBuf>) {
+        let unwatched: Vec<WatchDescriptor> = self
+            .watches
+            .iter()
+            .filter(|(_, pathbuf)| !pathbufs.contains(*pathbuf))
+            .map(|(wd, _)| *wd)
+            .collect();
+
+        for wd in unwatched {
+            let _ = self.inotify.rm_watch(wd);
+            self.watches.remove(&wd);
+        }
+
+        for pathbuf in pathbufs {
+            if !self.watches.values().any(|p| p == pathbuf)
+                && let Ok(wd) = self.inotify.add_watch(pathbuf, Self::flags())
+            {
+                self.watches.insert(wd, pathbuf.clone());
+            }
+        }
+    }
+
+    /// Paths that changed since the last call.
+    ///
+    /// A watched directory itself is returned when it was removed or moved,
+    /// or when events were lost and it has to be read again.
+    pub fn changes(&mut self) -> Vec<PathBuf> {
+        let mut pathbufs = Vec::new();
+
+        while let Ok(events) = self.inotify.read_events() {
+            for event in events {
+                if event.mask.contains(AddWatchFlags::IN_Q_OVERFLOW) {
+                    pathbufs.extend(self.watches.values().cloned());
+                } else if event.mask.contains(AddWatchFlags::IN_IGNORED) {
+                    self.watches.remove(&event.wd);
+                } else if let Some(dir) = self.watches.get(&event.wd) {
+                    pathbufs.push(match event.name {
+                        Some(name) => dir.join(name),
+                        None => dir.clone(),
+                    });
+                }
+            }
+        }
+
+        pathbufs.sort();
+        pathbufs.dedup();
+
+        pathbufs
+    }
+
+    fn flags() -> AddWatchFlags {
+        AddWatchFlags::IN_CREATE
+            | AddWatchFlags::IN_DELETE
+            | AddWatchFlags::IN_MODIFY
+            | AddWatchFlags::IN_ATTRIB
+            | AddWatchFlags::IN_MOVED_FROM
+            | AddWatchFlags::IN_MOVED_TO
+            | AddWatchFlags::IN_DELETE_SELF
+            | AddWatchFlags::IN_MOVE_SELF
+            | AddWatchFlags::IN_ONLYDIR
+    }
+}
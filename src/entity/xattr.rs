@@ -0,0 +1,174 @@
+use crate::error::JujikError;
+use rustix::{
+    fs::{XattrFlags, lgetxattr, llistxattr, lremovexattr, lsetxattr},
+    io::Errno,
+};
+use std::{fmt::Write, path::Path};
+
+// name, value
+pub type EntityXattr = (String, Vec<u8>);
+// name, value to set or `None` to remove
+pub type XattrChange = (String, Option<Vec<u8>>);
+
+/// Extended attributes of a path, links are not followed.
+pub struct EntityXattrs;
+
+impl EntityXattrs {
+    /// Every attribute with its value, sorted by name.
+    pub fn list(path: &Path) -> Result<Vec<EntityXattr>, JujikError> {
+        let mut xattrs = Vec::new();
+
+        for name in Self::names(path)? {
+            // Removed in between
+            if let Some(value) = Self::get(path, &name)? {
+                xattrs.push((name, value));
+            }
+        }
+
+        Ok(xattrs)
+    }
+
+    pub fn get(path: &Path, name: &str) -> Result<Option<Vec<u8>>, JujikError> {
+        let size = match lgetxattr(path, name, &mut [0u8; 0][..]) {
+            Ok(size) => size,
+            Err(Errno::NODATA) => return Ok(None),
+            Err(err) => return Err(JujikError::from(err)),
+        };
+
+        let mut value = vec![0u8; size];
+        let size = lgetxattr(path, name, &mut value[..])?;
+        value.truncate(size);
+
+        Ok(Some(value))
+    }
+
+    pub fn set(path: &Path, name: &str, value: &[u8]) -> Result<(), JujikError> {
+        Ok(lsetxattr(path, name, value, XattrFlags::empty())?)
+    }
+
+    pub fn remove(path: &Path, name: &str) -> Result<(), JujikError> {
+        match lremovexattr(path, name) {
+            Ok(()) | Err(Errno::NODATA) => Ok(()),
+            Err(err) => Err(JujikError::from(err)),
+        }
+    }
+
+    /// Sets every `Some` value and removes every `None` one.
+    pub fn apply(path: &Path, xattrs: &[XattrChange]) -> Result<(), JujikError> {
+        for (name, value) in xattrs {
+            match value {
+                Some(value) => Self::set(path, name, value)?,
+                None => Self::remove(path, name)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The value as text when it is printable UTF-8, a trailing NUL is left out.
+    pub fn text(value: &[u8]) -> Option<String> {
+        let value = value.strip_suffix(&[0]).unwrap_or(value);
+
+        std::str::from_utf8(value)
+            .ok()
+            .filter(|text| {
+                !text
+                    .chars()
+                    .any(|c| c.is_control() && c != '\n' && c != '\t')
+            })
+            .map(|text| text.to_string())
+    }
+
+    pub fn hex(value: &[u8]) -> String {
+        value.iter().fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+    }
+
+    /// Parses hex digits, whitespace is ignored.
+    pub fn parse_hex(hex: &str) -> Result<Vec<u8>, JujikError> {
+        let digits: Vec<char> = hex.chars().filter(|c| !c.is_whitespace()).collect();
+
+        if !digits.len().is_multiple_of(2) {
+            return Err(JujikError::Other(format!(
+                "Odd number of hex digits:\n{}",
+                hex
+            )));
+        }
+
+        digits
+            .chunks(2)
+            .map(|pair| {
+                u8::from_str_radix(&pair.iter().collect::<String>(), 16)
+                    .map_err(|_| JujikError::Other(format!("Invalid hex value:\n{}", hex)))
+            })
+            .collect()
+    }
+
+    /// The value as edited, hex digits or text.
+    pub fn value_str(value: &[u8], hex: bool) -> String {
+        if hex {
+            Self::hex(value)
+        } else {
+            Self::text(value).unwrap_or_default()
+        }
+    }
+
+    pub fn parse_value(value: &str, hex: bool) -> Result<Vec<u8>, JujikError> {
+        if hex {
+            Self::parse_hex(value)
+        } else {
+            Ok(value.as_bytes().to_vec())
+        }
+    }
+
+    /// Changes turning `before` into `after`, attributes missing from `after` are removed.
+    pub fn diff(before: &[EntityXattr], after: &[EntityXattr]) -> Vec<XattrChange> {
+        let removed = before
+            .iter()
+            .filter(|(name, _)| !after.iter().any(|(n, _)| n == name))
+            .map(|(name, _)| (name.clone(), None));
+
+        let changed = after
+            .iter()
+            .filter(|xattr| !before.contains(xattr))
+            .map(|(name, value)| (name.clone(), Some(value.clone())));
+
+        removed.chain(changed).collect()
+    }
+
+    /// POSIX ACLs are stored as attributes, they are edited on their own.
+    pub fn is_acl(name: &str) -> bool {
+        name.starts_with("system.posix_acl_")
+    }
+
+    /// Text of the value when possible, hex otherwise.
+    pub fn display(value: &[u8]) -> String {
+        Self::text(value).unwrap_or_else(|| format!("0x{}", Self::hex(value)))
+    }
+
+    fn names(path: &Path) -> Result<Vec<String>, JujikError> {
+        let size = match llistxattr(path, &mut [0u8; 0][..]) {
+            Ok(size) => size,
+            Err(Errno::NOTSUP) => return Ok(Vec::new()),
+            Err(err) => return Err(JujikError::from(err)),
+        };
+
+        if size == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut names = vec![0u8; size];
+        let size = llistxattr(path, &mut names[..])?;
+
+        let mut names: Vec<String> = names[..size]
+            .split(|b| *b == 0)
+            .filter(|name| !name.is_empty())
+            .map(|name| String::from_utf8_lossy(name).to_string())
+            .collect();
+        names.sort();
+
+        Ok(names)
+    }
+}
@@ -18,6 +18,8 @@ pub enum JujikError {
     Thread(Box<dyn Any + Send>),
     Nix(nix::errno::Errno),
     SerdeJson(serde_json::error::Error),
+    Regex(regex::Error),
+    Paths(Vec<(std::path::PathBuf, JujikError)>),
     Other(String),
 }
 
@@ -59,6 +61,12 @@ impl From<std::sync::mpsc::RecvTimeoutError> for JujikError {
     }
 }
 
+impl<T> From<std::sync::PoisonError<T>> for JujikError {
+    fn from(value: std::sync::PoisonError<T>) -> Self {
+        JujikError::Other(value.to_string())
+    }
+}
+
 impl From<eframe::Error> for JujikError {
     fn from(value: eframe::Error) -> Self {
         JujikError::EFrame(value)
@@ -83,12 +91,24 @@ impl From<nix::errno::Errno> for JujikError {
     }
 }
 
+impl From<rustix::io::Errno> for JujikError {
+    fn from(value: rustix::io::Errno) -> Self {
+        JujikError::IO(value.into())
+    }
+}
+
 impl From<serde_json::error::Error> for JujikError {
     fn from(value: serde_json::error::Error) -> Self {
         JujikError::SerdeJson(value)
     }
 }
 
+impl From<regex::Error> for JujikError {
+    fn from(value: regex::Error) -> Self {
+        JujikError::Regex(value)
+    }
+}
+
 impl std::error::Error for JujikError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -102,6 +122,8 @@ impl std::error::Error for JujikError {
             JujikError::Thread(_err) => None,
             JujikError::Nix(err) => Some(err),
             JujikError::SerdeJson(err) => Some(err),
+            JujikError::Regex(err) => Some(err),
+            JujikError::Paths(_errs) => None,
             JujikError::Other(_err) => None,
             JujikError::None => None,
         }
@@ -126,6 +148,12 @@ impl Display for JujikError {
                 JujikError::Thread(err) => format!("{:?}", err),
                 JujikError::Nix(err) => err.to_string(),
                 JujikError::SerdeJson(err) => err.to_string(),
+                JujikError::Regex(err) => err.to_string(),
+                JujikError::Paths(errs) => errs
+                    .iter()
+                    .map(|(pathbuf, err)| format!("{:?}: {}", pathbuf, err))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
                 JujikError::Other(err) => err.to_string(),
                 JujikError::None => "".to_string(),
             }
@@ -0,0 +1,257 @@
+use crate::error::JujikError;
+use std::{
+    fmt::Display,
+    fs::{self, symlink_metadata},
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+const JOB_PAUSE_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum JobKind {
+    #[default]
+    Copy,
+    Move,
+    Trash,
+    Remove,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum JobState {
+    #[default]
+    Running,
+    Paused,
+    Cancelled,
+    Failed,
+    Done,
+}
+
+/// Snapshot of a job sent to the view.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Job {
+    pub id: usize,
+    pub kind: JobKind,
+    pub state: JobState,
+    // done, total
+    pub files: (u64, u64),
+    pub bytes: (u64, u64),
+    pub current: PathBuf,
+    pub eta: Option<Duration>,
+    pub errors: String,
+}
+
+/// Progress and flow control shared between the model and the worker thread of a job.
+#[derive(Debug)]
+pub struct JobControl {
+    id: usize,
+    kind: JobKind,
+    started: Instant,
+    paused_for: AtomicU64,
+    paused: AtomicBool,
+    cancelled: AtomicBool,
+    files_done: AtomicU64,
+    files_total: AtomicU64,
+    bytes_done: AtomicU64,
+    bytes_total: AtomicU64,
+    current: Mutex<PathBuf>,
+}
+
+impl JobControl {
+    pub fn new(id: usize, kind: JobKind) -> Self {
+        Self {
+            id,
+            kind,
+            started: Instant::now(),
+            paused_for: AtomicU64::new(0),
+            paused: AtomicBool::new(false),
+            cancelled: AtomicBool::new(false),
+            files_done: AtomicU64::new(0),
+            files_total: AtomicU64::new(0),
+            bytes_done: AtomicU64::new(0),
+            bytes_total: AtomicU64::new(0),
+            current: Mutex::new(PathBuf::new()),
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Counts the files and bytes under every path, directories are not counted as files.
+    pub fn measure(pathbufs: &[PathBuf]) -> (u64, u64) {
+        let mut stack: Vec<PathBuf> = pathbufs.to_vec();
+        let (mut files, mut bytes) = (0, 0);
+
+        while let Some(pathbuf) = stack.pop() {
+            match symlink_metadata(&pathbuf) {
+                Ok(metadata) if metadata.is_dir() => {
+                    if let Ok(read_dir) = fs::read_dir(&pathbuf) {
+                        stack.extend(read_dir.flatten().map(|d| d.path()));
+                    }
+                }
+                Ok(metadata) => {
+                    files += 1;
+                    bytes += metadata.len();
+                }
+                Err(_) => {}
+            }
+        }
+
+        (files, bytes)
+    }
+
+    pub fn set_total(&self, files: u64, bytes: u64) {
+        self.files_total.store(files, Ordering::Relaxed);
+        self.bytes_total.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Blocks while the job is paused, fails once it is cancelled.
+    pub fn checkpoint(&self) -> Result<(), JujikError> {
+        while self.paused.load(Ordering::Relaxed) && !self.cancelled.load(Ordering::Relaxed) {
+            thread::sleep(JOB_PAUSE_INTERVAL);
+            self.paused_for
+                .fetch_add(JOB_PAUSE_INTERVAL.as_millis() as u64, Ordering::Relaxed);
+        }
+
+        if self.cancelled.load(Ordering::Relaxed) {
+            Err(JujikError::Other("Job cancelled".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn start(&self, pathbuf: &Path) {
+        if let Ok(mut current) = self.current.lock() {
+            current.clone_from(&pathbuf.to_path_buf());
+        }
+    }
+
+    pub fn add(&self, files: u64, bytes: u64) {
+        self.files_done.fetch_add(files, Ordering::Relaxed);
+        self.bytes_done.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn job(&self) -> Job {
+        let files = (
+            self.files_done.load(Ordering::Relaxed),
+            self.files_total.load(Ordering::Relaxed),
+        );
+        let bytes = (
+            self.bytes_done.load(Ordering::Relaxed),
+            self.bytes_total.load(Ordering::Relaxed),
+        );
+
+        let state = if self.cancelled.load(Ordering::Relaxed) {
+            JobState::Cancelled
+        } else if self.paused.load(Ordering::Relaxed) {
+            JobState::Paused
+        } else {
+            JobState::Running
+        };
+
+        Job {
+            id: self.id,
+            kind: self.kind,
+            state,
+            files,
+            bytes,
+            current: self.current.lock().map(|c| c.clone()).unwrap_or_default(),
+            eta: self.eta(files, bytes),
+            errors: String::new(),
+        }
+    }
+
+    // Bytes are the better measure, jobs without bytes (trash, remove) fall back to files
+    fn eta(&self, files: (u64, u64), bytes: (u64, u64)) -> Option<Duration> {
+        let (done, total) = if bytes.1 > 0 { bytes } else { files };
+        let active = self.started.elapsed().saturating_sub(Duration::from_millis(
+            self.paused_for.load(Ordering::Relaxed),
+        ));
+
+        if done == 0 || total < done {
+            None
+        } else {
+            Some(active.mul_f64((total - done) as f64 / done as f64))
+        }
+    }
+}
+
+impl Job {
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.state,
+            JobState::Cancelled | JobState::Failed | JobState::Done
+        )
+    }
+
+    pub fn fraction(&self) -> f32 {
+        let (done, total) = if self.bytes.1 > 0 {
+            self.bytes
+        } else {
+            self.files
+        };
+
+        if total == 0 {
+            if self.state == JobState::Done {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            done as f32 / total as f32
+        }
+    }
+}
+
+impl Display for JobKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                JobKind::Copy => "Copy",
+                JobKind::Move => "Move",
+                JobKind::Trash => "Trash",
+                JobKind::Remove => "Remove",
+            }
+        )
+    }
+}
+
+impl Display for JobState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                JobState::Running => "Running",
+                JobState::Paused => "Paused",
+                JobState::Cancelled => "Cancelled",
+                JobState::Failed => "Failed",
+                JobState::Done => "Done",
+            }
+        )
+    }
+}
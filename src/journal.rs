@@ -0,0 +1,316 @@
+use crate::{
+    entity::{
+        acl::EntityAcl,
+        kind::EntityKind,
+        link::EntityLink,
+        rename::EntitysRename,
+        transfer::{ConflictPolicy, EntitysTransfer},
+        trash::EntitysTrash,
+        xattr::{EntityXattrs, XattrChange},
+    },
+    error::JujikError,
+};
+use std::{
+    fs::{self, File, Permissions, symlink_metadata},
+    os::{
+        linux::fs::MetadataExt,
+        unix::{self, fs::PermissionsExt},
+    },
+    path::PathBuf,
+};
+
+const JOURNAL_LIMIT: usize = 100;
+
+/// One reversible change made by the model.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum JournalOperation {
+    // from, to
+    Rename(PathBuf, PathBuf),
+    // (from, to) of a batch rename
+    Renames(Vec<(PathBuf, PathBuf)>),
+    // source, copy
+    Copy(PathBuf, PathBuf),
+    Uncopy(PathBuf, PathBuf),
+    Create(PathBuf, EntityKind),
+    Remove(PathBuf, EntityKind),
+    // path, mode before, mode after
+    Permissions(PathBuf, u32, u32),
+    // path, (uid, gid) before, (uid, gid) after
+    Owners(PathBuf, (u32, u32), (u32, u32)),
+    // path, ACL before, ACL after
+    Acl(PathBuf, EntityAcl, EntityAcl),
+    // path, attributes before, attributes after
+    Xattrs(PathBuf, Vec<XattrChange>, Vec<XattrChange>),
+    // link, text before, text after
+    Link(PathBuf, PathBuf, PathBuf),
+    // original, trashed file, trash info
+    Trash(PathBuf, PathBuf, PathBuf),
+    Restore(PathBuf, PathBuf, PathBuf),
+}
+
+// dev, ino, mode, uid, gid, size, mtime, mtime_nsec
+type JournalState = (u64, u64, u32, u32, u32, u64, i64, i64);
+
+// State of a path right after an operation, `None` when it does not exist
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct JournalStamp {
+    pathbuf: PathBuf,
+    state: Option<JournalState>,
+}
+
+#[derive(Debug, Clone)]
+struct JournalRecord {
+    operations: Vec<JournalOperation>,
+    stamps: Vec<JournalStamp>,
+}
+
+/// Undo and redo stacks of the operations made by the model.
+///
+/// Every record keeps the state of the paths it touched, undo and redo
+/// refuse to run when any of them changed since.
+#[derive(Debug, Default)]
+pub struct Journal {
+    undo: Vec<JournalRecord>,
+    redo: Vec<JournalRecord>,
+}
+
+impl JournalOperation {
+    pub fn inverse(&self) -> Self {
+        match self.clone() {
+            Self::Rename(from, to) => Self::Rename(to, from),
+            Self::Renames(pairs) => {
+                Self::Renames(pairs.into_iter().map(|(from, to)| (to, from)).collect())
+            }
+            Self::Copy(source, copy) => Self::Uncopy(source, copy),
+            Self::Uncopy(source, copy) => Self::Copy(source, copy),
+            Self::Create(pathbuf, kind) => Self::Remove(pathbuf, kind),
+            Self::Remove(pathbuf, kind) => Self::Create(pathbuf, kind),
+            Self::Permissions(pathbuf, before, after) => Self::Permissions(pathbuf, after, before),
+            Self::Owners(pathbuf, before, after) => Self::Owners(pathbuf, after, before),
+            Self::Acl(pathbuf, before, after) => Self::Acl(pathbuf, after, before),
+            Self::Xattrs(pathbuf, before, after) => Self::Xattrs(pathbuf, after, before),
+            Self::Link(pathbuf, before, after) => Self::Link(pathbuf, after, before),
+            Self::Trash(original, files, info) => Self::Restore(original, files, info),
+            Self::Restore(original, files, info) => Self::Trash(original, files, info),
+        }
+    }
+
+    // Returns the operation as it was made, trashing picks new names every time
+    pub fn apply(&self) -> Result<Self, JujikError> {
+        match self {
+            Self::Rename(from, to) => {
+                Self::check_absent(to)?;
+                EntitysTransfer::rename(from, to)?;
+            }
+            Self::Renames(pairs) => EntitysRename::rename(pairs)?,
+            Self::Copy(source, copy) => {
+                Self::check_absent(copy)?;
+                EntitysTransfer::new(ConflictPolicy::Ask)
+                    .copy_pairs(&[(source.clone(), copy.clone())])?;
+            }
+            Self::Uncopy(_, copy) => EntitysTransfer::remove_path(copy)?,
+            Self::Create(pathbuf, kind) => {
+                if *kind == EntityKind::Directory {
+                    fs::create_dir(pathbuf)?;
+                } else {
+                    File::create_new(pathbuf)?;
+                }
+            }
+            Self::Remove(pathbuf, kind) => {
+                if *kind == EntityKind::Directory {
+                    fs::remove_dir(pathbuf)?;
+                } else {
+                    fs::remove_file(pathbuf)?;
+                }
+            }
+            Self::Permissions(pathbuf, _, after) => {
+                fs::set_permissions(pathbuf, Permissions::from_mode(*after))?;
+            }
+            Self::Owners(pathbuf, _, (uid, gid)) => {
+                unix::fs::lchown(pathbuf, Some(*uid), Some(*gid))?;
+            }
+            Self::Acl(pathbuf, _, after) => after.write(pathbuf)?,
+            Self::Xattrs(pathbuf, _, after) => EntityXattrs::apply(pathbuf, after)?,
+            Self::Link(pathbuf, _, after) => EntityLink::retarget(pathbuf, after)?,
+            Self::Trash(original, _, _) => {
+                let (files, info) = EntitysTrash::new()?.trash_path(original)?;
+
+                return Ok(Self::Trash(original.clone(), files, info));
+            }
+            Self::Restore(original, files, info) => {
+                EntitysTrash::restore_path(files, info, original)?;
+            }
+        }
+
+        Ok(self.clone())
+    }
+
+    fn paths(&self) -> Vec<PathBuf> {
+        match self.clone() {
+            Self::Rename(from, to) => vec![from, to],
+            Self::Renames(pairs) => pairs.into_iter().flat_map(|(f, t)| [f, t]).collect(),
+            // Undo removes the whole copy, every entity in it must be as it was copied
+            Self::Copy(_, copy) => Self::tree(copy),
+            Self::Uncopy(source, copy) => vec![source, copy],
+            Self::Create(pathbuf, _) | Self::Remove(pathbuf, _) => vec![pathbuf],
+            Self::Permissions(pathbuf, _, _)
+            | Self::Owners(pathbuf, _, _)
+            | Self::Acl(pathbuf, _, _)
+            | Self::Xattrs(pathbuf, _, _)
+            | Self::Link(pathbuf, _, _) => vec![pathbuf],
+            Self::Trash(original, files, _) | Self::Restore(original, files, _) => {
+                vec![original, files]
+            }
+        }
+    }
+
+    // `pathbuf` and everything under it, links are not followed
+    fn tree(pathbuf: PathBuf) -> Vec<PathBuf> {
+        let mut pathbufs = vec![pathbuf];
+        let mut idx = 0;
+
+        while let Some(pathbuf) = pathbufs.get(idx) {
+            if symlink_metadata(pathbuf).is_ok_and(|m| m.is_dir())
+                && let Ok(read_dir) = fs::read_dir(pathbuf)
+            {
+                let children: Vec<PathBuf> = read_dir.flatten().map(|d| d.path()).collect();
+                pathbufs.extend(children);
+            }
+
+            idx += 1;
+        }
+
+        pathbufs
+    }
+
+    fn check_absent(pathbuf: &PathBuf) -> Result<(), JujikError> {
+        if symlink_metadata(pathbuf).is_ok() {
+            Err(JujikError::Other(format!(
+                "Destination already exists:\n{:?}",
+                pathbuf
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl JournalStamp {
+    fn new(pathbuf: PathBuf) -> Self {
+        let state = symlink_metadata(&pathbuf).ok().map(|m| {
+            (
+                m.st_dev(),
+                m.st_ino(),
+                m.st_mode(),
+                m.st_uid(),
+                m.st_gid(),
+                m.st_size(),
+                m.st_mtime(),
+                m.st_mtime_nsec(),
+            )
+        });
+
+        Self { pathbuf, state }
+    }
+
+    fn changed(&self) -> bool {
+        Self::new(self.pathbuf.clone()).ne(self)
+    }
+}
+
+impl JournalRecord {
+    fn new(operations: Vec<JournalOperation>) -> Self {
+        let stamps = operations
+            .iter()
+            .flat_map(|o| o.paths())
+            .map(JournalStamp::new)
+            .collect();
+
+        Self { operations, stamps }
+    }
+
+    fn check(&self) -> Result<(), JujikError> {
+        if let Some(stamp) = self.stamps.iter().find(|s| s.changed()) {
+            Err(JujikError::Other(format!(
+                "Path changed since the operation:\n{:?}",
+                stamp.pathbuf
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Applies the inverse of every operation from the last one,
+    // a failure puts back the ones already reverted.
+    fn revert(&self) -> Result<Self, JujikError> {
+        let mut done: Vec<JournalOperation> = Vec::new();
+
+        for operation in self.operations.iter().rev() {
+            match operation.inverse().apply() {
+                Ok(operation) => done.push(operation),
+                Err(err) => {
+                    let failures: Vec<String> = done
+                        .iter()
+                        .rev()
+                        .filter_map(|operation| operation.inverse().apply().err())
+                        .map(|err| err.to_string())
+                        .collect();
+
+                    if failures.is_empty() {
+                        return Err(err);
+                    }
+
+                    // The first error is the cause, the others tell what is left half done
+                    return Err(JujikError::Other(format!(
+                        "{}\nCan not put back what was already reverted:\n{}",
+                        err,
+                        failures.join("\n")
+                    )));
+                }
+            }
+        }
+
+        Ok(Self::new(done))
+    }
+}
+
+impl Journal {
+    pub fn record(&mut self, operations: Vec<JournalOperation>) {
+        if operations.is_empty() {
+            return;
+        }
+
+        self.undo.push(JournalRecord::new(operations));
+        self.redo.clear();
+
+        if self.undo.len() > JOURNAL_LIMIT {
+            self.undo.remove(0);
+        }
+    }
+
+    pub fn undo(&mut self) -> Result<(), JujikError> {
+        Self::step(&mut self.undo, &mut self.redo, "undo")
+    }
+
+    pub fn redo(&mut self) -> Result<(), JujikError> {
+        Self::step(&mut self.redo, &mut self.undo, "redo")
+    }
+
+    fn step(
+        from: &mut Vec<JournalRecord>,
+        to: &mut Vec<JournalRecord>,
+        name: &str,
+    ) -> Result<(), JujikError> {
+        let record = from
+            .last()
+            .ok_or(JujikError::Other(format!("Nothing to {}", name)))?;
+
+        record.check()?;
+        let reverted = record.revert()?;
+
+        from.pop();
+        to.push(reverted);
+
+        Ok(())
+    }
+}
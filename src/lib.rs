@@ -3,6 +3,8 @@ pub mod config;
 pub mod controller;
 pub mod entity;
 pub mod error;
+pub mod job;
+pub mod journal;
 pub mod model;
 pub mod pin;
 pub mod tab;
@@ -1,26 +1,83 @@
-use crate::{commands::Command, error::JujikError, pin::Pin, tab::Tab};
+use crate::{
+    commands::Command,
+    entity::{
+        Entity, EntityDetails,
+        acl::EntityAcl,
+        dir_size::EntitysDirSizes,
+        filter::EntitysFilter,
+        find::{EntitysFinder, FindParameters, FindProgress},
+        kind::EntityKind,
+        link::EntityLink,
+        owner::{EntityOwners, OwnersChange},
+        rename::EntitysRename,
+        transfer::{ConflictPolicy, EntitysTransfer, TransferKind},
+        trash::EntitysTrash,
+        usage::{EntitysUsage, UsageProgress},
+        xattr::{EntityXattrs, XattrChange},
+    },
+    error::JujikError,
+    job::{JobControl, JobKind, JobState},
+    journal::{Journal, JournalOperation},
+    pin::Pin,
+    tab::Tab,
+};
 use std::{
+    collections::{HashMap, VecDeque},
     fs::{self, File},
-    os::unix,
-    sync::mpsc::{Receiver, Sender},
+    os::unix::fs::PermissionsExt,
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    },
     thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
+const MODEL_RECV_TIMEOUT: Duration = Duration::from_millis(8);
+const JOB_PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
 pub struct JujikModel {
     controller: Sender<Command>,
     model: Receiver<Command>,
+    finder_id: usize,
+    finders: HashMap<usize, Arc<AtomicBool>>,
+    job_id: usize,
+    jobs: HashMap<usize, Arc<JobControl>>,
+    journal: Arc<Mutex<Journal>>,
+    dir_sizes: Option<Sender<Command>>,
+    details: Option<Sender<Command>>,
 }
 
 impl JujikModel {
     pub fn new(controller: Sender<Command>, model: Receiver<Command>) -> Self {
-        Self { controller, model }
+        Self {
+            controller,
+            model,
+            finder_id: 0,
+            finders: HashMap::new(),
+            job_id: 0,
+            jobs: HashMap::new(),
+            journal: Arc::new(Mutex::new(Journal::default())),
+            dir_sizes: None,
+            details: None,
+        }
     }
 
-    pub fn run(self) -> Result<JoinHandle<Result<(), JujikError>>, JujikError> {
+    pub fn run(mut self) -> Result<JoinHandle<Result<(), JujikError>>, JujikError> {
         Ok(thread::Builder::new().name("Model".to_string()).spawn(
             move || -> Result<(), JujikError> {
+                let mut progress = Instant::now();
+
                 'event_loop: loop {
-                    if let Ok(command) = self.model.try_recv() {
+                    let command = match self.model.recv_timeout(MODEL_RECV_TIMEOUT) {
+                        Ok(command) => Some(command),
+                        Err(RecvTimeoutError::Timeout) => None,
+                        Err(RecvTimeoutError::Disconnected) => break 'event_loop,
+                    };
+
+                    if let Some(command) = command {
                         #[cfg(feature = "print_command")]
                         println!("Model: {:?}", command);
 
@@ -75,9 +132,36 @@ impl JujikModel {
                                 }
                             },
                             Command::CreateFinder(parameters) => {
-                                match Tab::tab_finder(parameters) {
+                                self.finder_id += 1;
+
+                                match Tab::tab_finder(self.finder_id, parameters.clone()) {
                                     Ok(new_tab) => {
                                         self.controller.send(Command::NewTab(None, new_tab))?;
+                                        self.spawn_finder(self.finder_id, parameters)?;
+                                    }
+                                    Err(err) => {
+                                        self.controller.send(Command::Error(Box::new(err)))?;
+                                    }
+                                }
+                            }
+                            Command::CreateTrash => match Tab::tab_trash() {
+                                Ok(new_tab) => {
+                                    self.controller.send(Command::NewTab(None, new_tab))?;
+                                }
+                                Err(err) => {
+                                    self.controller.send(Command::Error(Box::new(err)))?;
+                                }
+                            },
+                            Command::CreateUsage(pathbuf) => {
+                                self.finder_id += 1;
+
+                                match Tab::tab_usage(self.finder_id, pathbuf.clone()) {
+                                    Ok(new_tab) => {
+                                        let same_filesystem =
+                                            new_tab.usage().is_none_or(|u| u.same_filesystem());
+
+                                        self.controller.send(Command::NewTab(None, new_tab))?;
+                                        self.spawn_usage(self.finder_id, pathbuf, same_filesystem)?;
                                     }
                                     Err(err) => {
                                         self.controller.send(Command::Error(Box::new(err)))?;
@@ -89,6 +173,34 @@ impl JujikModel {
 
                                 self.controller.send(Command::NewTab(Some(idx), tab))?;
                             }
+                            // A Usage tab scans again from the new directory
+                            Command::ChangeTabDirectory(idx, mut tab, pathbuf)
+                                if tab.usage().is_some() =>
+                            {
+                                let path = tab.path();
+                                let pathbuf = pathbuf
+                                    .or_else(|| path.parent().map(|p| p.to_path_buf()))
+                                    .unwrap_or(path);
+
+                                if let Some(usage) = tab.usage() {
+                                    self.stop_finder(usage.id());
+                                }
+
+                                self.finder_id += 1;
+
+                                match tab.change_usage(self.finder_id, pathbuf.clone()) {
+                                    Ok(_) => {
+                                        let same_filesystem =
+                                            tab.usage().is_none_or(|u| u.same_filesystem());
+
+                                        self.controller.send(Command::NewTab(Some(idx), tab))?;
+                                        self.spawn_usage(self.finder_id, pathbuf, same_filesystem)?;
+                                    }
+                                    Err(err) => {
+                                        self.controller.send(Command::Error(Box::new(err)))?;
+                                    }
+                                }
+                            }
                             Command::ChangeTabDirectory(idx, mut tab, pathbuf) => {
                                 let res = if let Some(pathbuf) = pathbuf {
                                     tab.change_dir(pathbuf)
@@ -109,8 +221,21 @@ impl JujikModel {
                             // Entity
                             Command::CreateEntity(idx, tab, entity_ghost) => {
                                 if entity_ghost.is_dir() {
-                                    match fs::create_dir_all(entity_ghost.path()) {
+                                    let pathbuf = entity_ghost.path();
+                                    let exists = pathbuf.exists();
+
+                                    match fs::create_dir_all(&pathbuf) {
                                         Ok(_) => {
+                                            if !exists {
+                                                Self::record(
+                                                    &self.journal,
+                                                    vec![JournalOperation::Create(
+                                                        pathbuf,
+                                                        EntityKind::Directory,
+                                                    )],
+                                                );
+                                            }
+
                                             self.controller.send(Command::UpdateTab(idx))?;
                                         }
                                         Err(err) => {
@@ -118,8 +243,21 @@ impl JujikModel {
                                         }
                                     };
                                 } else {
-                                    match File::create(entity_ghost.path_with_name()) {
+                                    let pathbuf = entity_ghost.path_with_name();
+                                    let exists = pathbuf.exists();
+
+                                    match File::create(&pathbuf) {
                                         Ok(_) => {
+                                            if !exists {
+                                                Self::record(
+                                                    &self.journal,
+                                                    vec![JournalOperation::Create(
+                                                        pathbuf,
+                                                        EntityKind::File,
+                                                    )],
+                                                );
+                                            }
+
                                             self.controller.send(Command::UpdateTab(idx))?;
                                         }
                                         Err(err) => {
@@ -129,57 +267,193 @@ impl JujikModel {
                                 }
                             }
                             Command::DeleteEntitys(idx, tab, entitys) => {
-                                for entity in entitys {
-                                    let res = if entity.is_dir() {
-                                        fs::remove_dir_all(entity.path())
-                                    } else {
-                                        fs::remove_file(entity.path())
-                                    };
+                                match EntitysTrash::new() {
+                                    Ok(trash) => {
+                                        self.spawn_job(JobKind::Trash, move |control| {
+                                            control.set_total(entitys.len() as u64, 0);
+
+                                            let mut operations = Vec::new();
+                                            let mut failures = Vec::new();
 
-                                    if let Err(err) = res {
+                                            for entity in entitys {
+                                                if let Err(err) = control.checkpoint() {
+                                                    failures.push((entity.path(), err));
+                                                    break;
+                                                }
+
+                                                control.start(&entity.path());
+
+                                                match trash.trash_path(&entity.path()) {
+                                                    Ok((files, info)) => {
+                                                        operations.push(JournalOperation::Trash(
+                                                            entity.path(),
+                                                            files,
+                                                            info,
+                                                        ))
+                                                    }
+                                                    Err(err) => failures.push((entity.path(), err)),
+                                                }
+
+                                                control.add(1, 0);
+                                            }
+
+                                            (operations, Self::finish(failures))
+                                        })?
+                                    }
+                                    Err(err) => {
                                         self.controller.send(Command::Error(Box::new(err)))?;
                                     }
                                 }
-
-                                self.controller.send(Command::Update)?;
                             }
-                            Command::CopyEntitys(idx_tab, tab, idx_entity, entitys, pathbuf) => {
-                                for entity in entitys {
-                                    let mut pathbuf = pathbuf.clone();
-                                    pathbuf.push(entity.name_with_extension());
+                            Command::RemoveEntitys(_idx, _tab, entitys) => {
+                                self.spawn_job(JobKind::Remove, move |control| {
+                                    control.set_total(entitys.len() as u64, 0);
 
-                                    let res = fs::copy(entity.path(), pathbuf);
+                                    let mut failures = Vec::new();
 
-                                    if let Err(err) = res {
-                                        self.controller.send(Command::Error(Box::new(err)))?;
+                                    for entity in entitys {
+                                        if let Err(err) = control.checkpoint() {
+                                            failures.push((entity.path(), err));
+                                            break;
+                                        }
+
+                                        control.start(&entity.path());
+
+                                        if let Err(err) =
+                                            EntitysTransfer::remove_path(&entity.path())
+                                        {
+                                            failures.push((entity.path(), err));
+                                        }
+
+                                        control.add(1, 0);
                                     }
-                                }
 
-                                self.controller.send(Command::Update)?;
+                                    (Vec::new(), Self::finish(failures))
+                                })?;
                             }
-                            Command::MoveEntitys(idx_tab, tab, idx_entity, entitys, pathbuf) => {
-                                for entity in entitys {
-                                    let mut pathbuf = pathbuf.clone();
-                                    pathbuf.push(entity.name_with_extension());
+                            Command::CopyEntitys(
+                                idx_tab,
+                                tab,
+                                idx_entity,
+                                entitys,
+                                pathbuf,
+                                policy,
+                            ) => {
+                                let conflicts = EntitysTransfer::conflicts(&entitys, &pathbuf);
 
-                                    let res = fs::rename(entity.path(), pathbuf.clone());
+                                if policy == ConflictPolicy::Ask && !conflicts.is_empty() {
+                                    self.controller.send(Command::EntitysConflict(
+                                        TransferKind::Copy,
+                                        idx_tab,
+                                        tab,
+                                        idx_entity,
+                                        entitys,
+                                        pathbuf,
+                                        conflicts,
+                                    ))?;
+                                } else {
+                                    self.spawn_job(JobKind::Copy, move |control| {
+                                        let pathbufs: Vec<PathBuf> =
+                                            entitys.iter().map(|e| e.path()).collect();
+                                        let (files, bytes) = JobControl::measure(&pathbufs);
+                                        control.set_total(files, bytes);
 
-                                    if let Err(err) = res {
-                                        self.controller.send(Command::Error(Box::new(err)))?;
-                                    }
+                                        let mut transfer = EntitysTransfer::new(policy)
+                                            .with_control(control.clone());
+                                        let res = transfer.copy(&entitys, &pathbuf);
+
+                                        // Undone from the end, the copies go before the
+                                        // destinations they replaced come back
+                                        let operations = transfer
+                                            .trashed()
+                                            .iter()
+                                            .map(|(original, files, info)| {
+                                                JournalOperation::Trash(
+                                                    original.clone(),
+                                                    files.clone(),
+                                                    info.clone(),
+                                                )
+                                            })
+                                            .chain(transfer.done().iter().map(|(source, copy)| {
+                                                JournalOperation::Copy(source.clone(), copy.clone())
+                                            }))
+                                            .collect();
+
+                                        (operations, res)
+                                    })?;
                                 }
+                            }
+                            Command::MoveEntitys(
+                                idx_tab,
+                                tab,
+                                idx_entity,
+                                entitys,
+                                pathbuf,
+                                policy,
+                            ) => {
+                                let conflicts = EntitysTransfer::conflicts(&entitys, &pathbuf);
 
-                                self.controller.send(Command::Update)?;
+                                if policy == ConflictPolicy::Ask && !conflicts.is_empty() {
+                                    self.controller.send(Command::EntitysConflict(
+                                        TransferKind::Move,
+                                        idx_tab,
+                                        tab,
+                                        idx_entity,
+                                        entitys,
+                                        pathbuf,
+                                        conflicts,
+                                    ))?;
+                                } else {
+                                    self.spawn_job(JobKind::Move, move |control| {
+                                        let pathbufs: Vec<PathBuf> =
+                                            entitys.iter().map(|e| e.path()).collect();
+                                        let (files, bytes) = JobControl::measure(&pathbufs);
+                                        control.set_total(files, bytes);
+
+                                        let mut transfer = EntitysTransfer::new(policy)
+                                            .with_control(control.clone());
+                                        let res = transfer.relocate(&entitys, &pathbuf);
+
+                                        // Undone from the end, removed sources come back before
+                                        // their entitys and replaced destinations last
+                                        let operations = transfer
+                                            .trashed()
+                                            .iter()
+                                            .map(|(original, files, info)| {
+                                                JournalOperation::Trash(
+                                                    original.clone(),
+                                                    files.clone(),
+                                                    info.clone(),
+                                                )
+                                            })
+                                            .chain(transfer.done().iter().map(|(from, to)| {
+                                                JournalOperation::Rename(from.clone(), to.clone())
+                                            }))
+                                            .chain(transfer.removed().iter().map(|pathbuf| {
+                                                JournalOperation::Remove(
+                                                    pathbuf.clone(),
+                                                    EntityKind::Directory,
+                                                )
+                                            }))
+                                            .collect();
+
+                                        (operations, res)
+                                    })?;
+                                }
                             }
                             Command::ChangeEntityName(idx_tab, tab, idx_entity, entity, name) => {
                                 let mut path = entity.path_dir();
                                 path.push(name + "." + entity.extension_str().as_str());
 
-                                let res = fs::rename(entity.path(), path);
+                                let res = fs::rename(entity.path(), &path);
 
                                 if let Err(err) = res {
                                     self.controller.send(Command::Error(Box::new(err)))?;
                                 } else {
+                                    Self::record(
+                                        &self.journal,
+                                        vec![JournalOperation::Rename(entity.path(), path)],
+                                    );
                                     self.controller.send(Command::Update)?;
                                 }
                             }
@@ -193,14 +467,63 @@ impl JujikModel {
                                 let mut path = entity.path_dir();
                                 path.push(entity.name() + "." + extension.as_str());
 
-                                let res = fs::rename(entity.path(), path);
+                                let res = fs::rename(entity.path(), &path);
 
                                 if let Err(err) = res {
                                     self.controller.send(Command::Error(Box::new(err)))?;
                                 } else {
+                                    Self::record(
+                                        &self.journal,
+                                        vec![JournalOperation::Rename(entity.path(), path)],
+                                    );
                                     self.controller.send(Command::Update)?;
                                 }
                             }
+                            Command::RenameEntitys(_idx_tab, _tab, entitys, parameters) => {
+                                // The preview is made again, the entitys may have changed since the dialog
+                                let res = EntitysRename::new(parameters).and_then(|rename| {
+                                    let previews = rename.preview(&entitys);
+                                    let conflicts: Vec<(PathBuf, JujikError)> = previews
+                                        .iter()
+                                        .filter_map(|p| {
+                                            p.conflict.as_ref().map(|conflict| {
+                                                (
+                                                    p.destination.clone(),
+                                                    JujikError::Other(conflict.clone()),
+                                                )
+                                            })
+                                        })
+                                        .collect();
+
+                                    if !conflicts.is_empty() {
+                                        return Err(JujikError::Paths(conflicts));
+                                    }
+
+                                    let pairs: Vec<(PathBuf, PathBuf)> = previews
+                                        .into_iter()
+                                        .filter(|p| p.source != p.destination)
+                                        .map(|p| (p.source, p.destination))
+                                        .collect();
+
+                                    EntitysRename::rename(&pairs).map(|_| pairs)
+                                });
+
+                                match res {
+                                    Ok(pairs) => {
+                                        if !pairs.is_empty() {
+                                            Self::record(
+                                                &self.journal,
+                                                vec![JournalOperation::Renames(pairs)],
+                                            );
+                                        }
+
+                                        self.controller.send(Command::Update)?;
+                                    }
+                                    Err(err) => {
+                                        self.controller.send(Command::Error(Box::new(err)))?;
+                                    }
+                                }
+                            }
                             Command::ChangeEntityPermissions(
                                 idx_tab,
                                 tab,
@@ -208,13 +531,59 @@ impl JujikModel {
                                 entity,
                                 permissions,
                             ) => {
-                                let res = fs::set_permissions(entity.path(), permissions.into());
+                                let before = fs::symlink_metadata(entity.path())
+                                    .map(|m| m.permissions().mode() & 0o7777);
+                                let after = permissions.mode();
 
-                                if let Err(err) = res {
+                                let res = before.and_then(|before| {
+                                    fs::set_permissions(entity.path(), permissions.into())
+                                        .map(|_| before)
+                                });
+
+                                match res {
+                                    Ok(before) => {
+                                        Self::record(
+                                            &self.journal,
+                                            vec![JournalOperation::Permissions(
+                                                entity.path(),
+                                                before,
+                                                after,
+                                            )],
+                                        );
+                                        self.controller.send(Command::Update)?;
+                                    }
+                                    Err(err) => {
+                                        self.controller.send(Command::Error(Box::new(err)))?;
+                                    }
+                                }
+                            }
+                            Command::ChangeEntitysPermissions(
+                                _idx_tab,
+                                _tab,
+                                entitys,
+                                expression,
+                                recursive,
+                            ) => {
+                                let pathbufs: Vec<PathBuf> =
+                                    entitys.iter().map(|e| e.path()).collect();
+                                let (changes, failures) =
+                                    expression.apply_paths(&pathbufs, recursive);
+
+                                Self::record(
+                                    &self.journal,
+                                    changes
+                                        .into_iter()
+                                        .map(|(pathbuf, before, after)| {
+                                            JournalOperation::Permissions(pathbuf, before, after)
+                                        })
+                                        .collect(),
+                                );
+
+                                if let Err(err) = Self::finish(failures) {
                                     self.controller.send(Command::Error(Box::new(err)))?;
-                                } else {
-                                    self.controller.send(Command::Update)?;
                                 }
+
+                                self.controller.send(Command::Update)?;
                             }
                             Command::ChangeEntityOwners(
                                 idx_tab,
@@ -223,18 +592,122 @@ impl JujikModel {
                                 entity,
                                 owners,
                             ) => {
-                                let res = unix::fs::chown(
-                                    entity.path(),
+                                let (changes, failures) = EntityOwners::apply_paths(
+                                    &[entity.path()],
                                     Some(owners.uid()),
                                     Some(owners.gid()),
+                                    false,
+                                    true,
                                 );
 
-                                if let Err(err) = res {
+                                Self::record(&self.journal, Self::owners_operations(changes));
+
+                                if let Err(err) = Self::finish(failures) {
                                     self.controller.send(Command::Error(Box::new(err)))?;
                                 } else {
                                     self.controller.send(Command::Update)?;
                                 }
                             }
+                            Command::ChangeEntitysOwners(
+                                _idx_tab,
+                                _tab,
+                                entitys,
+                                (uid, gid),
+                                recursive,
+                                follow,
+                            ) => {
+                                let pathbufs: Vec<PathBuf> =
+                                    entitys.iter().map(|e| e.path()).collect();
+                                let (changes, failures) = EntityOwners::apply_paths(
+                                    &pathbufs, uid, gid, recursive, follow,
+                                );
+
+                                Self::record(&self.journal, Self::owners_operations(changes));
+
+                                if let Err(err) = Self::finish(failures) {
+                                    self.controller.send(Command::Error(Box::new(err)))?;
+                                }
+
+                                self.controller.send(Command::Update)?;
+                            }
+                            Command::ChangeEntityAcl(_idx_tab, _tab, _idx_entity, entity, acl) => {
+                                let res = EntityAcl::new(&entity.path())
+                                    .and_then(|before| acl.write(&entity.path()).map(|_| before));
+
+                                match res {
+                                    Ok(before) => {
+                                        Self::record(
+                                            &self.journal,
+                                            vec![JournalOperation::Acl(entity.path(), before, acl)],
+                                        );
+                                        self.controller.send(Command::Update)?;
+                                    }
+                                    Err(err) => {
+                                        self.controller.send(Command::Error(Box::new(err)))?;
+                                    }
+                                }
+                            }
+                            Command::ChangeEntityXattrs(
+                                _idx_tab,
+                                _tab,
+                                _idx_entity,
+                                entity,
+                                xattrs,
+                            ) => {
+                                let path = entity.path();
+                                let res = xattrs
+                                    .iter()
+                                    .map(|(name, _)| {
+                                        EntityXattrs::get(&path, name).map(|v| (name.clone(), v))
+                                    })
+                                    .collect::<Result<Vec<XattrChange>, JujikError>>()
+                                    .and_then(|before| {
+                                        EntityXattrs::apply(&path, &xattrs).map(|_| before)
+                                    });
+
+                                match res {
+                                    Ok(before) => {
+                                        Self::record(
+                                            &self.journal,
+                                            vec![JournalOperation::Xattrs(path, before, xattrs)],
+                                        );
+                                        self.controller.send(Command::Update)?;
+                                    }
+                                    Err(err) => {
+                                        self.controller.send(Command::Error(Box::new(err)))?;
+                                    }
+                                }
+                            }
+                            Command::ChangeEntityLink(
+                                _idx_tab,
+                                _tab,
+                                _idx_entity,
+                                entity,
+                                text,
+                            ) => {
+                                let res = fs::read_link(entity.path())
+                                    .map_err(JujikError::from)
+                                    .and_then(|before| {
+                                        EntityLink::retarget(&entity.path(), &text).map(|_| before)
+                                    });
+
+                                match res {
+                                    Ok(before) => {
+                                        Self::record(
+                                            &self.journal,
+                                            vec![JournalOperation::Link(
+                                                entity.path(),
+                                                before,
+                                                text,
+                                            )],
+                                        );
+                                        self.controller.send(Command::Update)?;
+                                    }
+                                    Err(err) => {
+                                        self.controller.send(Command::Error(Box::new(err)))?;
+                                    }
+                                }
+                            }
                             Command::ChangeEntityContent(idx, tab, entity, content) => {
                                 let res = fs::write(entity.path(), content);
 
@@ -249,6 +722,119 @@ impl JujikModel {
 
                                 self.controller.send(Command::NewTab(Some(idx), tab))?;
                             }
+                            Command::ChangeTabFilter(idx, mut tab, filter) => {
+                                match tab.set_filter(filter) {
+                                    Ok(_) => {
+                                        self.controller.send(Command::NewTab(Some(idx), tab))?;
+                                    }
+                                    Err(err) => {
+                                        self.controller.send(Command::Error(Box::new(err)))?;
+                                    }
+                                }
+                            }
+
+                            // Find
+                            Command::UpdateFind(idx, mut tab, parameters) => {
+                                if let Some(finder) = tab.finder() {
+                                    self.stop_finder(finder.id());
+                                }
+
+                                self.finder_id += 1;
+
+                                match tab.change_finder(self.finder_id, parameters.clone()) {
+                                    Ok(_) => {
+                                        self.controller.send(Command::NewTab(Some(idx), tab))?;
+                                        self.spawn_finder(self.finder_id, parameters)?;
+                                    }
+                                    Err(err) => {
+                                        self.controller.send(Command::Error(Box::new(err)))?;
+                                    }
+                                }
+                            }
+                            Command::StopFind(_idx, tab) => {
+                                if let Some(finder) = tab.finder() {
+                                    self.stop_finder(finder.id());
+                                }
+                            }
+
+                            // Usage
+                            Command::StopUsage(_idx, tab) => {
+                                if let Some(usage) = tab.usage() {
+                                    self.stop_finder(usage.id());
+                                }
+                            }
+
+                            // Directory size
+                            command @ (Command::ComputeDirSizes(_)
+                            | Command::InvalidateDirSizes(_)) => {
+                                self.send_dir_sizes(command)?;
+                            }
+
+                            // Details
+                            command @ Command::ReadDetails(_) => {
+                                self.send_details(command)?;
+                            }
+
+                            // Journal
+                            Command::Undo => {
+                                let res = self.journal.lock().map_err(JujikError::from);
+
+                                if let Err(err) = res.and_then(|mut journal| journal.undo()) {
+                                    self.controller.send(Command::Error(Box::new(err)))?;
+                                }
+
+                                self.controller.send(Command::Update)?;
+                            }
+                            Command::Redo => {
+                                let res = self.journal.lock().map_err(JujikError::from);
+
+                                if let Err(err) = res.and_then(|mut journal| journal.redo()) {
+                                    self.controller.send(Command::Error(Box::new(err)))?;
+                                }
+
+                                self.controller.send(Command::Update)?;
+                            }
+
+                            // Trash
+                            Command::RestoreEntitys(_idx, _tab, trash_entitys) => {
+                                let res = EntitysTrash::new()
+                                    .and_then(|trash| trash.restore(&trash_entitys));
+
+                                if let Err(err) = res {
+                                    self.controller.send(Command::Error(Box::new(err)))?;
+                                }
+
+                                self.controller.send(Command::Update)?;
+                            }
+                            Command::PurgeEntitys(_idx, _tab, trash_entitys) => {
+                                let res = EntitysTrash::new()
+                                    .and_then(|trash| trash.purge(&trash_entitys));
+
+                                if let Err(err) = res {
+                                    self.controller.send(Command::Error(Box::new(err)))?;
+                                }
+
+                                self.controller.send(Command::Update)?;
+                            }
+
+                            // Job
+                            Command::PauseJob(id) => {
+                                if let Some(control) = self.jobs.get(&id) {
+                                    control.pause();
+                                    self.controller.send(Command::JobProgress(control.job()))?;
+                                }
+                            }
+                            Command::ResumeJob(id) => {
+                                if let Some(control) = self.jobs.get(&id) {
+                                    control.resume();
+                                    self.controller.send(Command::JobProgress(control.job()))?;
+                                }
+                            }
+                            Command::CancelJob(id) => {
+                                if let Some(control) = self.jobs.get(&id) {
+                                    control.cancel();
+                                }
+                            }
 
                             // Other
                             Command::Drop => break 'event_loop,
@@ -256,7 +842,10 @@ impl JujikModel {
                         }
                     };
 
-                    std::thread::sleep(std::time::Duration::from_millis(8));
+                    if progress.elapsed() >= JOB_PROGRESS_INTERVAL {
+                        self.send_progress()?;
+                        progress = Instant::now();
+                    }
                 }
 
                 Ok(self.send_drop()?)
@@ -264,7 +853,312 @@ impl JujikModel {
         )?)
     }
 
-    fn send_drop(&self) -> Result<(), JujikError> {
+    fn spawn_finder(&mut self, id: usize, parameters: FindParameters) -> Result<(), JujikError> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let controller = self.controller.clone();
+
+        self.finders.retain(|_, stop| Arc::strong_count(stop) > 1);
+        self.finders.insert(id, stop.clone());
+
+        thread::Builder::new()
+            .name("Finder".to_string())
+            .spawn(move || {
+                let res = EntitysFinder::search(&parameters, &stop, |entitys, progress| {
+                    controller
+                        .send(Command::FindEntitys(id, entitys, progress))
+                        .is_ok()
+                });
+
+                if let Err(err) = res {
+                    let _ = controller.send(Command::FindEntitys(
+                        id,
+                        Vec::new(),
+                        FindProgress::default(),
+                    ));
+                    let _ = controller.send(Command::Error(Box::new(err)));
+                }
+            })?;
+
+        Ok(())
+    }
+
+    // Usage scans share the ids and stop flags of the finders
+    fn spawn_usage(
+        &mut self,
+        id: usize,
+        pathbuf: PathBuf,
+        same_filesystem: bool,
+    ) -> Result<(), JujikError> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let controller = self.controller.clone();
+
+        self.finders.retain(|_, stop| Arc::strong_count(stop) > 1);
+        self.finders.insert(id, stop.clone());
+
+        thread::Builder::new()
+            .name("Usage".to_string())
+            .spawn(move || {
+                let res = EntitysUsage::scan(
+                    &pathbuf,
+                    &EntitysFilter::default(),
+                    same_filesystem,
+                    &stop,
+                    |entitys, progress| {
+                        controller
+                            .send(Command::UsageEntitys(id, entitys, progress))
+                            .is_ok()
+                    },
+                );
+
+                if let Err(err) = res {
+                    let _ = controller.send(Command::UsageEntitys(
+                        id,
+                        Vec::new(),
+                        UsageProgress::default(),
+                    ));
+                    let _ = controller.send(Command::Error(Box::new(err)));
+                }
+            })?;
+
+        Ok(())
+    }
+
+    fn stop_finder(&mut self, id: usize) {
+        if let Some(stop) = self.finders.remove(&id) {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    // One worker measures directories for every tab, it starts with the first request
+    fn send_dir_sizes(&mut self, command: Command) -> Result<(), JujikError> {
+        if self.dir_sizes.is_none() {
+            let (sender, receiver) = mpsc::channel();
+            let controller = self.controller.clone();
+
+            thread::Builder::new()
+                .name("DirSizes".to_string())
+                .spawn(move || Self::measure_dirs(receiver, controller))?;
+
+            self.dir_sizes = Some(sender);
+        }
+
+        if let Some(sender) = &self.dir_sizes {
+            sender.send(command)?;
+        }
+
+        Ok(())
+    }
+
+    // Cached sizes are answered at once, the others in batches as they are measured
+    fn measure_dirs(receiver: Receiver<Command>, controller: Sender<Command>) {
+        let mut dir_sizes = EntitysDirSizes::default();
+        let mut queue: VecDeque<PathBuf> = VecDeque::new();
+        let mut measured = Vec::new();
+        let mut sent = Instant::now();
+
+        loop {
+            let mut commands = Vec::new();
+
+            if queue.is_empty() {
+                match receiver.recv() {
+                    Ok(command) => commands.push(command),
+                    Err(_) => return,
+                }
+            }
+
+            commands.extend(receiver.try_iter());
+
+            for command in commands {
+                match command {
+                    Command::ComputeDirSizes(pathbufs) => {
+                        let (cached, unknown): (Vec<_>, Vec<_>) = pathbufs
+                            .into_iter()
+                            .map(|pathbuf| (dir_sizes.cached(&pathbuf), pathbuf))
+                            .partition(|(size, _)| size.is_some());
+
+                        let cached: Vec<_> = cached
+                            .into_iter()
+                            .filter_map(|(size, pathbuf)| Some((pathbuf, size?)))
+                            .collect();
+
+                        if !cached.is_empty() && controller.send(Command::DirSizes(cached)).is_err()
+                        {
+                            return;
+                        }
+
+                        queue = unknown.into_iter().map(|(_, pathbuf)| pathbuf).collect();
+                    }
+                    Command::InvalidateDirSizes(pathbufs) => {
+                        for pathbuf in pathbufs {
+                            dir_sizes.invalidate(&pathbuf);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(pathbuf) = queue.pop_front()
+                && let Some(size) = dir_sizes.size(&pathbuf)
+            {
+                measured.push((pathbuf, size));
+            }
+
+            if !measured.is_empty() && (queue.is_empty() || sent.elapsed() >= JOB_PROGRESS_INTERVAL)
+            {
+                if controller
+                    .send(Command::DirSizes(std::mem::take(&mut measured)))
+                    .is_err()
+                {
+                    return;
+                }
+
+                sent = Instant::now();
+            }
+        }
+    }
+
+    // One worker reads the details of entitys for every tab, it starts with the first request
+    fn send_details(&mut self, command: Command) -> Result<(), JujikError> {
+        if self.details.is_none() {
+            let (sender, receiver) = mpsc::channel();
+            let controller = self.controller.clone();
+
+            thread::Builder::new()
+                .name("Details".to_string())
+                .spawn(move || Self::read_details(receiver, controller))?;
+
+            self.details = Some(sender);
+        }
+
+        if let Some(sender) = &self.details {
+            sender.send(command)?;
+        }
+
+        Ok(())
+    }
+
+    // Entitys are read again with their details and sent in batches
+    fn read_details(receiver: Receiver<Command>, controller: Sender<Command>) {
+        let mut queue: VecDeque<(PathBuf, EntityDetails)> = VecDeque::new();
+        let mut read = Vec::new();
+        let mut sent = Instant::now();
+
+        loop {
+            if queue.is_empty() {
+                match receiver.recv() {
+                    Ok(Command::ReadDetails(entitys)) => queue = entitys.into(),
+                    Ok(_) => {}
+                    Err(_) => return,
+                }
+            }
+
+            if let Some(Command::ReadDetails(entitys)) = receiver.try_iter().last() {
+                queue = entitys.into();
+            }
+
+            if let Some((pathbuf, details)) = queue.pop_front()
+                && let Ok(entity) = Entity::new(pathbuf)
+            {
+                read.push(entity.with_details(details));
+            }
+
+            if !read.is_empty() && (queue.is_empty() || sent.elapsed() >= JOB_PROGRESS_INTERVAL) {
+                if controller
+                    .send(Command::Details(std::mem::take(&mut read)))
+                    .is_err()
+                {
+                    return;
+                }
+
+                sent = Instant::now();
+            }
+        }
+    }
+
+    // The worker records the journal and sends the last progress of the job once it is over
+    fn spawn_job<F>(&mut self, kind: JobKind, work: F) -> Result<(), JujikError>
+    where
+        F: FnOnce(&Arc<JobControl>) -> (Vec<JournalOperation>, Result<(), JujikError>)
+            + Send
+            + 'static,
+    {
+        self.job_id += 1;
+
+        let control = Arc::new(JobControl::new(self.job_id, kind));
+        let controller = self.controller.clone();
+        let journal = self.journal.clone();
+
+        self.jobs
+            .retain(|_, control| Arc::strong_count(control) > 1);
+        self.jobs.insert(self.job_id, control.clone());
+        self.controller.send(Command::JobProgress(control.job()))?;
+
+        thread::Builder::new()
+            .name("Job".to_string())
+            .spawn(move || {
+                let (operations, res) = work(&control);
+                Self::record(&journal, operations);
+
+                let mut job = control.job();
+
+                match res {
+                    _ if control.is_cancelled() => job.state = JobState::Cancelled,
+                    Ok(()) => job.state = JobState::Done,
+                    Err(err) => {
+                        job.state = JobState::Failed;
+                        job.errors = err.to_string();
+                        let _ = controller.send(Command::Error(Box::new(err)));
+                    }
+                }
+
+                let _ = controller.send(Command::JobProgress(job));
+                let _ = controller.send(Command::Update);
+            })?;
+
+        Ok(())
+    }
+
+    fn send_progress(&mut self) -> Result<(), JujikError> {
+        self.jobs
+            .retain(|_, control| Arc::strong_count(control) > 1);
+
+        for control in self.jobs.values() {
+            self.controller.send(Command::JobProgress(control.job()))?;
+        }
+
+        Ok(())
+    }
+
+    fn record(journal: &Mutex<Journal>, operations: Vec<JournalOperation>) {
+        if let Ok(mut journal) = journal.lock() {
+            journal.record(operations);
+        }
+    }
+
+    fn owners_operations(changes: Vec<OwnersChange>) -> Vec<JournalOperation> {
+        changes
+            .into_iter()
+            .map(|(pathbuf, before, after)| JournalOperation::Owners(pathbuf, before, after))
+            .collect()
+    }
+
+    fn finish(failures: Vec<(PathBuf, JujikError)>) -> Result<(), JujikError> {
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(JujikError::Paths(failures))
+        }
+    }
+
+    fn send_drop(&mut self) -> Result<(), JujikError> {
+        for (_, stop) in self.finders.drain() {
+            stop.store(true, Ordering::Relaxed);
+        }
+
+        for (_, control) in self.jobs.drain() {
+            control.cancel();
+        }
+
         let _controller_drop = self.controller.send(Command::Drop);
         Ok(())
     }
@@ -1,12 +1,18 @@
 use crate::{
     entity::{
-        Entity,
+        Entity, EntityDetails,
+        filter::EntitysFilter,
         find::{EntitysFinder, FindParameters},
+        trash::{EntitysTrash, TrashEntity},
+        usage::EntitysUsage,
     },
     error::JujikError,
 };
 use serde::{Deserialize, Serialize};
-use std::{fmt::Display, fs::read_dir, path::PathBuf};
+use std::{cmp::Ordering, fmt::Display, fs::read_dir, path::PathBuf, thread};
+
+// Smaller directories are read on the calling thread
+const PARALLEL_READ_MIN: usize = 256;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum TabKind {
@@ -15,6 +21,8 @@ pub enum TabKind {
     View,
     Editor,
     Find,
+    Trash,
+    Usage,
 }
 
 #[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -25,8 +33,17 @@ pub enum SortField {
     Permissions,
     Owners,
     Size,
+    Allocated,
+    Links,
+    Inode,
+    Device,
     Modification,
     Creation,
+    Access,
+    Change,
+    Type,
+    // name of the extended attribute
+    Xattr(String),
 }
 
 #[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -40,6 +57,16 @@ pub enum SortDirection {
 pub struct SortBy {
     pub field: SortField,
     pub direction: SortDirection,
+    // decides between entitys equal by `field`
+    #[serde(default)]
+    pub then: Option<(SortField, SortDirection)>,
+    // digits in names compare as numbers, `file2` before `file10`
+    #[serde(default)]
+    pub natural: bool,
+    #[serde(default)]
+    pub ignore_case: bool,
+    #[serde(default)]
+    pub dirs_first: bool,
 }
 
 #[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -50,16 +77,27 @@ pub enum TabContent {
     View(Entity),
     Editor(Entity),
     Find(EntitysFinder),
+    Trash(Vec<TrashEntity>),
+    Usage(EntitysUsage),
 }
 
 #[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct Tab {
     name: String,
     content: TabContent,
+    #[serde(default)]
+    filter: EntitysFilter,
 }
 
 impl Tab {
     pub fn tab_entitys(pathbuf: PathBuf) -> Result<Self, JujikError> {
+        Self::tab_entitys_filtered(pathbuf, EntitysFilter::default())
+    }
+
+    pub fn tab_entitys_filtered(
+        pathbuf: PathBuf,
+        filter: EntitysFilter,
+    ) -> Result<Self, JujikError> {
         Ok(Self {
             name: format!(
                 "{}: {}",
@@ -69,8 +107,9 @@ impl Tab {
             content: TabContent::Entitys(
                 SortBy::default(),
                 pathbuf.clone(),
-                Tab::read_dir(pathbuf.clone())?,
+                Tab::read_dir(pathbuf.clone(), &filter)?,
             ),
+            filter,
         })
     }
 
@@ -81,7 +120,11 @@ impl Tab {
                 TabKind::View,
                 Entity::get_name(pathbuf.as_path())?
             ),
-            content: TabContent::View(Entity::new(pathbuf.clone())?),
+            content: TabContent::View(Entity::new(pathbuf.clone())?.with_details(EntityDetails {
+                mime: true,
+                ..Default::default()
+            })),
+            filter: EntitysFilter::default(),
         })
     }
 
@@ -92,18 +135,45 @@ impl Tab {
                 TabKind::Editor,
                 Entity::get_name(pathbuf.as_path())?
             ),
-            content: TabContent::Editor(Entity::new(pathbuf.clone())?),
+            content: TabContent::Editor(Entity::new(pathbuf.clone())?.with_details(
+                EntityDetails {
+                    mime: true,
+                    ..Default::default()
+                },
+            )),
+            filter: EntitysFilter::default(),
         })
     }
 
-    pub fn tab_finder(parameters: FindParameters) -> Result<Self, JujikError> {
+    pub fn tab_finder(id: usize, parameters: FindParameters) -> Result<Self, JujikError> {
         Ok(Self {
             name: format!(
                 "{}: {}",
                 TabKind::Find,
                 Entity::get_name(parameters.path.as_path())?
             ),
-            content: TabContent::Find(EntitysFinder::find(parameters)?),
+            content: TabContent::Find(EntitysFinder::new(id, parameters)?),
+            filter: EntitysFilter::default(),
+        })
+    }
+
+    pub fn tab_trash() -> Result<Self, JujikError> {
+        Ok(Self {
+            name: format!("{}", TabKind::Trash),
+            content: TabContent::Trash(EntitysTrash::new()?.list()),
+            filter: EntitysFilter::default(),
+        })
+    }
+
+    pub fn tab_usage(id: usize, pathbuf: PathBuf) -> Result<Self, JujikError> {
+        Ok(Self {
+            name: format!(
+                "{}: {}",
+                TabKind::Usage,
+                Entity::get_name(pathbuf.as_path())?
+            ),
+            content: TabContent::Usage(EntitysUsage::new(id, pathbuf)?),
+            filter: EntitysFilter::default(),
         })
     }
 
@@ -117,6 +187,8 @@ impl Tab {
             TabContent::View(entity) => entity.path_dir(),
             TabContent::Editor(entity) => entity.path_dir(),
             TabContent::Find(finder) => finder.parameters().path,
+            TabContent::Trash(_) => PathBuf::new(),
+            TabContent::Usage(usage) => usage.path(),
             TabContent::None => PathBuf::new(),
         }
     }
@@ -129,6 +201,17 @@ impl Tab {
         }
     }
 
+    pub fn filter(&self) -> &EntitysFilter {
+        &self.filter
+    }
+
+    /// Reads the directory again with the new filter.
+    pub fn set_filter(&mut self, filter: EntitysFilter) -> Result<(), JujikError> {
+        self.filter = filter;
+
+        self.update_entitys()
+    }
+
     pub fn set_name(&mut self, name: String) {
         self.name.clone_from(&name);
     }
@@ -167,46 +250,134 @@ impl Tab {
         }
     }
 
+    pub fn finder(&self) -> Option<&EntitysFinder> {
+        if let TabContent::Find(finder) = &self.content {
+            Some(finder)
+        } else {
+            None
+        }
+    }
+
+    pub fn finder_mut(&mut self) -> Option<&mut EntitysFinder> {
+        if let TabContent::Find(finder) = &mut self.content {
+            Some(finder)
+        } else {
+            None
+        }
+    }
+
+    pub fn usage(&self) -> Option<&EntitysUsage> {
+        if let TabContent::Usage(usage) = &self.content {
+            Some(usage)
+        } else {
+            None
+        }
+    }
+
+    pub fn usage_mut(&mut self) -> Option<&mut EntitysUsage> {
+        if let TabContent::Usage(usage) = &mut self.content {
+            Some(usage)
+        } else {
+            None
+        }
+    }
+
+    pub fn trash_entitys(&self) -> Option<Vec<TrashEntity>> {
+        if let TabContent::Trash(trash_entitys) = &self.content {
+            Some(trash_entitys.clone())
+        } else {
+            None
+        }
+    }
+
     pub fn sort(&mut self) {
         if let TabContent::Entitys(sortby, _, entitys) = &mut self.content {
-            match sortby.field {
-                SortField::Name => entitys.sort_by(|e1, e2| e1.name().cmp(&e2.name())),
-                SortField::Extension => {
-                    entitys.sort_by(|e1, e2| e1.extension().cmp(e2.extension()))
-                }
-                SortField::Permissions => {
-                    entitys.sort_by(|e1, e2| e1.permissions().cmp(e2.permissions()))
-                }
-                SortField::Owners => entitys.sort_by(|e1, e2| e1.owners().cmp(e2.owners())),
-                SortField::Size => entitys.sort_by(|e1, e2| e1.size().cmp(e2.size())),
-                SortField::Modification => {
-                    entitys.sort_by(|e1, e2| e1.modification().cmp(e2.modification()))
-                }
-                SortField::Creation => entitys.sort_by(|e1, e2| e1.creation().cmp(e2.creation())),
-            }
-
-            match sortby.direction {
-                SortDirection::Ascending => {}
-                SortDirection::Descending => entitys.reverse(),
-            }
+            entitys.sort_by(|e1, e2| sortby.compare(e1, e2));
         }
     }
 
-    fn read_dir(pathbuf: PathBuf) -> Result<Vec<Entity>, JujikError> {
-        let mut entitys: Vec<Entity> = Vec::new();
+    fn read_dir(pathbuf: PathBuf, filter: &EntitysFilter) -> Result<Vec<Entity>, JujikError> {
+        let mut paths: Vec<PathBuf> = Vec::new();
+        let ignore = filter.ignore_files(&pathbuf);
 
         for dir_entry in read_dir(pathbuf.clone())? {
-            if let Ok(entity) = Entity::new(dir_entry?.path()) {
-                entitys.push(entity);
+            let dir_entry = dir_entry?;
+            // The type comes with the directory entry, no stat yet
+            let is_dir = dir_entry.file_type().is_ok_and(|t| t.is_dir());
+
+            if filter.allows(&dir_entry.path(), is_dir, &ignore) {
+                paths.push(dir_entry.path());
             }
         }
 
-        Ok(entitys)
+        let workers = thread::available_parallelism().map_or(1, |n| n.get());
+
+        if paths.len() < PARALLEL_READ_MIN || workers == 1 {
+            return Ok(Self::read_entitys(paths));
+        }
+
+        let chunk_len = paths.len().div_ceil(workers);
+
+        Ok(thread::scope(|scope| {
+            let handles: Vec<_> = paths
+                .chunks(chunk_len)
+                .map(|chunk| scope.spawn(|| Self::read_entitys(chunk.to_vec())))
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().map_err(JujikError::from))
+                .collect::<Result<Vec<_>, _>>()
+        })?
+        .into_iter()
+        .flatten()
+        .collect())
+    }
+
+    // Entries that vanished since the listing are skipped
+    fn read_entitys(paths: Vec<PathBuf>) -> Vec<Entity> {
+        paths
+            .into_iter()
+            .filter_map(|path| Entity::new(path).ok())
+            .collect()
     }
 
+    // The filter and the sort order stay with the tab
     pub fn change_dir(&mut self, pathbuf: PathBuf) -> Result<(), JujikError> {
-        if let TabContent::Entitys(_, _, _) = &self.content {
-            *self = Tab::tab_entitys(pathbuf)?;
+        if let TabContent::Entitys(sortby, _, _) = &self.content {
+            let sortby = sortby.clone();
+
+            *self = Tab::tab_entitys_filtered(pathbuf, self.filter.clone())?;
+            self.set_sortby(&sortby);
+        }
+
+        Ok(())
+    }
+
+    pub fn change_finder(
+        &mut self,
+        id: usize,
+        parameters: FindParameters,
+    ) -> Result<(), JujikError> {
+        if let TabContent::Find(finder) = &mut self.content {
+            *finder = EntitysFinder::new(id, parameters)?;
+        }
+
+        Ok(())
+    }
+
+    /// Points a Usage tab to another directory, the scan is started by the model.
+    pub fn change_usage(&mut self, id: usize, pathbuf: PathBuf) -> Result<(), JujikError> {
+        if let TabContent::Usage(usage) = &mut self.content {
+            let same_filesystem = usage.same_filesystem();
+
+            *usage = EntitysUsage::new(id, pathbuf.clone())?;
+            usage.set_same_filesystem(same_filesystem);
+            self.name = format!(
+                "{}: {}",
+                TabKind::Usage,
+                Entity::get_name(pathbuf.as_path())?
+            );
         }
 
         Ok(())
@@ -214,9 +385,12 @@ impl Tab {
 
     pub fn change_dir_back(&mut self) -> Result<(), JujikError> {
         match &self.content {
-            TabContent::Entitys(_, _, _) => {
+            TabContent::Entitys(sortby, _, _) => {
+                let sortby = sortby.clone();
+
                 if let Some(parent) = self.path().parent() {
-                    *self = Tab::tab_entitys(parent.to_path_buf())?;
+                    *self = Tab::tab_entitys_filtered(parent.to_path_buf(), self.filter.clone())?;
+                    self.set_sortby(&sortby);
                 }
             }
             _ => {}
@@ -229,17 +403,259 @@ impl Tab {
         if let Some(entitys) = self.entitys_mut() {
             entitys.clear();
         }
+
+        if let TabContent::Trash(trash_entitys) = &mut self.content {
+            trash_entitys.clear();
+        }
+
+        if let Some(usage) = self.usage_mut() {
+            usage.clear();
+        }
+    }
+
+    /// Reads again the entitys at `pathbufs` that are in the directory of the tab,
+    /// the directory itself is read whole. Returns whether the tab changed.
+    pub fn update_paths(&mut self, pathbufs: &[PathBuf]) -> bool {
+        let mut changed = false;
+
+        if let TabContent::Entitys(_, dir, entitys) = &mut self.content {
+            let ignore = self.filter.ignore_files(dir);
+
+            for pathbuf in pathbufs {
+                if pathbuf == dir {
+                    *entitys = Tab::read_dir(dir.clone(), &self.filter).unwrap_or_default();
+                    changed = true;
+                } else if pathbuf.parent() == Some(dir.as_path())
+                    && let Some(name) = pathbuf.file_name()
+                {
+                    let name = name.to_string_lossy();
+
+                    entitys.retain(|e| e.name_with_extension() != name);
+
+                    if let Ok(entity) = Entity::new(pathbuf.clone())
+                        && self.filter.allows(pathbuf, entity.is_dir(), &ignore)
+                    {
+                        entitys.push(entity);
+                    }
+
+                    changed = true;
+                }
+            }
+        }
+
+        changed
     }
 
     pub fn update_entitys(&mut self) -> Result<(), JujikError> {
         if let TabContent::Entitys(_, pathbuf, entitys) = &mut self.content {
-            *entitys = Tab::read_dir(pathbuf.clone())?;
+            *entitys = Tab::read_dir(pathbuf.clone(), &self.filter)?;
+        }
+
+        if let TabContent::Trash(trash_entitys) = &mut self.content {
+            *trash_entitys = EntitysTrash::new()?.list();
+        }
+
+        if let Some(usage) = self.usage_mut() {
+            usage.retain_existing();
         }
 
         Ok(())
     }
 }
 
+impl SortField {
+    /// Every field an entitys tab can be sorted by.
+    pub fn fields(xattrs: &[String]) -> Vec<SortField> {
+        let mut fields = vec![
+            SortField::Name,
+            SortField::Extension,
+            SortField::Permissions,
+            SortField::Owners,
+            SortField::Size,
+            SortField::Allocated,
+            SortField::Links,
+            SortField::Inode,
+            SortField::Device,
+            SortField::Modification,
+            SortField::Creation,
+            SortField::Access,
+            SortField::Change,
+            SortField::Type,
+        ];
+
+        fields.extend(xattrs.iter().cloned().map(SortField::Xattr));
+        fields
+    }
+}
+
+impl SortDirection {
+    pub fn toggle(&self) -> Self {
+        match self {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        }
+    }
+
+    fn apply(&self, ordering: Ordering) -> Ordering {
+        match self {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        }
+    }
+}
+
+impl SortBy {
+    /// Sorts by `field`, a second pick of the same field flips the direction.
+    pub fn sort_by_field(&mut self, field: SortField) {
+        if self.field == field {
+            self.direction = self.direction.toggle();
+        } else {
+            self.field = field;
+            self.direction = SortDirection::Ascending;
+        }
+
+        if self
+            .then
+            .as_ref()
+            .is_some_and(|(then, _)| *then == self.field)
+        {
+            self.then = None;
+        }
+    }
+
+    /// Makes `field` the secondary key, a second pick flips its direction.
+    pub fn then_by_field(&mut self, field: SortField) {
+        if self.field == field {
+            return;
+        }
+
+        self.then = match self.then.take() {
+            Some((then, direction)) if then == field => Some((then, direction.toggle())),
+            _ => Some((field, SortDirection::Ascending)),
+        };
+    }
+
+    // Directories stay first whatever the direction
+    pub fn compare(&self, e1: &Entity, e2: &Entity) -> Ordering {
+        let dirs = if self.dirs_first {
+            e2.is_dir().cmp(&e1.is_dir())
+        } else {
+            Ordering::Equal
+        };
+
+        dirs.then_with(|| {
+            self.direction
+                .apply(self.compare_field(&self.field, e1, e2))
+        })
+        .then_with(|| match &self.then {
+            Some((field, direction)) => direction.apply(self.compare_field(field, e1, e2)),
+            None => Ordering::Equal,
+        })
+    }
+
+    fn compare_field(&self, field: &SortField, e1: &Entity, e2: &Entity) -> Ordering {
+        match field {
+            SortField::Name => self.compare_names(&e1.name(), &e2.name()),
+            SortField::Extension => match (e1.extension(), e2.extension()) {
+                (Some(ext1), Some(ext2)) => self.compare_names(ext1, ext2),
+                (ext1, ext2) => ext1.cmp(ext2),
+            },
+            SortField::Permissions => e1.permissions().cmp(e2.permissions()),
+            SortField::Owners => e1.owners().cmp(e2.owners()),
+            SortField::Size => e1.size().cmp(e2.size()),
+            SortField::Allocated => e1.allocated().cmp(e2.allocated()),
+            SortField::Links => e1.links().cmp(&e2.links()),
+            SortField::Inode => e1.inode().cmp(&e2.inode()),
+            SortField::Device => e1.device().cmp(&e2.device()),
+            SortField::Modification => e1.modification().cmp(e2.modification()),
+            SortField::Creation => e1.creation().cmp(e2.creation()),
+            SortField::Access => e1.access().cmp(e2.access()),
+            SortField::Change => e1.change().cmp(e2.change()),
+            SortField::Type => e1.mime().cmp(e2.mime()),
+            SortField::Xattr(name) => e1.xattr(name).cmp(&e2.xattr(name)),
+        }
+    }
+
+    /// Names equal up to case or leading zeros still get a fixed order.
+    pub fn compare_names(&self, name1: &str, name2: &str) -> Ordering {
+        if !self.natural && !self.ignore_case {
+            return name1.cmp(name2);
+        }
+
+        let (mut chars1, mut chars2) = (name1.chars().peekable(), name2.chars().peekable());
+
+        loop {
+            let ordering = match (chars1.peek(), chars2.peek()) {
+                (None, None) => break,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(c1), Some(c2))
+                    if self.natural && c1.is_ascii_digit() && c2.is_ascii_digit() =>
+                {
+                    let number1 = Self::take_number(&mut chars1);
+                    let number2 = Self::take_number(&mut chars2);
+
+                    // No parsing, a run of digits may not fit any integer
+                    number1
+                        .len()
+                        .cmp(&number2.len())
+                        .then_with(|| number1.cmp(&number2))
+                }
+                (Some(_), Some(_)) => {
+                    let (c1, c2) = (chars1.next().unwrap(), chars2.next().unwrap());
+
+                    if self.ignore_case {
+                        c1.to_lowercase().cmp(c2.to_lowercase())
+                    } else {
+                        c1.cmp(&c2)
+                    }
+                }
+            };
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        name1.cmp(name2)
+    }
+
+    // Digits without the leading zeros
+    fn take_number(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+        let mut number = String::new();
+
+        while let Some(c) = chars.next_if(char::is_ascii_digit) {
+            if !(number.is_empty() && c == '0') {
+                number.push(c);
+            }
+        }
+
+        number
+    }
+}
+
+impl Display for SortField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortField::Name => write!(f, "Name"),
+            SortField::Extension => write!(f, "Extension"),
+            SortField::Permissions => write!(f, "Permissions"),
+            SortField::Owners => write!(f, "Owners"),
+            SortField::Size => write!(f, "Size"),
+            SortField::Allocated => write!(f, "Allocated"),
+            SortField::Links => write!(f, "Links"),
+            SortField::Inode => write!(f, "Inode"),
+            SortField::Device => write!(f, "Device"),
+            SortField::Modification => write!(f, "Modification"),
+            SortField::Creation => write!(f, "Creation"),
+            SortField::Access => write!(f, "Access"),
+            SortField::Change => write!(f, "Change"),
+            SortField::Type => write!(f, "Type"),
+            SortField::Xattr(name) => write!(f, "Xattr {}", name),
+        }
+    }
+}
+
 impl Display for TabKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -250,6 +666,8 @@ impl Display for TabKind {
                 TabKind::View => "View",
                 TabKind::Editor => "Editor",
                 TabKind::Find => "Find",
+                TabKind::Trash => "Trash",
+                TabKind::Usage => "Usage",
                 TabKind::None => "None",
             }
         )
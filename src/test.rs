@@ -2,10 +2,18 @@
 mod entity_tests {
     use crate::entity::{
         Entity,
+        date::EntityDate,
         kind::EntityKind,
-        permission::{EntityPermissionsCategory, EntityPermissionsKind},
+        permission::{
+            EntityPermissions, EntityPermissionsCategory, EntityPermissionsKind,
+            EntityPermissionsSpecial,
+        },
+    };
+    use std::{
+        fs::{self, File, Permissions, create_dir},
+        os::unix::fs::{MetadataExt, PermissionsExt},
+        time::{Duration, SystemTime},
     };
-    use std::fs::{File, create_dir};
     use tempfile::TempDir;
 
     #[test]
@@ -52,6 +60,23 @@ mod entity_tests {
         assert!(!ghost_entity.exists());
     }
 
+    #[test]
+    fn test_entity_dates() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("dates.txt");
+        let file = File::create(&file_path).unwrap();
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        file.set_modified(modified).unwrap();
+
+        let entity = Entity::new(file_path).unwrap();
+
+        assert_eq!(entity.modification(), &EntityDate::new(modified));
+        assert_ne!(entity.creation(), entity.modification());
+        assert!(!entity.access().is_none());
+        assert!(entity.change() > entity.modification());
+        assert_eq!(EntityDate::default().date_str(), "Unknown");
+    }
+
     #[test]
     fn test_entity_content_operations() {
         let temp_dir = TempDir::new().unwrap();
@@ -80,6 +105,52 @@ mod entity_tests {
             EntityPermissionsKind::Write
         ));
     }
+
+    #[test]
+    fn test_entity_permissions_special() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("special.sh");
+        File::create(&file_path).unwrap();
+        fs::set_permissions(&file_path, Permissions::from_mode(0o4754)).unwrap();
+
+        let entity = Entity::new(file_path).unwrap();
+        let permissions = entity.permissions();
+
+        assert_eq!(permissions.mode(), 0o4754);
+        assert!(permissions.has_special(EntityPermissionsSpecial::Setuid));
+        assert_eq!(permissions.symbolic(), "rwsr-xr--");
+
+        assert_eq!(EntityPermissions::new(0o2640).symbolic(), "rw-r-S---");
+        assert_eq!(EntityPermissions::new(0o1777).symbolic(), "rwxrwxrwt");
+        assert_eq!(EntityPermissions::new(0o1776).symbolic(), "rwxrwxrwT");
+
+        let mut permissions = EntityPermissions::new(0o755);
+        permissions.set_special(EntityPermissionsSpecial::Setgid);
+        assert_eq!(permissions.mode(), 0o2755);
+        permissions.unset_special(EntityPermissionsSpecial::Setgid);
+        assert_eq!(permissions.mode(), 0o755);
+    }
+
+    #[test]
+    fn test_entity_allocated_links_inode() {
+        let temp_dir = TempDir::new().unwrap();
+        let sparse = temp_dir.path().join("sparse.img");
+        File::create(&sparse)
+            .unwrap()
+            .set_len(16 * 1024 * 1024)
+            .unwrap();
+        fs::hard_link(&sparse, temp_dir.path().join("link.img")).unwrap();
+
+        let entity = Entity::new(sparse.clone()).unwrap();
+        let metadata = fs::symlink_metadata(&sparse).unwrap();
+
+        assert_eq!(entity.size().size_byte(), 16 * 1024 * 1024);
+        assert!(entity.allocated() < entity.size());
+        assert_eq!(entity.links(), 2);
+        assert_eq!(entity.inode(), metadata.ino());
+        assert_eq!(entity.device(), metadata.dev());
+        assert!(entity.device_str().contains(':'));
+    }
 }
 
 #[cfg(test)]
@@ -111,6 +182,110 @@ mod model_tests {
     }
 }
 
+#[cfg(test)]
+mod model_find_tests {
+    use crate::{
+        commands::Command,
+        entity::find::{FindParameters, FindProgress},
+        model::JujikModel,
+        tab::Tab,
+    };
+    use std::{sync::mpsc, time::Duration};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_finder_streams_results() {
+        let (controller_tx, controller_rx) = mpsc::channel();
+        let (model_tx, model_rx) = mpsc::channel();
+
+        let model_handle = JujikModel::new(controller_tx, model_rx).run().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        for i in 0..10 {
+            std::fs::write(temp_dir.path().join(format!("file_{}.txt", i)), "").unwrap();
+        }
+
+        model_tx
+            .send(Command::CreateFinder(FindParameters {
+                path: temp_dir.path().to_path_buf(),
+                ..Default::default()
+            }))
+            .unwrap();
+
+        let id = match controller_rx.recv_timeout(Duration::from_secs(1)).unwrap() {
+            Command::NewTab(None, tab) => {
+                let finder = tab.finder().unwrap();
+                assert!(finder.progress().running);
+                finder.id()
+            }
+            _ => panic!("Expected NewTab command"),
+        };
+
+        let mut found = 0;
+        let mut progress = FindProgress::default();
+
+        while let Ok(Command::FindEntitys(batch_id, entitys, batch_progress)) =
+            controller_rx.recv_timeout(Duration::from_secs(1))
+        {
+            assert_eq!(batch_id, id);
+            found += entitys.len();
+            progress = batch_progress;
+
+            if !progress.running {
+                break;
+            }
+        }
+
+        assert!(!progress.running);
+        assert_eq!(found, 10);
+        assert_eq!(progress.matches, 10);
+
+        model_tx.send(Command::Drop).unwrap();
+        let _ = model_handle.join();
+    }
+
+    #[test]
+    fn test_update_finder_keeps_tab() {
+        let (controller_tx, controller_rx) = mpsc::channel();
+        let (model_tx, model_rx) = mpsc::channel();
+
+        let model_handle = JujikModel::new(controller_tx, model_rx).run().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut tab = Tab::tab_finder(
+            0,
+            FindParameters {
+                path: temp_dir.path().to_path_buf(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        tab.set_name("Sources".to_string());
+
+        let parameters = FindParameters {
+            path: temp_dir.path().to_path_buf(),
+            extension: "rs".to_string(),
+            ..Default::default()
+        };
+
+        model_tx
+            .send(Command::UpdateFind(3, tab, parameters.clone()))
+            .unwrap();
+
+        match controller_rx.recv_timeout(Duration::from_secs(1)).unwrap() {
+            Command::NewTab(Some(3), tab) => {
+                assert_eq!(tab.name(), "Sources");
+                assert_eq!(tab.finder().unwrap().parameters(), parameters);
+                assert_ne!(tab.finder().unwrap().id(), 0);
+            }
+            _ => panic!("Expected NewTab command"),
+        }
+
+        model_tx.send(Command::Drop).unwrap();
+        let _ = model_handle.join();
+    }
+}
+
 #[cfg(test)]
 mod controller_tests {
     use crate::{commands::Command, config::Config, controller::JujikController};
@@ -218,6 +393,7 @@ mod functional_tests {
         tab.set_sortby(&SortBy {
             field: SortField::Size,
             direction: SortDirection::Ascending,
+            ..Default::default()
         });
 
         let entities = if let Some(entities) = tab.entitys() {
@@ -254,41 +430,2167 @@ mod functional_tests {
 }
 
 #[cfg(test)]
-mod benchmarks {
+mod find_tests {
+    use crate::entity::{
+        find::{EntitysFinder, FindParameters},
+        kind::EntityKind,
+        size::EntitySize,
+    };
+    use std::{fs::create_dir, sync::atomic::AtomicBool};
+    use tempfile::TempDir;
+
+    fn find_tree() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+
+        std::fs::write(temp_dir.path().join("small.txt"), "a").unwrap();
+        std::fs::write(temp_dir.path().join("large.txt"), "a".repeat(1000)).unwrap();
+        create_dir(temp_dir.path().join("nested")).unwrap();
+        std::fs::write(temp_dir.path().join("nested/code.rs"), "fn main() {}").unwrap();
+
+        temp_dir
+    }
+
+    fn names(finder: &EntitysFinder) -> Vec<String> {
+        let mut names: Vec<String> = finder
+            .entitys()
+            .iter()
+            .map(|e| e.name_with_extension())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_find_unset_parameters_match_everything() {
+        let temp_dir = find_tree();
+
+        let finder = EntitysFinder::find(FindParameters {
+            path: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            names(&finder),
+            vec!["code.rs", "large.txt", "nested", "small.txt"]
+        );
+    }
+
+    #[test]
+    fn test_find_name_filters() {
+        let temp_dir = find_tree();
+
+        let finder = EntitysFinder::find(FindParameters {
+            path: temp_dir.path().to_path_buf(),
+            extension: ".txt".to_string(),
+            regex: "^s".to_string(),
+            kind: EntityKind::File,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(names(&finder), vec!["small.txt"]);
+
+        let invalid = EntitysFinder::find(FindParameters {
+            path: temp_dir.path().to_path_buf(),
+            regex: "(".to_string(),
+            ..Default::default()
+        });
+
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_find_search_stops() {
+        let temp_dir = find_tree();
+        let mut batches = 0;
+
+        let progress = EntitysFinder::search(
+            &FindParameters {
+                path: temp_dir.path().to_path_buf(),
+                ..Default::default()
+            },
+            &AtomicBool::new(true),
+            |entitys, progress| {
+                batches += 1;
+                assert!(entitys.is_empty());
+                assert!(!progress.running);
+                true
+            },
+        )
+        .unwrap();
+
+        assert_eq!(batches, 1);
+        assert_eq!(progress.matches, 0);
+    }
+
+    #[test]
+    fn test_find_size_range_is_inclusive() {
+        let temp_dir = find_tree();
+
+        let finder = EntitysFinder::find(FindParameters {
+            path: temp_dir.path().to_path_buf(),
+            kind: EntityKind::File,
+            size: (EntitySize::new(12), EntitySize::new(1000)),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(names(&finder), vec!["code.rs", "large.txt"]);
+    }
+}
+
+#[cfg(test)]
+mod transfer_tests {
     use crate::{
-        commands::Command,
-        controller::JujikController,
-        entity::{Entity, kind::EntityKind},
-        model::JujikModel,
-        tab::Tab,
+        entity::{
+            Entity,
+            kind::EntityKind,
+            transfer::{ConflictPolicy, EntitysTransfer},
+            trash::EntitysTrash,
+        },
+        error::JujikError,
+        journal::{Journal, JournalOperation},
     };
     use std::{
-        sync::mpsc,
-        time::{Duration, Instant},
+        fs::{self, Permissions, create_dir},
+        os::unix::fs::{PermissionsExt, symlink},
+        time::{Duration, SystemTime},
     };
     use tempfile::TempDir;
 
     #[test]
-    fn benchmark_entity_creation() {
+    fn test_copy_dir_recursive_keeps_metadata() {
         let temp_dir = TempDir::new().unwrap();
-        let test_files: Vec<_> = (0..1000)
-            .map(|i| {
-                let path = temp_dir.path().join(format!("file_{}.txt", i));
-                std::fs::write(&path, "content").unwrap();
-                path
-            })
+        let source = temp_dir.path().join("source");
+        let target = temp_dir.path().join("target");
+        let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        create_dir(&source).unwrap();
+        create_dir(&target).unwrap();
+        create_dir(source.join("nested")).unwrap();
+        fs::write(source.join("nested/script.sh"), "echo").unwrap();
+        fs::set_permissions(
+            source.join("nested/script.sh"),
+            Permissions::from_mode(0o750),
+        )
+        .unwrap();
+        fs::File::options()
+            .write(true)
+            .open(source.join("nested/script.sh"))
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+        symlink("nested/script.sh", source.join("link")).unwrap();
+
+        EntitysTransfer::new(ConflictPolicy::Ask)
+            .copy(&[Entity::new(source).unwrap()], &target)
+            .unwrap();
+
+        let copied = target.join("source/nested/script.sh");
+        let metadata = fs::metadata(&copied).unwrap();
+
+        assert_eq!(fs::read_to_string(&copied).unwrap(), "echo");
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o750);
+        assert_eq!(metadata.modified().unwrap(), mtime);
+        assert_eq!(
+            fs::read_link(target.join("source/link")).unwrap(),
+            std::path::PathBuf::from("nested/script.sh")
+        );
+    }
+
+    #[test]
+    fn test_copy_conflict_policies() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("target");
+
+        create_dir(&target).unwrap();
+        fs::write(temp_dir.path().join("file.txt"), "new").unwrap();
+        fs::write(target.join("file.txt"), "old").unwrap();
+
+        let entitys = [Entity::new(temp_dir.path().join("file.txt")).unwrap()];
+
+        assert_eq!(
+            EntitysTransfer::conflicts(&entitys, &target),
+            vec![target.join("file.txt")]
+        );
+        assert!(matches!(
+            EntitysTransfer::new(ConflictPolicy::Ask).copy(&entitys, &target),
+            Err(JujikError::Paths(failures)) if failures.len() == 1
+        ));
+
+        EntitysTransfer::new(ConflictPolicy::Skip)
+            .copy(&entitys, &target)
+            .unwrap();
+        assert_eq!(fs::read_to_string(target.join("file.txt")).unwrap(), "old");
+
+        EntitysTransfer::new(ConflictPolicy::Rename)
+            .copy(&entitys, &target)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(target.join("file (1).txt")).unwrap(),
+            "new"
+        );
+
+        EntitysTransfer::new(ConflictPolicy::Overwrite)
+            .with_trash(EntitysTrash::from_path(temp_dir.path().join("Trash")))
+            .copy(&entitys, &target)
+            .unwrap();
+        assert_eq!(fs::read_to_string(target.join("file.txt")).unwrap(), "new");
+    }
+
+    #[test]
+    fn test_copy_overwrite_trashes_destination_and_undo_restores() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = EntitysTrash::from_path(temp_dir.path().join("Trash"));
+        let target = temp_dir.path().join("target");
+
+        // A file over a whole directory tree
+        fs::create_dir_all(target.join("item/nested")).unwrap();
+        fs::write(target.join("item/nested/keep.txt"), "keep").unwrap();
+        fs::write(temp_dir.path().join("item"), "new").unwrap();
+
+        let entitys = [Entity::new(temp_dir.path().join("item")).unwrap()];
+        let mut transfer = EntitysTransfer::new(ConflictPolicy::Overwrite).with_trash(trash);
+        transfer.copy(&entitys, &target).unwrap();
+
+        assert_eq!(fs::read_to_string(target.join("item")).unwrap(), "new");
+        assert_eq!(transfer.done().len(), 1);
+        assert_eq!(transfer.trashed().len(), 1);
+        // No partial copy left behind
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 1);
+
+        let mut journal = Journal::default();
+        journal.record(
+            transfer
+                .trashed()
+                .iter()
+                .map(|(original, files, info)| {
+                    JournalOperation::Trash(original.clone(), files.clone(), info.clone())
+                })
+                .chain(
+                    transfer
+                        .done()
+                        .iter()
+                        .map(|(source, copy)| JournalOperation::Copy(source.clone(), copy.clone())),
+                )
+                .collect(),
+        );
+        journal.undo().unwrap();
+
+        assert_eq!(
+            fs::read_to_string(target.join("item/nested/keep.txt")).unwrap(),
+            "keep"
+        );
+    }
+
+    #[test]
+    fn test_copy_overwrite_failure_keeps_destination() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("target");
+
+        create_dir(&target).unwrap();
+        fs::write(target.join("pipe"), "old").unwrap();
+        nix::unistd::mkfifo(
+            &temp_dir.path().join("pipe"),
+            nix::sys::stat::Mode::from_bits_truncate(0o644),
+        )
+        .unwrap();
+
+        let entitys = [Entity::new(temp_dir.path().join("pipe")).unwrap()];
+        let mut transfer = EntitysTransfer::new(ConflictPolicy::Overwrite)
+            .with_trash(EntitysTrash::from_path(temp_dir.path().join("Trash")));
+
+        assert!(transfer.copy(&entitys, &target).is_err());
+        assert_eq!(fs::read_to_string(target.join("pipe")).unwrap(), "old");
+        assert!(transfer.trashed().is_empty());
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_move_across_copies_then_deletes() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+
+        create_dir(&source).unwrap();
+        create_dir(source.join("nested")).unwrap();
+        fs::write(source.join("nested/file.txt"), "content").unwrap();
+        symlink("nested", source.join("link")).unwrap();
+
+        EntitysTransfer::move_across(&source, &temp_dir.path().join("moved")).unwrap();
+
+        assert!(!source.exists());
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("moved/nested/file.txt")).unwrap(),
+            "content"
+        );
+        assert!(temp_dir.path().join("moved/link").is_symlink());
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_move_failure_keeps_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+
+        create_dir(&source).unwrap();
+        fs::write(source.join("file.txt"), "content").unwrap();
+
+        assert!(
+            EntitysTransfer::move_across(&source, &temp_dir.path().join("missing/moved")).is_err()
+        );
+        assert_eq!(
+            fs::read_to_string(source.join("file.txt")).unwrap(),
+            "content"
+        );
+
+        let target = temp_dir.path().join("target");
+        create_dir(&target).unwrap();
+        fs::write(target.join("source"), "old").unwrap();
+
+        let mut transfer = EntitysTransfer::new(ConflictPolicy::Overwrite)
+            .with_trash(EntitysTrash::from_path(temp_dir.path().join("Trash")));
+        transfer
+            .relocate(&[Entity::new(source.clone()).unwrap()], &target)
+            .unwrap();
+
+        assert!(!source.exists());
+        assert_eq!(
+            fs::read_to_string(target.join("source/file.txt")).unwrap(),
+            "content"
+        );
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 1);
+        assert_eq!(fs::read_to_string(&transfer.trashed()[0].1).unwrap(), "old");
+    }
+
+    #[test]
+    fn test_move_merges_dirs_and_undo_restores_overwritten() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = EntitysTrash::from_path(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join("source/dir");
+        let target = temp_dir.path().join("target");
+
+        fs::create_dir_all(&source).unwrap();
+        fs::create_dir_all(target.join("dir")).unwrap();
+        fs::write(source.join("a.txt"), "new").unwrap();
+        fs::write(source.join("c.txt"), "c").unwrap();
+        fs::write(target.join("dir/a.txt"), "old").unwrap();
+        fs::write(target.join("dir/b.txt"), "b").unwrap();
+
+        let mut transfer = EntitysTransfer::new(ConflictPolicy::Skip).with_trash(trash.clone());
+        transfer
+            .relocate(&[Entity::new(source.clone()).unwrap()], &target)
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(target.join("dir/a.txt")).unwrap(), "old");
+        assert_eq!(fs::read_to_string(target.join("dir/c.txt")).unwrap(), "c");
+        assert!(source.join("a.txt").exists());
+        assert!(transfer.removed().is_empty());
+
+        let mut transfer = EntitysTransfer::new(ConflictPolicy::Overwrite).with_trash(trash);
+        transfer
+            .relocate(&[Entity::new(source.clone()).unwrap()], &target)
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(target.join("dir/a.txt")).unwrap(), "new");
+        assert_eq!(fs::read_to_string(target.join("dir/b.txt")).unwrap(), "b");
+        assert!(!source.exists());
+        assert_eq!(transfer.removed(), std::slice::from_ref(&source));
+
+        let mut journal = Journal::default();
+        journal.record(
+            transfer
+                .trashed()
+                .iter()
+                .map(|(original, files, info)| {
+                    JournalOperation::Trash(original.clone(), files.clone(), info.clone())
+                })
+                .chain(
+                    transfer
+                        .done()
+                        .iter()
+                        .map(|(from, to)| JournalOperation::Rename(from.clone(), to.clone())),
+                )
+                .chain(
+                    transfer
+                        .removed()
+                        .iter()
+                        .map(|dir| JournalOperation::Remove(dir.clone(), EntityKind::Directory)),
+                )
+                .collect(),
+        );
+        journal.undo().unwrap();
+
+        assert_eq!(fs::read_to_string(target.join("dir/a.txt")).unwrap(), "old");
+        assert_eq!(fs::read_to_string(source.join("a.txt")).unwrap(), "new");
+    }
+}
+
+#[cfg(test)]
+mod trash_tests {
+    use crate::entity::{
+        Entity,
+        trash::{EntitysTrash, TrashEntity},
+    };
+    use std::fs::{self, create_dir};
+    use tempfile::TempDir;
+
+    fn list(trash: &EntitysTrash, temp_dir: &TempDir) -> Vec<TrashEntity> {
+        let mut trash_entitys: Vec<TrashEntity> = trash
+            .list()
+            .into_iter()
+            .filter(|t| t.original().starts_with(temp_dir.path()))
             .collect();
+        trash_entitys.sort_by_key(|t| t.original().clone());
+        trash_entitys
+    }
 
-        let start = Instant::now();
+    #[test]
+    fn test_trash_writes_info_and_lists() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = EntitysTrash::from_path(temp_dir.path().join("Trash"));
+        let source = temp_dir.path().join("my file.txt");
+        let other = temp_dir.path().join("other");
+
+        fs::write(&source, "content").unwrap();
+        create_dir(&other).unwrap();
+        fs::write(other.join("my file.txt"), "other").unwrap();
+
+        trash
+            .trash(&[
+                Entity::new(source.clone()).unwrap(),
+                Entity::new(other.join("my file.txt")).unwrap(),
+            ])
+            .unwrap();
 
-        for file_path in test_files {
-            let _ = Entity::new(file_path).unwrap();
-        }
+        assert!(!source.exists());
 
-        let duration = start.elapsed();
-        println!("Entity creation benchmark: {:?} for 1000 files", duration);
+        let info =
+            fs::read_to_string(temp_dir.path().join("Trash/info/my file.txt.trashinfo")).unwrap();
+        assert!(info.starts_with("[Trash Info]\nPath="));
+        assert!(info.contains("my%20file.txt\nDeletionDate="));
+        assert!(temp_dir.path().join("Trash/files/my file.txt.2").exists());
 
-        assert!(duration.as_millis() < 1000);
+        let trash_entitys = list(&trash, &temp_dir);
+
+        assert_eq!(trash_entitys.len(), 2);
+        assert_eq!(
+            trash_entitys[0].original(),
+            &temp_dir.path().canonicalize().unwrap().join("my file.txt")
+        );
+        assert_eq!(trash_entitys[0].name(), "my file.txt");
+        assert!(!trash_entitys[0].deletion().is_none());
+    }
+
+    #[test]
+    fn test_trash_restore_and_purge() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash = EntitysTrash::from_path(temp_dir.path().join("Trash"));
+        let dir = temp_dir.path().join("dir");
+        let file = temp_dir.path().join("file.txt");
+
+        create_dir(&dir).unwrap();
+        fs::write(dir.join("nested.txt"), "nested").unwrap();
+        fs::write(&file, "file").unwrap();
+
+        trash
+            .trash(&[
+                Entity::new(dir.clone()).unwrap(),
+                Entity::new(file.clone()).unwrap(),
+            ])
+            .unwrap();
+
+        let trash_entitys = list(&trash, &temp_dir);
+        trash.restore(&trash_entitys[..1]).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join("nested.txt")).unwrap(),
+            "nested"
+        );
+
+        fs::write(&file, "new").unwrap();
+        assert!(trash.restore(&trash_entitys[1..]).is_err());
+
+        trash.purge(&trash_entitys[1..]).unwrap();
+
+        assert!(list(&trash, &temp_dir).is_empty());
+        assert_eq!(
+            fs::read_dir(temp_dir.path().join("Trash/files"))
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+}
+
+#[cfg(test)]
+mod journal_tests {
+    use crate::{
+        entity::{
+            Entity,
+            kind::EntityKind,
+            transfer::{ConflictPolicy, EntitysTransfer},
+        },
+        journal::{Journal, JournalOperation},
+    };
+    use std::{
+        fs::{self, Permissions},
+        os::unix::fs::PermissionsExt,
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_journal_undo_redo_rename_and_permissions() {
+        let temp_dir = TempDir::new().unwrap();
+        let from = temp_dir.path().join("from.txt");
+        let to = temp_dir.path().join("to.txt");
+        let mut journal = Journal::default();
+
+        fs::write(&from, "content").unwrap();
+        fs::set_permissions(&from, Permissions::from_mode(0o644)).unwrap();
+
+        fs::rename(&from, &to).unwrap();
+        journal.record(vec![JournalOperation::Rename(from.clone(), to.clone())]);
+        fs::set_permissions(&to, Permissions::from_mode(0o600)).unwrap();
+        journal.record(vec![JournalOperation::Permissions(
+            to.clone(),
+            0o644,
+            0o600,
+        )]);
+
+        journal.undo().unwrap();
+        assert_eq!(
+            fs::metadata(&to).unwrap().permissions().mode() & 0o777,
+            0o644
+        );
+
+        journal.undo().unwrap();
+        assert!(from.exists() && !to.exists());
+        assert!(journal.undo().is_err());
+
+        journal.redo().unwrap();
+        journal.redo().unwrap();
+        assert_eq!(
+            fs::metadata(&to).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+        assert!(journal.redo().is_err());
+    }
+
+    #[test]
+    fn test_journal_undo_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("target");
+        let mut journal = Journal::default();
+
+        fs::create_dir(&target).unwrap();
+        fs::write(temp_dir.path().join("file.txt"), "content").unwrap();
+
+        let mut transfer = EntitysTransfer::new(ConflictPolicy::Ask);
+        transfer
+            .copy(
+                &[Entity::new(temp_dir.path().join("file.txt")).unwrap()],
+                &target,
+            )
+            .unwrap();
+        journal.record(
+            transfer
+                .done()
+                .iter()
+                .map(|(source, copy)| JournalOperation::Copy(source.clone(), copy.clone()))
+                .collect(),
+        );
+
+        journal.undo().unwrap();
+        assert!(!target.join("file.txt").exists());
+
+        journal.redo().unwrap();
+        assert_eq!(
+            fs::read_to_string(target.join("file.txt")).unwrap(),
+            "content"
+        );
+    }
+
+    #[test]
+    fn test_journal_refuses_changed_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let created = temp_dir.path().join("created.txt");
+        let mut journal = Journal::default();
+
+        fs::File::create_new(&created).unwrap();
+        journal.record(vec![JournalOperation::Create(
+            created.clone(),
+            EntityKind::File,
+        )]);
+
+        fs::write(&created, "changed").unwrap();
+
+        assert!(journal.undo().is_err());
+        assert_eq!(fs::read_to_string(&created).unwrap(), "changed");
+    }
+
+    #[test]
+    fn test_journal_refuses_undo_of_changed_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("copy");
+        let target = temp_dir.path().join("target");
+
+        fs::create_dir_all(source.join("sub")).unwrap();
+        fs::create_dir(&target).unwrap();
+        fs::write(source.join("sub/file.txt"), "content").unwrap();
+
+        let copy = |journal: &mut Journal| {
+            let mut transfer = EntitysTransfer::new(ConflictPolicy::Rename);
+            transfer
+                .copy(&[Entity::new(source.clone()).unwrap()], &target)
+                .unwrap();
+            journal.record(
+                transfer
+                    .done()
+                    .iter()
+                    .map(|(source, copy)| JournalOperation::Copy(source.clone(), copy.clone()))
+                    .collect(),
+            );
+        };
+
+        // Work added deep in the copy is not thrown away
+        let mut journal = Journal::default();
+        copy(&mut journal);
+        fs::write(target.join("copy/sub/later.txt"), "later").unwrap();
+        assert!(journal.undo().is_err());
+        assert!(target.join("copy/sub/later.txt").exists());
+
+        // Nor an edit of a copied file
+        let mut journal = Journal::default();
+        copy(&mut journal);
+        fs::write(target.join("copy (1)/sub/file.txt"), "edited").unwrap();
+        assert!(journal.undo().is_err());
+        assert_eq!(
+            fs::read_to_string(target.join("copy (1)/sub/file.txt")).unwrap(),
+            "edited"
+        );
+
+        let mut journal = Journal::default();
+        copy(&mut journal);
+        journal.undo().unwrap();
+        assert!(!target.join("copy (2)").exists());
+    }
+}
+
+#[cfg(test)]
+mod job_tests {
+    use crate::{
+        commands::Command,
+        entity::{
+            Entity,
+            transfer::{ConflictPolicy, EntitysTransfer},
+        },
+        job::{JobControl, JobKind, JobState},
+        model::JujikModel,
+        tab::Tab,
+    };
+    use std::{
+        fs::{self, create_dir},
+        sync::{Arc, mpsc},
+        time::Duration,
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_copy_reports_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        let target = temp_dir.path().join("target");
+
+        create_dir(&source).unwrap();
+        create_dir(&target).unwrap();
+        fs::write(source.join("big.bin"), vec![7; 3 * 1024 * 1024 + 1]).unwrap();
+        fs::write(source.join("small.txt"), "small").unwrap();
+
+        let control = Arc::new(JobControl::new(1, JobKind::Copy));
+        let (files, bytes) = JobControl::measure(std::slice::from_ref(&source));
+        control.set_total(files, bytes);
+
+        EntitysTransfer::new(ConflictPolicy::Ask)
+            .with_control(control.clone())
+            .copy(&[Entity::new(source).unwrap()], &target)
+            .unwrap();
+
+        let job = control.job();
+        assert_eq!(job.files, (2, 2));
+        assert_eq!(job.bytes, (3 * 1024 * 1024 + 6, 3 * 1024 * 1024 + 6));
+        assert_eq!(job.fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_cancelled_copy_leaves_no_partial_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("target");
+
+        create_dir(&target).unwrap();
+        fs::write(temp_dir.path().join("big.bin"), vec![7; 1024]).unwrap();
+
+        let control = Arc::new(JobControl::new(1, JobKind::Copy));
+        control.cancel();
+
+        let res = EntitysTransfer::new(ConflictPolicy::Ask)
+            .with_control(control.clone())
+            .copy(
+                &[Entity::new(temp_dir.path().join("big.bin")).unwrap()],
+                &target,
+            );
+
+        assert!(res.is_err());
+        assert_eq!(control.job().state, JobState::Cancelled);
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_model_copy_job_finishes() {
+        let (controller_tx, controller_rx) = mpsc::channel();
+        let (model_tx, model_rx) = mpsc::channel();
+
+        let model_handle = JujikModel::new(controller_tx, model_rx).run().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("target");
+
+        create_dir(&target).unwrap();
+        fs::write(temp_dir.path().join("file.txt"), "content").unwrap();
+
+        model_tx
+            .send(Command::CopyEntitys(
+                0,
+                Tab::default(),
+                0,
+                vec![Entity::new(temp_dir.path().join("file.txt")).unwrap()],
+                target.clone(),
+                ConflictPolicy::Ask,
+            ))
+            .unwrap();
+
+        let mut job = None;
+
+        while let Ok(command) = controller_rx.recv_timeout(Duration::from_secs(1)) {
+            if let Command::JobProgress(progress) = command
+                && progress.is_finished()
+            {
+                job = Some(progress);
+                break;
+            }
+        }
+
+        let job = job.unwrap();
+        assert_eq!(job.kind, JobKind::Copy);
+        assert_eq!(job.state, JobState::Done);
+        assert_eq!(job.files, (1, 1));
+        assert_eq!(
+            fs::read_to_string(target.join("file.txt")).unwrap(),
+            "content"
+        );
+
+        model_tx.send(Command::Drop).unwrap();
+        let _ = model_handle.join();
+    }
+}
+
+#[cfg(test)]
+mod watch_tests {
+    use crate::{entity::watch::EntitysWatcher, tab::Tab};
+    use std::{collections::HashSet, fs, thread, time::Duration};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_watcher_updates_tab_incrementally() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().to_path_buf();
+
+        fs::write(dir.join("old.txt"), "").unwrap();
+        fs::write(dir.join("kept.txt"), "").unwrap();
+
+        let mut tab = Tab::tab_entitys(dir.clone()).unwrap();
+        let mut watcher = EntitysWatcher::new().unwrap();
+        watcher.watch(&HashSet::from([dir.clone()]));
+
+        fs::remove_file(dir.join("old.txt")).unwrap();
+        fs::write(dir.join("new.txt"), "content").unwrap();
+        thread::sleep(Duration::from_millis(50));
+
+        let changes = watcher.changes();
+        assert!(changes.contains(&dir.join("old.txt")));
+        assert!(changes.contains(&dir.join("new.txt")));
+        assert!(!changes.contains(&dir.join("kept.txt")));
+
+        assert!(tab.update_paths(&changes));
+
+        let mut names: Vec<String> = tab
+            .entitys()
+            .unwrap()
+            .iter()
+            .map(|e| e.name_with_extension())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["kept.txt", "new.txt"]);
+    }
+
+    #[test]
+    fn test_watcher_unwatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut watcher = EntitysWatcher::new().unwrap();
+
+        watcher.watch(&HashSet::from([temp_dir.path().to_path_buf()]));
+        watcher.watch(&HashSet::new());
+
+        fs::write(temp_dir.path().join("file.txt"), "").unwrap();
+        thread::sleep(Duration::from_millis(50));
+
+        assert!(watcher.changes().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod rename_tests {
+    use crate::{
+        entity::{
+            Entity,
+            rename::{EntitysRename, RenameCase, RenameParameters},
+        },
+        journal::{Journal, JournalOperation},
+    };
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn entitys(temp_dir: &TempDir, names: &[&str]) -> Vec<Entity> {
+        names
+            .iter()
+            .map(|name| {
+                fs::write(temp_dir.path().join(name), name).unwrap();
+                Entity::new(temp_dir.path().join(name)).unwrap()
+            })
+            .collect()
+    }
+
+    fn new_names(rename: &EntitysRename, entitys: &[Entity]) -> Vec<String> {
+        rename
+            .preview(entitys)
+            .iter()
+            .map(|p| {
+                p.destination
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rename_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let entitys = entitys(&temp_dir, &["photo_a.jpeg", "photo_b.jpeg"]);
+
+        let rename = EntitysRename::new(RenameParameters {
+            search: r"photo_(\w)".to_string(),
+            replace: "{n:03}-$1".to_string(),
+            regex: true,
+            case: RenameCase::Upper,
+            extension: Some("jpg".to_string()),
+            start: 9,
+            step: 1,
+        })
+        .unwrap();
+        assert_eq!(new_names(&rename, &entitys), vec!["009-A.jpg", "010-B.jpg"]);
+
+        let rename = EntitysRename::new(RenameParameters {
+            search: ".".to_string(),
+            replace: "$".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            new_names(&rename, &entitys),
+            vec!["photo_a.jpeg", "photo_b.jpeg"]
+        );
+
+        let rename = EntitysRename::new(RenameParameters {
+            replace: "{name} {date:%Y} {date:%Q}".to_string(),
+            case: RenameCase::Title,
+            extension: Some(String::new()),
+            ..Default::default()
+        })
+        .unwrap();
+        let year = entitys[0]
+            .modification()
+            .date()
+            .with_timezone(&chrono::Local)
+            .format("%Y")
+            .to_string();
+        assert_eq!(
+            new_names(&rename, &entitys)[0],
+            format!("Photo_A {} {{Date:%Q}}", year)
+        );
+    }
+
+    #[test]
+    fn test_rename_conflicts() {
+        let temp_dir = TempDir::new().unwrap();
+        let entitys = entitys(&temp_dir, &["1.txt", "2.txt"]);
+        fs::write(temp_dir.path().join("taken.txt"), "").unwrap();
+
+        let conflicts = |search: &str, replace: &str| {
+            EntitysRename::new(RenameParameters {
+                search: search.to_string(),
+                replace: replace.to_string(),
+                start: 2,
+                ..Default::default()
+            })
+            .unwrap()
+            .preview(&entitys)
+            .into_iter()
+            .map(|p| p.conflict)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            conflicts("", "same"),
+            vec![Some("Duplicate name".to_string()); 2]
+        );
+        assert_eq!(
+            conflicts("", "{n}/x")[0],
+            Some("Invalid character".to_string())
+        );
+        assert_eq!(
+            conflicts("1", "taken"),
+            vec![Some("Already exists".to_string()), None]
+        );
+        // "2.txt" is renamed too, it can take the place
+        assert_eq!(conflicts("", "{n}"), vec![None, None]);
+    }
+
+    #[test]
+    fn test_rename_swap_rollback_and_undo() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.txt");
+        let b = temp_dir.path().join("b.txt");
+        let c = temp_dir.path().join("c.txt");
+
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+        fs::write(&c, "c").unwrap();
+
+        // The second destination is taken, the first rename is put back
+        assert!(
+            EntitysRename::rename(&[
+                (a.clone(), temp_dir.path().join("d.txt")),
+                (b.clone(), c.clone())
+            ])
+            .is_err()
+        );
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "b");
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 3);
+
+        let pairs = vec![(a.clone(), b.clone()), (b.clone(), a.clone())];
+        EntitysRename::rename(&pairs).unwrap();
+        assert_eq!(fs::read_to_string(&a).unwrap(), "b");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "a");
+
+        let mut journal = Journal::default();
+        journal.record(vec![JournalOperation::Renames(pairs)]);
+        journal.undo().unwrap();
+        assert_eq!(fs::read_to_string(&a).unwrap(), "a");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "b");
+    }
+}
+
+#[cfg(test)]
+mod link_tests {
+    use crate::{
+        entity::{Entity, kind::EntityKind, link::EntityLink},
+        journal::{Journal, JournalOperation},
+        tab::Tab,
+    };
+    use std::{fs, os::unix, path::PathBuf};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_link_keeps_own_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("dir");
+        let link = temp_dir.path().join("link");
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("inner.txt"), "inner").unwrap();
+        unix::fs::symlink("dir", &link).unwrap();
+
+        let entity = Entity::new(link.clone()).unwrap();
+        let entity_link = entity.link().clone().unwrap();
+
+        assert_eq!(entity.path(), link);
+        assert_eq!(*entity.kind(), EntityKind::Symlink);
+        assert_eq!(entity_link.text(), &PathBuf::from("dir"));
+        assert_eq!(entity_link.target(), &dir.canonicalize().unwrap());
+        assert!(entity.points_to_dir());
+        assert!(!entity.is_broken());
+
+        let tab = Tab::tab_entitys(entity.path()).unwrap();
+        let entitys = tab.entitys().unwrap();
+
+        assert_eq!(tab.path(), link);
+        assert_eq!(entitys[0].path(), link.join("inner.txt"));
+    }
+
+    #[test]
+    fn test_broken_link() {
+        let temp_dir = TempDir::new().unwrap();
+        let link = temp_dir.path().join("broken");
+        unix::fs::symlink("missing", &link).unwrap();
+
+        let entity = Entity::new(link.clone()).unwrap();
+
+        assert!(entity.exists());
+        assert!(entity.is_broken());
+        assert!(!entity.points_to_dir());
+        assert_eq!(
+            entity.link().as_ref().unwrap().target(),
+            &temp_dir.path().join("missing")
+        );
+    }
+
+    #[test]
+    fn test_retarget_and_undo() {
+        let temp_dir = TempDir::new().unwrap();
+        let link = temp_dir.path().join("link");
+        let file = temp_dir.path().join("file.txt");
+        fs::write(&file, "file").unwrap();
+        unix::fs::symlink("missing", &link).unwrap();
+
+        EntityLink::retarget(&link, &PathBuf::from("file.txt")).unwrap();
+        let mut journal = Journal::default();
+        journal.record(vec![JournalOperation::Link(
+            link.clone(),
+            PathBuf::from("missing"),
+            PathBuf::from("file.txt"),
+        )]);
+
+        assert_eq!(fs::read_to_string(&link).unwrap(), "file");
+
+        journal.undo().unwrap();
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("missing"));
+
+        assert!(EntityLink::retarget(&file, &PathBuf::from("missing")).is_err());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "file");
+    }
+}
+
+#[cfg(test)]
+mod chmod_tests {
+    use crate::{entity::permission::EntityPermissionsExpression, error::JujikError};
+    use std::{
+        fs::{self, Permissions},
+        os::unix::{self, fs::PermissionsExt},
+    };
+    use tempfile::TempDir;
+
+    fn apply(expression: &str, mode: u32, is_dir: bool) -> u32 {
+        EntityPermissionsExpression::parse(expression)
+            .unwrap()
+            .apply(mode, is_dir)
+    }
+
+    fn mode(pathbuf: &std::path::Path) -> u32 {
+        fs::symlink_metadata(pathbuf).unwrap().permissions().mode() & 0o7777
+    }
+
+    #[test]
+    fn test_parse_and_apply() {
+        assert_eq!(apply("0750", 0o644, false), 0o750);
+        assert_eq!(apply("u+x,go-w", 0o666, false), 0o744);
+        assert_eq!(apply("a=rX", 0o640, false), 0o444);
+        assert_eq!(apply("a=rX", 0o740, false), 0o555);
+        assert_eq!(apply("a=rX", 0o700, true), 0o555);
+        assert_eq!(apply("+x", 0o644, false), 0o755);
+        assert_eq!(apply("u+s,o+t", 0o755, true), 0o5755);
+        assert_eq!(apply("u+w-x", 0o500, false), 0o600);
+
+        for invalid in ["", "8", "07777777", "u", "u+y", "x+r", "u+r,"] {
+            assert!(
+                EntityPermissionsExpression::parse(invalid).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_recursive() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("dir");
+        let file = dir.join("file.txt");
+        let script = dir.join("script.sh");
+        let outside = temp_dir.path().join("outside.txt");
+        fs::create_dir(&dir).unwrap();
+        fs::write(&file, "file").unwrap();
+        fs::write(&script, "script").unwrap();
+        fs::write(&outside, "outside").unwrap();
+        fs::set_permissions(&file, Permissions::from_mode(0o600)).unwrap();
+        fs::set_permissions(&script, Permissions::from_mode(0o700)).unwrap();
+        fs::set_permissions(&outside, Permissions::from_mode(0o600)).unwrap();
+        fs::set_permissions(&dir, Permissions::from_mode(0o700)).unwrap();
+        unix::fs::symlink(&outside, dir.join("link")).unwrap();
+
+        let expression = EntityPermissionsExpression::parse("go=rX").unwrap();
+        let (changes, failures) =
+            expression.apply_paths(&[dir.clone(), temp_dir.path().join("missing")], true);
+
+        assert_eq!(mode(&file), 0o644);
+        assert_eq!(mode(&script), 0o755);
+        assert_eq!(mode(&dir), 0o755);
+        assert_eq!(mode(&outside), 0o600);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(failures.len(), 1);
+        assert!(matches!(failures[0].1, JujikError::IO(_)));
+    }
+}
+
+#[cfg(test)]
+mod chown_tests {
+    use crate::{
+        entity::owner::EntityOwners,
+        journal::{Journal, JournalOperation},
+    };
+    use nix::unistd::getuid;
+    use std::{
+        fs,
+        os::{linux::fs::MetadataExt, unix},
+        path::Path,
+    };
+    use tempfile::TempDir;
+
+    fn ids(pathbuf: &Path) -> (u32, u32) {
+        let metadata = fs::symlink_metadata(pathbuf).unwrap();
+        (metadata.st_uid(), metadata.st_gid())
+    }
+
+    #[test]
+    fn test_databases() {
+        assert!(
+            EntityOwners::users()
+                .iter()
+                .any(|(uid, name)| *uid == 0 && name == "root")
+        );
+        assert!(EntityOwners::groups().iter().any(|(gid, _)| *gid == 0));
+    }
+
+    #[test]
+    fn test_apply_recursive() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("dir");
+        let file = dir.join("file.txt");
+        let outside = temp_dir.path().join("outside.txt");
+        let link = dir.join("link");
+        fs::create_dir(&dir).unwrap();
+        fs::write(&file, "file").unwrap();
+        fs::write(&outside, "outside").unwrap();
+        unix::fs::symlink(&outside, &link).unwrap();
+        let before = ids(&outside);
+
+        // Ids without a name
+        let (changes, failures) =
+            EntityOwners::apply_paths(std::slice::from_ref(&dir), Some(54321), None, true, false);
+
+        if getuid().is_root() {
+            assert!(failures.is_empty());
+            assert_eq!(changes.len(), 3);
+            assert_eq!(ids(&file), (54321, before.1));
+            assert_eq!(ids(&link), (54321, before.1));
+            assert_eq!(ids(&outside), before);
+
+            let mut journal = Journal::default();
+            journal.record(
+                changes
+                    .into_iter()
+                    .map(|(p, b, a)| JournalOperation::Owners(p, b, a))
+                    .collect(),
+            );
+            journal.undo().unwrap();
+            assert_eq!(ids(&file), before);
+            assert_eq!(ids(&link), before);
+
+            let (changes, _) = EntityOwners::apply_paths(
+                std::slice::from_ref(&link),
+                None,
+                Some(54321),
+                false,
+                true,
+            );
+            assert_eq!(changes[0].0, outside.canonicalize().unwrap());
+            assert_eq!(ids(&outside), (before.0, 54321));
+            assert_eq!(ids(&link), before);
+        } else {
+            // The directory fails, so nothing under it is tried
+            assert!(changes.is_empty());
+            assert_eq!(failures.len(), 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod acl_tests {
+    use crate::{
+        entity::{
+            Entity, EntityDetails,
+            acl::{AclEntry, AclTag, EntityAcl},
+        },
+        journal::{Journal, JournalOperation},
+    };
+    use std::{
+        fs::{self, Permissions},
+        os::unix::fs::PermissionsExt,
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_acl_write_read_undo() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("shared.txt");
+        fs::write(&file, "shared").unwrap();
+        fs::set_permissions(&file, Permissions::from_mode(0o640)).unwrap();
+
+        let before = EntityAcl::new(&file).unwrap();
+        assert_eq!(before.access, EntityAcl::from_mode(0o640));
+        assert!(!Entity::new(file.clone()).unwrap().has_acl());
+
+        let mut acl = before.clone();
+        acl.access.push(AclEntry::new(AclTag::User(54321), 0o7));
+        acl.write(&file).unwrap();
+
+        let after = EntityAcl::new(&file).unwrap();
+        assert!(
+            after
+                .access
+                .contains(&AclEntry::new(AclTag::User(54321), 0o7))
+        );
+        // The mask is added and shown as the group bits
+        assert!(after.access.contains(&AclEntry::new(AclTag::Mask, 0o7)));
+        assert_eq!(
+            fs::metadata(&file).unwrap().permissions().mode() & 0o777,
+            0o670
+        );
+
+        // The mark comes with the attributes
+        let entity = Entity::new(file.clone()).unwrap();
+        assert!(!entity.has_acl());
+
+        let entity = entity.with_details(EntityDetails {
+            xattrs: true,
+            ..Default::default()
+        });
+        assert!(entity.has_acl());
+        assert!(entity.permissions_str().starts_with("rw-rwx---+"));
+
+        let mut journal = Journal::default();
+        journal.record(vec![JournalOperation::Acl(
+            file.clone(),
+            before.clone(),
+            after,
+        )]);
+        journal.undo().unwrap();
+
+        assert_eq!(EntityAcl::new(&file).unwrap(), before);
+        assert!(!EntityAcl::exists(&file));
+    }
+
+    #[test]
+    fn test_default_acl() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("project");
+        fs::create_dir(&dir).unwrap();
+
+        let mut acl = EntityAcl::new(&dir).unwrap();
+        acl.default = acl.access.clone();
+        acl.default.push(AclEntry::new(AclTag::Group(54321), 0o5));
+        acl.write(&dir).unwrap();
+
+        fs::write(dir.join("inherited.txt"), "inherited").unwrap();
+        let inherited = EntityAcl::new(&dir.join("inherited.txt")).unwrap();
+        assert!(
+            inherited
+                .access
+                .iter()
+                .any(|e| e.tag == AclTag::Group(54321))
+        );
+        assert_eq!(
+            AclEntry::new(AclTag::Group(54321), 0o5).to_string(),
+            "group:54321:r-x"
+        );
+
+        acl.default.clear();
+        acl.write(&dir).unwrap();
+        assert!(EntityAcl::new(&dir).unwrap().default.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod xattr_tests {
+    use crate::{
+        entity::{Entity, EntityDetails, xattr::EntityXattrs},
+        journal::{Journal, JournalOperation},
+    };
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_xattr_set_list_undo() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("tagged.txt");
+        fs::write(&file, "tagged").unwrap();
+
+        EntityXattrs::set(&file, "user.comment", b"draft").unwrap();
+        EntityXattrs::set(&file, "user.blob", &[0x00, 0xff]).unwrap();
+
+        // Listing a directory reads no attributes
+        let mut entity = Entity::new(file.clone()).unwrap();
+        assert!(entity.xattrs().is_empty());
+
+        let details = EntityDetails {
+            xattrs: true,
+            ..Default::default()
+        };
+        assert_eq!(entity.missing_details(details), details);
+
+        entity.read_details(details);
+        assert_eq!(entity.missing_details(details), EntityDetails::default());
+        assert_eq!(entity.xattr("user.comment"), Some(&b"draft".to_vec()));
+        assert_eq!(
+            entity
+                .xattrs()
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["user.blob", "user.comment"]
+        );
+
+        let mut after = entity.xattrs().clone();
+        after.retain(|(name, _)| name != "user.blob");
+        after[0].1 = b"final".to_vec();
+        after.push(("user.rating".to_string(), b"5".to_vec()));
+
+        let changes = EntityXattrs::diff(entity.xattrs(), &after);
+        assert_eq!(changes.len(), 3);
+
+        let before = changes
+            .iter()
+            .map(|(name, _)| (name.clone(), EntityXattrs::get(&file, name).unwrap()))
+            .collect();
+        EntityXattrs::apply(&file, &changes).unwrap();
+        assert_eq!(EntityXattrs::list(&file).unwrap(), after);
+
+        let mut journal = Journal::default();
+        journal.record(vec![JournalOperation::Xattrs(
+            file.clone(),
+            before,
+            changes,
+        )]);
+        journal.undo().unwrap();
+
+        assert_eq!(&EntityXattrs::list(&file).unwrap(), entity.xattrs());
+    }
+
+    #[test]
+    fn test_xattr_display() {
+        assert_eq!(EntityXattrs::display(b"text\0"), "text");
+        assert_eq!(EntityXattrs::display(&[0x00, 0xff]), "0x00ff");
+        assert_eq!(EntityXattrs::hex(b"ab"), "6162");
+        assert_eq!(EntityXattrs::parse_hex("61 62").unwrap(), b"ab");
+        assert!(EntityXattrs::parse_hex("616").is_err());
+        assert!(EntityXattrs::parse_hex("zz").is_err());
+        assert_eq!(EntityXattrs::value_str(&[0x00, 0xff], false), "");
+        assert_eq!(EntityXattrs::parse_value("ff", true).unwrap(), vec![0xff]);
+    }
+}
+
+#[cfg(test)]
+mod mime_tests {
+    use crate::entity::{Entity, EntityDetails, mime::EntityMime};
+    use std::{fs, path::Path};
+    use tempfile::TempDir;
+
+    fn mime(path: &Path) -> String {
+        Entity::new(path.to_path_buf())
+            .unwrap()
+            .with_details(EntityDetails {
+                mime: true,
+                ..Default::default()
+            })
+            .mime()
+            .mime()
+            .to_string()
+    }
+
+    #[test]
+    fn test_mime_sniffing() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        fs::write(path.join("notes"), "plain words\n").unwrap();
+        assert_eq!(mime(&path.join("notes")), "text/plain");
+
+        // Content wins over the name
+        fs::write(path.join("image.txt"), b"\x89PNG\r\n\x1a\n\x00\x00").unwrap();
+        assert_eq!(mime(&path.join("image.txt")), "image/png");
+
+        fs::write(path.join("blob.txt"), [0x00, 0x01, 0x02, 0xff]).unwrap();
+        assert_eq!(mime(&path.join("blob.txt")), "application/octet-stream");
+        assert!(
+            !Entity::new(path.join("blob.txt"))
+                .unwrap()
+                .with_details(EntityDetails {
+                    mime: true,
+                    ..Default::default()
+                })
+                .mime()
+                .is_text()
+        );
+
+        fs::write(path.join("page"), "<!DOCTYPE html><html></html>").unwrap();
+        assert_eq!(mime(&path.join("page")), "text/html");
+
+        fs::write(path.join("run"), "#!/bin/sh\necho run\n").unwrap();
+        assert_eq!(mime(&path.join("run")), "application/x-shellscript");
+
+        fs::create_dir(path.join("dir")).unwrap();
+        assert_eq!(mime(&path.join("dir")), "inode/directory");
+
+        std::os::unix::fs::symlink(path.join("image.txt"), path.join("link")).unwrap();
+        assert_eq!(mime(&path.join("link")), "image/png");
+
+        assert!(EntityMime::from("application/json").is_text());
+        assert!(EntityMime::from("image/svg+xml").is_text());
+        assert!(!EntityMime::from("application/pdf").is_text());
+    }
+
+    #[test]
+    fn test_mime_globs() {
+        if !Path::new("/usr/share/mime/globs2").exists() {
+            return;
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        fs::write(path.join("data.JSON"), "{}").unwrap();
+        assert_eq!(mime(&path.join("data.JSON")), "application/json");
+
+        // Zip content with an office name
+        fs::write(path.join("report.docx"), b"PK\x03\x04\x14\x00\x00\x00").unwrap();
+        assert_eq!(
+            mime(&path.join("report.docx")),
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+        );
+
+        fs::write(path.join("archive.txt"), b"PK\x03\x04\x14\x00\x00\x00").unwrap();
+        assert_eq!(mime(&path.join("archive.txt")), "application/zip");
+    }
+
+    #[test]
+    fn test_mime_sniffed_only_when_asked() {
+        if !Path::new("/usr/share/mime/globs2").exists() {
+            return;
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        fs::write(path.join("image.txt"), b"\x89PNG\r\n\x1a\n\x00\x00").unwrap();
+
+        let mut entity = Entity::new(path.join("image.txt")).unwrap();
+        assert!(!entity.mime().is_sniffed());
+        assert_eq!(entity.mime().mime(), "text/plain");
+
+        let details = EntityDetails {
+            mime: true,
+            ..Default::default()
+        };
+        assert_eq!(entity.missing_details(details), details);
+
+        entity.read_details(details);
+        assert!(entity.mime().is_sniffed());
+        assert_eq!(entity.mime().mime(), "image/png");
+        assert_eq!(entity.missing_details(details), EntityDetails::default());
+    }
+}
+
+#[cfg(test)]
+mod filter_tests {
+    use crate::{
+        entity::{
+            filter::EntitysFilter,
+            find::{EntitysFinder, FindParameters},
+        },
+        tab::Tab,
+    };
+    use std::fs::{self, create_dir, create_dir_all};
+    use tempfile::TempDir;
+
+    fn source_tree() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        create_dir(path.join(".git")).unwrap();
+        fs::write(path.join(".gitignore"), "target/\n*.log\n!keep.log\n").unwrap();
+        fs::write(path.join(".hidden"), "").unwrap();
+        fs::write(path.join("main.rs"), "").unwrap();
+        fs::write(path.join("build.log"), "").unwrap();
+        fs::write(path.join("keep.log"), "").unwrap();
+        create_dir_all(path.join("target/debug")).unwrap();
+        fs::write(path.join("target/debug/main.rs"), "").unwrap();
+        create_dir_all(path.join("web/node_modules/pkg")).unwrap();
+        fs::write(path.join("web/.ignore"), "node_modules/\n").unwrap();
+        fs::write(path.join("web/node_modules/pkg/main.rs"), "").unwrap();
+        fs::write(path.join("web/main.rs"), "").unwrap();
+
+        temp_dir
+    }
+
+    fn names(tab: &Tab) -> Vec<String> {
+        let mut names: Vec<String> = tab
+            .entitys()
+            .unwrap()
+            .iter()
+            .map(|e| e.name_with_extension())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_tab_filter() {
+        let temp_dir = source_tree();
+        let mut tab = Tab::tab_entitys(temp_dir.path().to_path_buf()).unwrap();
+        assert!(names(&tab).contains(&".hidden".to_string()));
+
+        tab.set_filter(EntitysFilter {
+            show_hidden: false,
+            ignore: true,
+        })
+        .unwrap();
+        assert_eq!(names(&tab), vec!["keep.log", "main.rs", "web"]);
+
+        // The filter stays with the tab
+        tab.change_dir(temp_dir.path().join("web")).unwrap();
+        assert_eq!(names(&tab), vec!["main.rs"]);
+        tab.change_dir_back().unwrap();
+        assert!(!tab.filter().show_hidden);
+    }
+
+    #[test]
+    fn test_find_skips_ignored() {
+        let temp_dir = source_tree();
+        let find = |filter| {
+            let mut paths: Vec<String> = EntitysFinder::find(FindParameters {
+                path: temp_dir.path().to_path_buf(),
+                regex: "^main".to_string(),
+                filter,
+                ..Default::default()
+            })
+            .unwrap()
+            .entitys()
+            .iter()
+            .map(|e| {
+                e.path()
+                    .strip_prefix(temp_dir.path())
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(find(EntitysFilter::default()).len(), 4);
+        assert_eq!(
+            find(EntitysFilter {
+                show_hidden: true,
+                ignore: true,
+            }),
+            vec!["main.rs", "web/main.rs"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod dir_size_tests {
+    use crate::{
+        commands::Command,
+        entity::{dir_size::EntitysDirSizes, size::EntitySize},
+        model::JujikModel,
+    };
+    use std::{
+        fs::{self, create_dir_all},
+        sync::mpsc,
+        time::Duration,
+    };
+    use tempfile::TempDir;
+
+    fn size_byte(size: Option<(EntitySize, EntitySize)>) -> Option<u64> {
+        size.map(|(s, _)| s.size_byte())
+    }
+
+    #[test]
+    fn test_dir_sizes_cache_and_invalidate() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("a");
+        create_dir_all(dir.join("b")).unwrap();
+        fs::write(dir.join("f"), [0; 10]).unwrap();
+        fs::write(dir.join("b/g"), [0; 5]).unwrap();
+
+        let mut dir_sizes = EntitysDirSizes::default();
+
+        assert_eq!(dir_sizes.cached(&dir), None);
+        assert_eq!(size_byte(dir_sizes.size(&dir)), Some(15));
+        assert_eq!(size_byte(dir_sizes.cached(&dir.join("b"))), Some(5));
+        assert_eq!(dir_sizes.size(&dir.join("f")), None);
+
+        // A deep change leaves the mtime of the top directory as it was
+        fs::write(dir.join("b/h"), [0; 7]).unwrap();
+        assert_eq!(dir_sizes.cached(&dir), None);
+        assert_eq!(size_byte(dir_sizes.size(&dir)), Some(22));
+
+        dir_sizes.invalidate(&dir.join("b/h"));
+        assert_eq!(dir_sizes.cached(&dir), None);
+        assert_eq!(size_byte(dir_sizes.size(&dir)), Some(22));
+    }
+
+    #[test]
+    fn test_dir_sizes_see_changes_two_levels_down() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("a");
+        create_dir_all(dir.join("b/c")).unwrap();
+        fs::write(dir.join("f"), [0; 10]).unwrap();
+        fs::write(dir.join("b/c/g"), [0; 5]).unwrap();
+
+        let mut dir_sizes = EntitysDirSizes::default();
+        assert_eq!(size_byte(dir_sizes.size(&dir)), Some(15));
+        assert_eq!(size_byte(dir_sizes.cached(&dir)), Some(15));
+
+        // Only the mtime of `c` moves
+        fs::write(dir.join("b/c/h"), [0; 7]).unwrap();
+        assert_eq!(dir_sizes.cached(&dir), None);
+        assert_eq!(dir_sizes.cached(&dir.join("b/c")), None);
+        assert_eq!(size_byte(dir_sizes.size(&dir)), Some(22));
+        assert_eq!(size_byte(dir_sizes.cached(&dir.join("b"))), Some(12));
+
+        fs::remove_file(dir.join("b/c/g")).unwrap();
+        assert_eq!(size_byte(dir_sizes.size(&dir)), Some(17));
+    }
+
+    #[test]
+    fn test_dir_sizes_count_links_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("a");
+        create_dir_all(dir.join("x")).unwrap();
+        create_dir_all(dir.join("y")).unwrap();
+        fs::write(dir.join("x/f"), [0; 100]).unwrap();
+        fs::hard_link(dir.join("x/f"), dir.join("x/g")).unwrap();
+        fs::hard_link(dir.join("x/f"), dir.join("y/h")).unwrap();
+
+        let mut dir_sizes = EntitysDirSizes::default();
+
+        assert_eq!(size_byte(dir_sizes.size(&dir.join("y"))), Some(100));
+        assert_eq!(size_byte(dir_sizes.size(&dir)), Some(100));
+        // The file counts in whichever subdirectory the walk met first
+        assert_eq!(
+            size_byte(dir_sizes.cached(&dir.join("x"))).unwrap()
+                + size_byte(dir_sizes.cached(&dir.join("y"))).unwrap(),
+            100
+        );
+    }
+
+    #[test]
+    fn test_model_streams_dir_sizes() {
+        let (controller_tx, controller_rx) = mpsc::channel();
+        let (model_tx, model_rx) = mpsc::channel();
+
+        let model_handle = JujikModel::new(controller_tx, model_rx).run().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("a");
+        create_dir_all(&dir).unwrap();
+        fs::write(dir.join("f"), [0; 10]).unwrap();
+
+        for _ in 0..2 {
+            model_tx
+                .send(Command::ComputeDirSizes(vec![dir.clone()]))
+                .unwrap();
+
+            match controller_rx.recv_timeout(Duration::from_secs(1)).unwrap() {
+                Command::DirSizes(sizes) => {
+                    assert_eq!(sizes.len(), 1);
+                    assert_eq!(sizes[0].0, dir);
+                    assert_eq!(sizes[0].1.0.size_byte(), 10);
+                }
+                _ => panic!("Expected DirSizes command"),
+            }
+        }
+
+        model_tx.send(Command::Drop).unwrap();
+        let _ = model_handle.join();
+    }
+}
+
+#[cfg(test)]
+mod usage_tests {
+    use crate::{
+        commands::Command,
+        entity::{filter::EntitysFilter, find::EntitysWalk, usage::EntitysUsage},
+        model::JujikModel,
+    };
+    use std::{
+        fs::{self, create_dir_all, hard_link},
+        os::linux::fs::MetadataExt,
+        sync::{atomic::AtomicBool, mpsc},
+        time::Duration,
+    };
+    use tempfile::TempDir;
+
+    fn blocks(path: &std::path::Path) -> u64 {
+        fs::symlink_metadata(path).unwrap().st_blocks() * 512
+    }
+
+    #[test]
+    fn test_usage_scan() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        create_dir_all(path.join("big/deep")).unwrap();
+        fs::write(path.join("big/deep/a"), vec![1; 64 * 1024]).unwrap();
+        fs::write(path.join("small"), vec![1; 4096]).unwrap();
+        // A second link to the same file is not counted again
+        hard_link(path.join("big/deep/a"), path.join("big/b")).unwrap();
+
+        let mut batches = Vec::new();
+        let progress = EntitysUsage::scan(
+            path,
+            &EntitysFilter::default(),
+            true,
+            &AtomicBool::new(false),
+            |entitys, progress| {
+                batches.push((entitys, progress));
+                true
+            },
+        )
+        .unwrap();
+
+        let (entitys, last) = batches.pop().unwrap();
+        assert!(!last.running);
+        assert_eq!(last, progress);
+        assert_eq!(progress.files, 3);
+
+        let names: Vec<String> = entitys.iter().map(|e| e.name()).collect();
+        assert_eq!(names, vec!["big", "small"]);
+
+        let big = blocks(&path.join("big"))
+            + blocks(&path.join("big/deep"))
+            + blocks(&path.join("big/deep/a"));
+        assert_eq!(entitys[0].size().size_byte(), big);
+        assert_eq!(progress.total, big + blocks(&path.join("small")));
+    }
+
+    #[test]
+    fn test_treemap() {
+        let sizes = [60, 30, 6, 3, 1, 0];
+        let rects = EntitysUsage::treemap(&sizes, [10.0, 20.0, 100.0, 50.0]);
+
+        assert_eq!(rects.len(), sizes.len());
+        assert_eq!(rects[5][2] * rects[5][3], 0.0);
+
+        for (size, [x, y, width, height]) in sizes.iter().zip(&rects) {
+            let area = *size as f32 / 100.0 * 5000.0;
+            assert!((width * height - area).abs() < 0.01);
+            assert!(*x >= 10.0 - 0.01 && x + width <= 110.0 + 0.01);
+            assert!(*y >= 20.0 - 0.01 && y + height <= 70.0 + 0.01);
+        }
+
+        for (i, a) in rects.iter().enumerate() {
+            for b in rects.iter().skip(i + 1) {
+                let overlap_x = (a[0] + a[2]).min(b[0] + b[2]) - a[0].max(b[0]);
+                let overlap_y = (a[1] + a[3]).min(b[1] + b[3]) - a[1].max(b[1]);
+                assert!(overlap_x <= 0.01 || overlap_y <= 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn test_model_usage_tab_drills_down() {
+        let (controller_tx, controller_rx) = mpsc::channel();
+        let (model_tx, model_rx) = mpsc::channel();
+
+        let model_handle = JujikModel::new(controller_tx, model_rx).run().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        create_dir_all(temp_dir.path().join("sub")).unwrap();
+        fs::write(temp_dir.path().join("sub/file"), "content").unwrap();
+
+        model_tx
+            .send(Command::CreateUsage(temp_dir.path().to_path_buf()))
+            .unwrap();
+
+        let tab = match controller_rx.recv_timeout(Duration::from_secs(1)).unwrap() {
+            Command::NewTab(None, tab) => tab,
+            _ => panic!("Expected NewTab command"),
+        };
+        let id = tab.usage().unwrap().id();
+
+        match controller_rx.recv_timeout(Duration::from_secs(1)).unwrap() {
+            Command::UsageEntitys(batch_id, entitys, progress) => {
+                assert_eq!(batch_id, id);
+                assert!(!progress.running);
+                assert_eq!(entitys[0].name(), "sub");
+            }
+            _ => panic!("Expected UsageEntitys command"),
+        }
+
+        model_tx
+            .send(Command::ChangeTabDirectory(
+                0,
+                tab,
+                Some(temp_dir.path().join("sub")),
+            ))
+            .unwrap();
+
+        match controller_rx.recv_timeout(Duration::from_secs(1)).unwrap() {
+            Command::NewTab(Some(0), tab) => {
+                assert_eq!(tab.path(), temp_dir.path().join("sub"));
+                assert_ne!(tab.usage().unwrap().id(), id);
+            }
+            _ => panic!("Expected NewTab command"),
+        }
+
+        model_tx.send(Command::Drop).unwrap();
+        let _ = model_handle.join();
+    }
+
+    #[test]
+    fn test_walk_stays_on_one_filesystem() {
+        let root = std::path::Path::new("/");
+        let proc = std::path::Path::new("/proc");
+
+        // Needs /proc mounted apart from the root
+        if fs::symlink_metadata(proc).map(|m| m.st_dev()).ok()
+            == fs::symlink_metadata(root).map(|m| m.st_dev()).ok()
+        {
+            return;
+        }
+
+        // The children of the root come first in the breadth first walk
+        let children = |same_filesystem| {
+            EntitysWalk::new(root, &EntitysFilter::default())
+                .with_same_filesystem(same_filesystem)
+                .map(|(pathbuf, _)| pathbuf)
+                .take_while(|pathbuf| pathbuf.parent() == Some(root))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(children(false).iter().any(|p| p == proc));
+        assert!(!children(true).iter().any(|p| p == proc));
+    }
+}
+
+#[cfg(test)]
+mod format_tests {
+    use crate::{
+        config::Config,
+        entity::{
+            date::EntityDate,
+            format::{EntitysFormat, SizeUnits},
+            size::EntitySize,
+        },
+    };
+    use chrono::{TimeDelta, TimeZone, Utc};
+
+    #[test]
+    fn test_format_size_units() {
+        let mut format = EntitysFormat::default();
+        let size = EntitySize::new(1_500_000);
+
+        assert_eq!(format.size(&size), "1.43 MiB");
+        assert_eq!(format.size(&EntitySize::new(1000)), "1000 B");
+        assert_eq!(format.size(&EntitySize::default()), "None");
+
+        format.units = SizeUnits::Si;
+        assert_eq!(format.size(&size), "1.50 MB");
+        assert_eq!(format.size(&EntitySize::new(1000)), "1.00 kB");
+
+        format.exact_bytes = true;
+        assert_eq!(format.size(&size), "1500000 B");
+    }
+
+    #[test]
+    fn test_format_parse_size() {
+        let mut format = EntitysFormat::default();
+        let parse = |format: &EntitysFormat, value| format.parse_size(value).unwrap().size_byte();
+
+        assert_eq!(parse(&format, "1000"), 1000);
+        assert_eq!(parse(&format, "12 B"), 12);
+        assert_eq!(parse(&format, "2KiB"), 2048);
+        assert_eq!(parse(&format, "1.5 mib"), 1_572_864);
+        assert_eq!(parse(&format, "2 kB"), 2000);
+        assert_eq!(parse(&format, "3 GB"), 3_000_000_000);
+        assert_eq!(parse(&format, "1 TiB"), 1 << 40);
+        assert_eq!(parse(&format, "4 K"), 4096);
+        assert!(format.parse_size("").unwrap().is_none());
+        assert!(format.parse_size("5 parsecs").is_err());
+        assert!(format.parse_size("MiB").is_err());
+
+        format.units = SizeUnits::Si;
+        assert_eq!(parse(&format, "4 K"), 4000);
+        assert_eq!(parse(&format, "2KiB"), 2048);
+    }
+
+    #[test]
+    fn test_format_size_exact_reads_back() {
+        let mut format = EntitysFormat::default();
+
+        assert_eq!(format.size_exact(&EntitySize::default()), "");
+        assert_eq!(format.size_exact(&EntitySize::new(0)), "0 B");
+        assert_eq!(format.size_exact(&EntitySize::new(1_572_864)), "1536 KiB");
+        assert_eq!(format.size_exact(&EntitySize::new(1 << 30)), "1 GiB");
+        assert_eq!(format.size_exact(&EntitySize::new(1500)), "1500 B");
+
+        format.units = SizeUnits::Si;
+        assert_eq!(format.size_exact(&EntitySize::new(1500)), "1500 B");
+        assert_eq!(format.size_exact(&EntitySize::new(2_000_000)), "2 MB");
+
+        for size in [0, 1, 1000, 1024, 1_500_000, 1 << 40] {
+            let size = EntitySize::new(size);
+            assert_eq!(format.parse_size(&format.size_exact(&size)).unwrap(), size);
+        }
+    }
+
+    #[test]
+    fn test_format_date() {
+        let mut format = EntitysFormat::default();
+        let date = EntityDate::from_datetime(Utc.with_ymd_and_hms(2024, 3, 5, 14, 7, 0).unwrap());
+
+        assert_eq!(format.date(&date), "05/03/2024");
+        assert_eq!(format.date(&EntityDate::default()), "Unknown");
+
+        format.date_pattern = "%Y-%m-%d %H:%M".to_string();
+        assert_eq!(format.date(&date), "2024-03-05 14:07");
+
+        // Unknown specifier
+        format.date_pattern = "%Q".to_string();
+        assert_eq!(format.date(&date), "05/03/2024");
+
+        format.relative_dates = true;
+        let hours_ago = EntityDate::from_datetime(Utc::now() - TimeDelta::hours(3));
+        let day_ago = EntityDate::from_datetime(Utc::now() - TimeDelta::days(1));
+        assert_eq!(format.date(&hours_ago), "3 hours ago");
+        assert_eq!(format.date(&day_ago), "1 day ago");
+        assert_eq!(format.date(&EntityDate::now()), "just now");
+    }
+
+    #[test]
+    fn test_config_without_format() {
+        let mut json = serde_json::to_value(Config::default()).unwrap();
+        json.as_object_mut().unwrap().remove("entitys_format");
+
+        let config: Config = serde_json::from_value(json).unwrap();
+
+        assert_eq!(config.entitys_format, EntitysFormat::default());
+    }
+}
+
+#[cfg(test)]
+mod sort_tests {
+    use crate::{
+        config::Config,
+        tab::{SortBy, SortDirection, SortField, Tab},
+    };
+    use std::{cmp::Ordering, fs};
+    use tempfile::TempDir;
+
+    fn names(tab: &mut Tab, sortby: &SortBy) -> Vec<String> {
+        tab.set_sortby(sortby);
+        tab.sort();
+
+        tab.entitys()
+            .unwrap()
+            .iter()
+            .map(|entity| entity.name_with_extension())
+            .collect()
+    }
+
+    #[test]
+    fn test_compare_names() {
+        let mut sortby = SortBy::default();
+
+        assert_eq!(sortby.compare_names("file10", "file2"), Ordering::Less);
+        assert_eq!(sortby.compare_names("Zeta", "alpha"), Ordering::Less);
+
+        sortby.natural = true;
+        sortby.ignore_case = true;
+        assert_eq!(sortby.compare_names("file10", "file2"), Ordering::Greater);
+        assert_eq!(sortby.compare_names("file002", "file10"), Ordering::Less);
+        assert_eq!(sortby.compare_names("Zeta", "alpha"), Ordering::Greater);
+        assert_eq!(
+            sortby.compare_names("a99999999999999999999999", "a1"),
+            Ordering::Greater
+        );
+        assert_ne!(sortby.compare_names("File", "file"), Ordering::Equal);
+        assert_ne!(sortby.compare_names("file01", "file1"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_sort_dirs_first_and_then_by() {
+        let temp_dir = TempDir::new().unwrap();
+
+        fs::write(temp_dir.path().join("file2.txt"), "aa").unwrap();
+        fs::write(temp_dir.path().join("File10.txt"), "aa").unwrap();
+        fs::write(temp_dir.path().join("big.txt"), "a".repeat(100)).unwrap();
+        fs::create_dir(temp_dir.path().join("zdir")).unwrap();
+
+        let mut tab = Tab::tab_entitys(temp_dir.path().to_path_buf()).unwrap();
+        let mut sortby = SortBy::default();
+
+        assert_eq!(
+            names(&mut tab, &sortby),
+            ["File10.txt", "big.txt", "file2.txt", "zdir"]
+        );
+
+        sortby.natural = true;
+        sortby.ignore_case = true;
+        sortby.dirs_first = true;
+        sortby.direction = SortDirection::Descending;
+        assert_eq!(
+            names(&mut tab, &sortby),
+            ["zdir", "File10.txt", "file2.txt", "big.txt"]
+        );
+
+        sortby.sort_by_field(SortField::Size);
+        sortby.then_by_field(SortField::Name);
+        sortby.then_by_field(SortField::Name);
+        assert_eq!(sortby.direction, SortDirection::Ascending);
+        assert_eq!(
+            sortby.then,
+            Some((SortField::Name, SortDirection::Descending))
+        );
+        assert_eq!(
+            names(&mut tab, &sortby),
+            ["zdir", "File10.txt", "file2.txt", "big.txt"]
+        );
+
+        sortby.then_by_field(SortField::Name);
+        assert_eq!(
+            names(&mut tab, &sortby),
+            ["zdir", "file2.txt", "File10.txt", "big.txt"]
+        );
+
+        // The primary key can not also be the secondary one
+        sortby.sort_by_field(SortField::Name);
+        assert_eq!(sortby.then, None);
+    }
+
+    #[test]
+    fn test_sortby_kept_in_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut tab = Tab::tab_entitys(temp_dir.path().to_path_buf()).unwrap();
+        let sortby = SortBy {
+            field: SortField::Size,
+            then: Some((SortField::Name, SortDirection::Descending)),
+            natural: true,
+            dirs_first: true,
+            ..Default::default()
+        };
+        tab.set_sortby(&sortby);
+
+        let mut config = Config::default();
+        config.tabs.push(tab);
+
+        let config: Config =
+            serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+
+        assert_eq!(config.tabs[0].sortby(), sortby);
+    }
+
+    #[test]
+    fn test_sortby_kept_on_change_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("sub")).unwrap();
+
+        let mut tab = Tab::tab_entitys(temp_dir.path().to_path_buf()).unwrap();
+        let sortby = SortBy {
+            field: SortField::Modification,
+            direction: SortDirection::Descending,
+            then: Some((SortField::Name, SortDirection::Ascending)),
+            ignore_case: true,
+            ..Default::default()
+        };
+        tab.set_sortby(&sortby);
+
+        tab.change_dir(temp_dir.path().join("sub")).unwrap();
+        assert_eq!(tab.path(), temp_dir.path().join("sub"));
+        assert_eq!(tab.sortby(), sortby);
+
+        tab.change_dir_back().unwrap();
+        assert_eq!(tab.path(), temp_dir.path());
+        assert_eq!(tab.sortby(), sortby);
+    }
+}
+
+#[cfg(test)]
+mod benchmarks {
+    use crate::{
+        commands::Command,
+        controller::JujikController,
+        entity::{Entity, kind::EntityKind},
+        model::JujikModel,
+        tab::Tab,
+    };
+    use std::{
+        sync::mpsc,
+        time::{Duration, Instant},
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn benchmark_entity_creation() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_files: Vec<_> = (0..1000)
+            .map(|i| {
+                let path = temp_dir.path().join(format!("file_{}.txt", i));
+                std::fs::write(&path, "content").unwrap();
+                path
+            })
+            .collect();
+
+        let start = Instant::now();
+
+        for file_path in test_files {
+            let _ = Entity::new(file_path).unwrap();
+        }
+
+        let duration = start.elapsed();
+        println!("Entity creation benchmark: {:?} for 1000 files", duration);
+
+        assert!(duration.as_millis() < 1000);
+    }
+
+    #[test]
+    fn benchmark_read_dir() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for i in 0..5000 {
+            let path = temp_dir.path().join(format!("file_{}.txt", i));
+            std::fs::write(&path, "content").unwrap();
+        }
+
+        let start = Instant::now();
+        let tab = Tab::tab_entitys(temp_dir.path().to_path_buf()).unwrap();
+        let duration = start.elapsed();
+        println!("Directory read benchmark: {:?} for 5000 files", duration);
+
+        match tab.content() {
+            crate::tab::TabContent::Entitys(_, _, entitys) => assert_eq!(entitys.len(), 5000),
+            _ => panic!("Expected an entitys tab"),
+        }
+        assert!(duration.as_millis() < 2000);
     }
 
     #[test]
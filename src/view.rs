@@ -1,26 +1,36 @@
 use crate::config::Config;
-use crate::entity::Entity;
+use crate::entity::acl::{AclEntry, AclTag, EntityAcl};
 use crate::entity::date::EntityDate;
+use crate::entity::filter::EntitysFilter;
 use crate::entity::find::{EntitysFinder, FindParameters};
+use crate::entity::format::{DateZone, EntitysFormat, SizeUnits};
 use crate::entity::kind::EntityKind;
 use crate::entity::owner::EntityOwners;
 use crate::entity::permission::{
-    EntityPermissions, EntityPermissionsCategory, EntityPermissionsKind,
+    EntityPermissions, EntityPermissionsCategory, EntityPermissionsExpression,
+    EntityPermissionsKind, EntityPermissionsSpecial,
 };
+use crate::entity::rename::{EntitysRename, RenameCase, RenameParameters, RenamePreview};
 use crate::entity::size::EntitySize;
+use crate::entity::transfer::{ConflictPolicy, TransferKind};
+use crate::entity::trash::TrashEntity;
+use crate::entity::usage::EntitysUsage;
+use crate::entity::xattr::{EntityXattr, EntityXattrs};
+use crate::entity::{Entity, EntityDetails};
+use crate::job::{Job, JobState};
 use crate::tab::{SortBy, SortDirection, SortField, TabContent};
 use crate::{commands::Command, error::JujikError, pin::Pin, tab::Tab};
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveTime};
 use eframe::{App, EventLoopBuilderHook, NativeOptions, run_native};
 use egui::{
-    Align, Button, CentralPanel, Color32, ComboBox, Context, DragValue, Event, Id, Key, Label,
-    Layout, Modal, Modifiers, Response, RichText, ScrollArea, Sense, SidePanel, Sides, Stroke,
-    TextEdit, TextStyle, TopBottomPanel, Ui, Visuals, menu,
+    Align, Align2, Button, CentralPanel, Checkbox, Color32, ComboBox, Context, DragValue, Event,
+    FontId, Id, Key, Label, Layout, Modal, Modifiers, Pos2, ProgressBar, Rect, Response, RichText,
+    ScrollArea, Sense, SidePanel, Sides, Stroke, TextEdit, TextStyle, TopBottomPanel, Ui, Vec2,
+    Visuals, menu,
 };
 use egui_extras::{Column, DatePickerButton, TableBuilder};
 use serde::{Deserialize, Serialize};
 use std::f32;
-use std::time::{Duration, Instant};
 use std::{
     collections::HashSet,
     path::PathBuf,
@@ -30,6 +40,9 @@ use std::{
 };
 use winit::platform::wayland::EventLoopBuilderExtWayland;
 
+// Bytes shown for content that is not text
+const BINARY_VIEW_LEN: u64 = 4096;
+
 #[derive(Default)]
 struct Message {
     show: bool,
@@ -76,22 +89,101 @@ struct EntityInfo {
     name: String,
     extension: String,
     kind: EntityKind,
+    link: String,
     permissions: EntityPermissions,
     owners: EntityOwners,
     size: EntitySize,
     modification: EntityDate,
     creation: EntityDate,
+    access: EntityDate,
+    change: EntityDate,
+    acl: EntityAcl,
+    // name, value, value as hex
+    xattrs: Vec<(String, String, bool)>,
+    new_xattr: (String, String, bool),
     change_permissions: ChangeEntityPermissions,
     change_owners: ChangeEntityOwners,
+    change_acl: ChangeEntityAcl,
 }
 
 #[derive(Default)]
 struct EntitysDelete {
     show: (bool, bool),
+    permanent: bool,
+    idx_tab: usize,
+    tab: Tab,
+    idx_entity: usize,
+    entitys: Vec<Entity>,
+}
+
+#[derive(Default)]
+struct TrashInfo {
+    show: bool,
+    idx_tab: usize,
+    tab: Tab,
+    selection: HashSet<TrashEntity>,
+    purge: Vec<TrashEntity>,
+}
+
+#[derive(Default)]
+struct EntitysRenameInfo {
+    show: bool,
+    idx_tab: usize,
+    tab: Tab,
+    entitys: Vec<Entity>,
+    parameters: RenameParameters,
+    extension: (bool, String),
+}
+
+#[derive(Default)]
+struct EntitysChmodInfo {
+    show: bool,
+    idx_tab: usize,
+    tab: Tab,
+    entitys: Vec<Entity>,
+    expression: String,
+    recursive: bool,
+}
+
+#[derive(Default)]
+struct EntitysChownInfo {
+    show: bool,
+    idx_tab: usize,
+    tab: Tab,
+    entitys: Vec<Entity>,
+    users: Vec<(u32, String)>,
+    groups: Vec<(u32, String)>,
+    // change, id
+    uid: (bool, u32),
+    gid: (bool, u32),
+    recursive: bool,
+    follow: bool,
+}
+
+#[derive(Default)]
+struct JobsInfo {
+    show: bool,
+    jobs: Vec<Job>,
+}
+
+#[derive(Default)]
+struct EntitysConflict {
+    show: bool,
+    kind: TransferKind,
     idx_tab: usize,
     tab: Tab,
     idx_entity: usize,
     entitys: Vec<Entity>,
+    pathbuf: PathBuf,
+    conflicts: Vec<PathBuf>,
+}
+
+#[derive(Default)]
+struct ChangeEntityAcl {
+    show: bool,
+    acl: EntityAcl,
+    uid: u32,
+    gid: u32,
 }
 
 #[derive(Default)]
@@ -100,6 +192,8 @@ struct ChangeEntityPermissions {
     user: (bool, bool, bool),
     group: (bool, bool, bool),
     other: (bool, bool, bool),
+    // setuid, setgid, sticky
+    special: (bool, bool, bool),
 }
 
 #[derive(Default)]
@@ -135,6 +229,7 @@ struct EntityEdit {
 #[derive(Default)]
 struct FinderInfo {
     show: bool,
+    update: bool,
     idx_tab: usize,
     tab: Tab,
     parameters: FindParameters,
@@ -149,10 +244,10 @@ struct FinderInfo {
     change_owners: ChangeEntityOwners,
     size: (EntitySize, EntitySize),
     date_modification: (EntityDate, EntityDate),
-    date_creation: (EntityDate, EntityDate),
     change_size: (String, String),
     change_date_modification: (NaiveDate, NaiveDate),
-    change_date_creation: (NaiveDate, NaiveDate),
+    filter: EntitysFilter,
+    same_filesystem: bool,
 }
 
 #[derive(Default)]
@@ -161,6 +256,11 @@ struct EntitysSortByInfo {
     sortby: SortBy,
     field: SortField,
     direction: SortDirection,
+    then: Option<SortField>,
+    then_direction: SortDirection,
+    natural: bool,
+    ignore_case: bool,
+    dirs_first: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -174,6 +274,25 @@ pub struct EntitysShowColumn {
     size: bool,
     date_modification: bool,
     date_creation: bool,
+    #[serde(default)]
+    date_access: bool,
+    #[serde(default)]
+    date_change: bool,
+    #[serde(default)]
+    mime: bool,
+    #[serde(default)]
+    dir_size: bool,
+    #[serde(default)]
+    allocated: bool,
+    #[serde(default)]
+    links: bool,
+    #[serde(default)]
+    inode: bool,
+    #[serde(default)]
+    device: bool,
+    // names of the extended attributes shown as columns
+    #[serde(default)]
+    xattrs: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -195,12 +314,13 @@ pub struct JujikView {
     controller: Sender<Command>,
     view: Receiver<Command>,
     first_update: bool,
-    update: Instant,
     style: JujikStyle,
     pins: Vec<Pin>,
     tabs: Vec<Tab>,
     message: Message,
     entitys_show: EntitysShowColumn,
+    entitys_show_xattr: String,
+    entitys_format: EntitysFormat,
     entitys_sortby_info: EntitysSortByInfo,
     current_tab_idx: usize,
     entitys_selection: EntitysSelection,
@@ -209,6 +329,12 @@ pub struct JujikView {
     entity_create: EntityCreate,
     entity_info: EntityInfo,
     entitys_delete: EntitysDelete,
+    entitys_conflict: EntitysConflict,
+    entitys_rename: EntitysRenameInfo,
+    entitys_chmod: EntitysChmodInfo,
+    entitys_chown: EntitysChownInfo,
+    trash_info: TrashInfo,
+    jobs_info: JobsInfo,
     entity_edit: EntityEdit,
     finder_info: FinderInfo,
 }
@@ -230,6 +356,14 @@ impl App for JujikView {
             self.main_bar(ctx, ui);
         });
 
+        if self.jobs_info.show {
+            TopBottomPanel::bottom("jobs")
+                .resizable(true)
+                .show(ctx, |ui| {
+                    self.jobs(ui);
+                });
+        }
+
         SidePanel::left("Bind")
             .width_range(100.0..=300.0)
             .show(ctx, |ui| {
@@ -247,10 +381,29 @@ impl App for JujikView {
 
         let _ = self.handle_commad(ctx).inspect_err(JujikError::handle_err);
 
+        self.journal_keys(ctx);
+        self.filter_keys(ctx);
+
         if self.message.show {
             self.message(ctx);
         }
 
+        if self.entitys_conflict.show {
+            self.entitys_conflict(ctx);
+        }
+
+        if self.entitys_rename.show {
+            self.entitys_rename(ctx);
+        }
+
+        if self.entitys_chmod.show {
+            self.entitys_chmod(ctx);
+        }
+
+        if self.entitys_chown.show {
+            self.entitys_chown(ctx);
+        }
+
         //TODO meybe do not need
         ctx.request_repaint();
     }
@@ -264,6 +417,7 @@ impl App for JujikView {
                 self.tabs.clone(),
                 self.current_tab_idx,
                 self.entitys_show.clone(),
+                self.entitys_format.clone(),
             )))
             .inspect_err(JujikError::handle_err);
 
@@ -280,12 +434,13 @@ impl JujikView {
             controller,
             view,
             first_update: true,
-            update: Instant::now(),
             style: JujikStyle::default(),
             pins: Vec::new(),
             tabs: Vec::new(),
             message: Message::default(),
             entitys_show: EntitysShowColumn::default(),
+            entitys_show_xattr: String::new(),
+            entitys_format: EntitysFormat::default(),
             entitys_sortby_info: EntitysSortByInfo::default(),
             current_tab_idx: 0,
             entitys_selection: EntitysSelection::default(),
@@ -294,6 +449,12 @@ impl JujikView {
             entity_create: EntityCreate::default(),
             entity_info: EntityInfo::default(),
             entitys_delete: EntitysDelete::default(),
+            entitys_conflict: EntitysConflict::default(),
+            entitys_rename: EntitysRenameInfo::default(),
+            entitys_chmod: EntitysChmodInfo::default(),
+            entitys_chown: EntitysChownInfo::default(),
+            trash_info: TrashInfo::default(),
+            jobs_info: JobsInfo::default(),
             entity_edit: EntityEdit::default(),
             finder_info: FinderInfo::default(),
         }
@@ -333,6 +494,7 @@ impl JujikView {
                             self.tabs.clone(),
                             self.current_tab_idx,
                             self.entitys_show.clone(),
+                            self.entitys_format.clone(),
                         )))
                         .inspect_err(JujikError::handle_err);
                 }
@@ -342,6 +504,7 @@ impl JujikView {
                     self.tabs.clone_from(&config.tabs);
                     self.current_tab_idx = config.current_tab_idx;
                     self.entitys_show.clone_from(&config.entitys_show);
+                    self.entitys_format.clone_from(&config.entitys_format);
                 }
 
                 // Other
@@ -353,6 +516,37 @@ impl JujikView {
                     self.message.show = true;
                     self.message.value = format!("{:?}", err);
                 }
+                Command::EntitysConflict(
+                    kind,
+                    idx_tab,
+                    tab,
+                    idx_entity,
+                    entitys,
+                    pathbuf,
+                    conflicts,
+                ) => {
+                    self.entitys_conflict = EntitysConflict {
+                        show: true,
+                        kind,
+                        idx_tab,
+                        tab,
+                        idx_entity,
+                        entitys,
+                        pathbuf,
+                        conflicts,
+                    };
+                }
+                Command::JobProgress(job) => {
+                    match self.jobs_info.jobs.iter_mut().find(|j| j.id == job.id) {
+                        // A late progress must not bring back a finished job
+                        Some(old) if old.is_finished() => {}
+                        Some(old) => *old = job,
+                        None => {
+                            self.jobs_info.jobs.push(job);
+                            self.jobs_info.show = true;
+                        }
+                    }
+                }
                 Command::Drop => {
                     ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                 }
@@ -360,14 +554,74 @@ impl JujikView {
             }
         };
 
-        if self.update.elapsed() >= Duration::from_secs(5) {
-            self.controller
-                .send(Command::UpdateTab(self.current_tab_idx))?;
+        Ok(())
+    }
+
+    // Text fields keep Ctrl+Z for their own undo
+    fn journal_keys(&self, ctx: &Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let (redo, undo) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z),
+                i.consume_key(Modifiers::COMMAND, Key::Z),
+            )
+        });
+
+        if redo {
+            let _ = self
+                .controller
+                .send(Command::Redo)
+                .inspect_err(JujikError::handle_err);
+        } else if undo {
+            let _ = self
+                .controller
+                .send(Command::Undo)
+                .inspect_err(JujikError::handle_err);
+        }
+    }
 
-            self.update = Instant::now();
+    // Ctrl+H shows or hides dotfiles in the current tab
+    fn filter_keys(&self, ctx: &Context) {
+        if ctx.wants_keyboard_input()
+            || !ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::H))
+        {
+            return;
         }
 
-        Ok(())
+        if let Some(tab) = self.tabs.get(self.current_tab_idx) {
+            match tab.content() {
+                TabContent::Entitys(_, _, _) => {
+                    let mut filter = tab.filter().clone();
+                    filter.show_hidden = !filter.show_hidden;
+
+                    let _ = self
+                        .controller
+                        .send(Command::ChangeTabFilter(
+                            self.current_tab_idx,
+                            tab.clone(),
+                            filter,
+                        ))
+                        .inspect_err(JujikError::handle_err);
+                }
+                TabContent::Find(finder) => {
+                    let mut parameters = finder.parameters();
+                    parameters.filter.show_hidden = !parameters.filter.show_hidden;
+
+                    let _ = self
+                        .controller
+                        .send(Command::UpdateFind(
+                            self.current_tab_idx,
+                            tab.clone(),
+                            parameters,
+                        ))
+                        .inspect_err(JujikError::handle_err);
+                }
+                _ => {}
+            }
+        }
     }
 
     fn style(&self, ctx: &egui::Context) {
@@ -436,6 +690,45 @@ impl JujikView {
                 },
             );
 
+            ui.menu_button(
+                RichText::new("Edit")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+                |ui| {
+                    if ui
+                        .button(
+                            RichText::new("Undo")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        )
+                        .clicked()
+                    {
+                        let _ = self
+                            .controller
+                            .send(Command::Undo)
+                            .inspect_err(JujikError::handle_err);
+
+                        ui.close_menu();
+                    }
+
+                    if ui
+                        .button(
+                            RichText::new("Redo")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        )
+                        .clicked()
+                    {
+                        let _ = self
+                            .controller
+                            .send(Command::Redo)
+                            .inspect_err(JujikError::handle_err);
+
+                        ui.close_menu();
+                    }
+                },
+            );
+
             ui.menu_button(
                 RichText::new("Show")
                     .color(self.style.text_color.into_color32())
@@ -470,12 +763,28 @@ impl JujikView {
                                     .color(self.style.text_color.into_color32())
                                     .size(self.style.text_size),
                             );
-                            ui.checkbox(
-                                &mut self.entitys_show.permissions,
-                                RichText::new("Permissions")
-                                    .color(self.style.text_color.into_color32())
-                                    .size(self.style.text_size),
-                            );
+                            // The controller reads attributes for the ACL mark only while this is on
+                            if ui
+                                .checkbox(
+                                    &mut self.entitys_show.permissions,
+                                    RichText::new("Permissions")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                )
+                                .changed()
+                            {
+                                let _ = self
+                                    .controller
+                                    .send(Command::SetConfig(Config::new(
+                                        self.style.clone(),
+                                        self.pins.clone(),
+                                        self.tabs.clone(),
+                                        self.current_tab_idx,
+                                        self.entitys_show.clone(),
+                                        self.entitys_format.clone(),
+                                    )))
+                                    .inspect_err(JujikError::handle_err);
+                            }
                             ui.checkbox(
                                 &mut self.entitys_show.owners,
                                 RichText::new("Owners")
@@ -488,6 +797,52 @@ impl JujikView {
                                     .color(self.style.text_color.into_color32())
                                     .size(self.style.text_size),
                             );
+                            // The controller measures directories only while this is on
+                            if ui
+                                .checkbox(
+                                    &mut self.entitys_show.dir_size,
+                                    RichText::new("Directory sizes")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                )
+                                .changed()
+                            {
+                                let _ = self
+                                    .controller
+                                    .send(Command::SetConfig(Config::new(
+                                        self.style.clone(),
+                                        self.pins.clone(),
+                                        self.tabs.clone(),
+                                        self.current_tab_idx,
+                                        self.entitys_show.clone(),
+                                        self.entitys_format.clone(),
+                                    )))
+                                    .inspect_err(JujikError::handle_err);
+                            }
+                            ui.checkbox(
+                                &mut self.entitys_show.allocated,
+                                RichText::new("Allocated size")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                            ui.checkbox(
+                                &mut self.entitys_show.links,
+                                RichText::new("Hard links")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                            ui.checkbox(
+                                &mut self.entitys_show.inode,
+                                RichText::new("Inode")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                            ui.checkbox(
+                                &mut self.entitys_show.device,
+                                RichText::new("Device")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
                             ui.checkbox(
                                 &mut self.entitys_show.date_modification,
                                 RichText::new("Date modification")
@@ -500,37 +855,260 @@ impl JujikView {
                                     .color(self.style.text_color.into_color32())
                                     .size(self.style.text_size),
                             );
-                        },
-                    );
+                            ui.checkbox(
+                                &mut self.entitys_show.date_access,
+                                RichText::new("Date access")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                            ui.checkbox(
+                                &mut self.entitys_show.date_change,
+                                RichText::new("Date change")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                            // The controller sniffs the content only while this is on
+                            if ui
+                                .checkbox(
+                                    &mut self.entitys_show.mime,
+                                    RichText::new("Type")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                )
+                                .changed()
+                            {
+                                let _ = self
+                                    .controller
+                                    .send(Command::SetConfig(Config::new(
+                                        self.style.clone(),
+                                        self.pins.clone(),
+                                        self.tabs.clone(),
+                                        self.current_tab_idx,
+                                        self.entitys_show.clone(),
+                                        self.entitys_format.clone(),
+                                    )))
+                                    .inspect_err(JujikError::handle_err);
+                            }
 
-                    let style = ui.button(
-                        RichText::new("Style")
-                            .color(self.style.text_color.into_color32())
-                            .size(self.style.text_size),
-                    );
+                            ui.separator();
 
-                    if style.clicked() {
-                        self.style.show = true;
-                    }
-                },
-            );
+                            let mut remove = None;
+                            for (idx, name) in self.entitys_show.xattrs.iter().enumerate() {
+                                ui.horizontal(|ui| {
+                                    ui.label(
+                                        RichText::new(format!("Xattr {}", name))
+                                            .color(self.style.text_color.into_color32())
+                                            .size(self.style.text_size),
+                                    );
+                                    if ui
+                                        .button(
+                                            RichText::new("Remove")
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                        )
+                                        .clicked()
+                                    {
+                                        remove = Some(idx);
+                                    }
+                                });
+                            }
+                            let mut changed = remove.is_some();
+                            if let Some(idx) = remove {
+                                self.entitys_show.xattrs.remove(idx);
+                            }
 
-            // if ui
-            //     .button(
-            //         RichText::new("Find")
-            //             .color(self.style.text_color.into_color32())
-            //             .size(self.style.text_size),
-            //     )
-            //     .clicked()
-            // {
-            //     self.finder_info.show = true;
-            // }
-        });
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    TextEdit::singleline(&mut self.entitys_show_xattr)
+                                        .hint_text("user.name")
+                                        .desired_width(120.0),
+                                );
+                                let name = self.entitys_show_xattr.trim().to_string();
+                                if ui
+                                    .add_enabled(
+                                        !name.is_empty()
+                                            && !self.entitys_show.xattrs.contains(&name),
+                                        Button::new(
+                                            RichText::new("Add xattr")
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                        ),
+                                    )
+                                    .clicked()
+                                {
+                                    self.entitys_show.xattrs.push(name);
+                                    self.entitys_show_xattr.clear();
+                                    changed = true;
+                                }
+                            });
 
-        if self.finder_info.show {
-            self.entitys_selection.entitys.clear();
+                            // The controller reads attributes only while a column shows them
+                            if changed {
+                                let _ = self
+                                    .controller
+                                    .send(Command::SetConfig(Config::new(
+                                        self.style.clone(),
+                                        self.pins.clone(),
+                                        self.tabs.clone(),
+                                        self.current_tab_idx,
+                                        self.entitys_show.clone(),
+                                        self.entitys_format.clone(),
+                                    )))
+                                    .inspect_err(JujikError::handle_err);
+                            }
+                        },
+                    );
 
-            self.finder_info(ctx, true);
+                    ui.menu_button(
+                        RichText::new("Format")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                        |ui| {
+                            ui.radio_value(
+                                &mut self.entitys_format.units,
+                                SizeUnits::Iec,
+                                RichText::new("Binary units (KiB)")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                            ui.radio_value(
+                                &mut self.entitys_format.units,
+                                SizeUnits::Si,
+                                RichText::new("Decimal units (kB)")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                            ui.checkbox(
+                                &mut self.entitys_format.exact_bytes,
+                                RichText::new("Exact bytes")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+
+                            ui.separator();
+
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new("Date pattern:")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                                ui.add(
+                                    TextEdit::singleline(&mut self.entitys_format.date_pattern)
+                                        .hint_text("%d/%m/%Y %H:%M")
+                                        .desired_width(120.0),
+                                );
+                            });
+                            ui.radio_value(
+                                &mut self.entitys_format.date_zone,
+                                DateZone::Local,
+                                RichText::new("Local time")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                            ui.radio_value(
+                                &mut self.entitys_format.date_zone,
+                                DateZone::Utc,
+                                RichText::new("UTC")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                            ui.checkbox(
+                                &mut self.entitys_format.relative_dates,
+                                RichText::new("Relative dates")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                        },
+                    );
+
+                    let style = ui.button(
+                        RichText::new("Style")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    );
+
+                    if style.clicked() {
+                        self.style.show = true;
+                    }
+                },
+            );
+
+            if ui
+                .button(
+                    RichText::new("Find")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                )
+                .clicked()
+            {
+                let mut parameters = FindParameters::default();
+
+                if let Some(tab) = self.tabs.get(self.current_tab_idx) {
+                    parameters.path = tab.path();
+                }
+
+                self.finder_info
+                    .set_parameters(parameters, &self.entitys_format);
+                self.finder_info.update = false;
+                self.finder_info.show = true;
+            }
+
+            if ui
+                .button(
+                    RichText::new("Usage")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                )
+                .clicked()
+                && let Some(tab) = self.tabs.get(self.current_tab_idx)
+            {
+                let _ = self
+                    .controller
+                    .send(Command::CreateUsage(tab.path()))
+                    .inspect_err(JujikError::handle_err);
+            }
+
+            if ui
+                .button(
+                    RichText::new("Trash")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                )
+                .clicked()
+            {
+                let _ = self
+                    .controller
+                    .send(Command::CreateTrash)
+                    .inspect_err(JujikError::handle_err);
+            }
+
+            if ui
+                .button(
+                    RichText::new("Jobs")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                )
+                .clicked()
+            {
+                self.jobs_info.show = !self.jobs_info.show;
+            }
+        });
+
+        if self.finder_info.show {
+            if !self.finder_info.update {
+                self.entitys_selection.entitys.clear();
+            }
+
+            self.finder_info(ctx, !self.finder_info.update);
+        }
+
+        if self.finder_info.change_permissions.show {
+            self.finder_change_permissions(ctx);
+        }
+
+        if self.finder_info.change_owners.show {
+            self.finder_change_owners(ctx);
         }
 
         if self.style.show {
@@ -752,6 +1330,7 @@ impl JujikView {
                                     self.tabs.clone(),
                                     self.current_tab_idx,
                                     self.entitys_show.clone(),
+                                    self.entitys_format.clone(),
                                 )))
                                 .inspect_err(JujikError::handle_err);
 
@@ -852,7 +1431,22 @@ impl JujikView {
 
                     ui.separator();
 
-                    self.view_text(ui, entity);
+                    if entity.mime().is_text() {
+                        self.view_text(ui, entity);
+                    } else {
+                        self.view_binary(ui, entity);
+                    }
+                }
+                TabContent::Editor(entity) if !entity.mime().is_text() => {
+                    self.view_text_bar(ui, entity);
+
+                    ui.separator();
+
+                    ui.label(
+                        RichText::new(format!("Can not edit {} content as text", entity.mime()))
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    );
                 }
                 TabContent::Editor(entity) => {
                     if !self.entity_edit.changed {
@@ -877,6 +1471,20 @@ impl JujikView {
 
                     self.finder(ctx, ui, self.current_tab_idx, tab, finder);
                 }
+                TabContent::Trash(trash_entitys) => {
+                    self.trash_bar(ui, tab, trash_entitys);
+
+                    ui.separator();
+
+                    self.trash(ctx, ui, self.current_tab_idx, tab, trash_entitys);
+                }
+                TabContent::Usage(usage) => {
+                    self.usage_bar(ui, tab, usage);
+
+                    ui.separator();
+
+                    self.usage(ctx, ui, self.current_tab_idx, tab, usage);
+                }
                 _ => {}
             }
         }
@@ -910,6 +1518,14 @@ impl JujikView {
                     .column(Column::remainder())
                     .column(Column::remainder())
                     .column(Column::remainder())
+                    .column(Column::remainder())
+                    .column(Column::remainder())
+                    .column(Column::remainder())
+                    .column(Column::remainder())
+                    .column(Column::remainder())
+                    .column(Column::remainder())
+                    .column(Column::remainder())
+                    .columns(Column::remainder(), self.entitys_show.xattrs.len())
                     .header(30.0, |mut header| {
                         header.col(|ui| {});
                         if self.entitys_show.filekind {
@@ -926,10 +1542,12 @@ impl JujikView {
                         if self.entitys_show.name {
                             header.col(|ui| {
                                 ui.centered_and_justified(|ui| {
-                                    ui.label(
-                                        RichText::new("Name")
-                                            .color(self.style.text_color.into_color32())
-                                            .size(self.style.text_size),
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Name",
+                                        SortField::Name,
                                     );
                                 });
                             });
@@ -937,10 +1555,25 @@ impl JujikView {
                         if self.entitys_show.extension {
                             header.col(|ui| {
                                 ui.centered_and_justified(|ui| {
-                                    ui.label(
-                                        RichText::new("Extension")
-                                            .color(self.style.text_color.into_color32())
-                                            .size(self.style.text_size),
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Extension",
+                                        SortField::Extension,
+                                    );
+                                });
+                            });
+                        }
+                        if self.entitys_show.mime {
+                            header.col(|ui| {
+                                ui.centered_and_justified(|ui| {
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Type",
+                                        SortField::Type,
                                     );
                                 });
                             });
@@ -948,10 +1581,12 @@ impl JujikView {
                         if self.entitys_show.permissions {
                             header.col(|ui| {
                                 ui.centered_and_justified(|ui| {
-                                    ui.label(
-                                        RichText::new("Permissions")
-                                            .color(self.style.text_color.into_color32())
-                                            .size(self.style.text_size),
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Permissions",
+                                        SortField::Permissions,
                                     );
                                 });
                             });
@@ -959,10 +1594,12 @@ impl JujikView {
                         if self.entitys_show.owners {
                             header.col(|ui| {
                                 ui.centered_and_justified(|ui| {
-                                    ui.label(
-                                        RichText::new("Owners")
-                                            .color(self.style.text_color.into_color32())
-                                            .size(self.style.text_size),
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Owners",
+                                        SortField::Owners,
                                     );
                                 });
                             });
@@ -970,10 +1607,64 @@ impl JujikView {
                         if self.entitys_show.size {
                             header.col(|ui| {
                                 ui.centered_and_justified(|ui| {
-                                    ui.label(
-                                        RichText::new("Size")
-                                            .color(self.style.text_color.into_color32())
-                                            .size(self.style.text_size),
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Size",
+                                        SortField::Size,
+                                    );
+                                });
+                            });
+                        }
+                        if self.entitys_show.allocated {
+                            header.col(|ui| {
+                                ui.centered_and_justified(|ui| {
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Allocated",
+                                        SortField::Allocated,
+                                    );
+                                });
+                            });
+                        }
+                        if self.entitys_show.links {
+                            header.col(|ui| {
+                                ui.centered_and_justified(|ui| {
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Links",
+                                        SortField::Links,
+                                    );
+                                });
+                            });
+                        }
+                        if self.entitys_show.inode {
+                            header.col(|ui| {
+                                ui.centered_and_justified(|ui| {
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Inode",
+                                        SortField::Inode,
+                                    );
+                                });
+                            });
+                        }
+                        if self.entitys_show.device {
+                            header.col(|ui| {
+                                ui.centered_and_justified(|ui| {
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Device",
+                                        SortField::Device,
                                     );
                                 });
                             });
@@ -981,10 +1672,12 @@ impl JujikView {
                         if self.entitys_show.date_modification {
                             header.col(|ui| {
                                 ui.centered_and_justified(|ui| {
-                                    ui.label(
-                                        RichText::new("Modification Date")
-                                            .color(self.style.text_color.into_color32())
-                                            .size(self.style.text_size),
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Modification Date",
+                                        SortField::Modification,
                                     );
                                 });
                             });
@@ -992,10 +1685,51 @@ impl JujikView {
                         if self.entitys_show.date_creation {
                             header.col(|ui| {
                                 ui.centered_and_justified(|ui| {
-                                    ui.label(
-                                        RichText::new("Creation Data")
-                                            .color(self.style.text_color.into_color32())
-                                            .size(self.style.text_size),
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Creation Data",
+                                        SortField::Creation,
+                                    );
+                                });
+                            });
+                        }
+                        if self.entitys_show.date_access {
+                            header.col(|ui| {
+                                ui.centered_and_justified(|ui| {
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Access Date",
+                                        SortField::Access,
+                                    );
+                                });
+                            });
+                        }
+                        if self.entitys_show.date_change {
+                            header.col(|ui| {
+                                ui.centered_and_justified(|ui| {
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        "Change Date",
+                                        SortField::Change,
+                                    );
+                                });
+                            });
+                        }
+                        for name in &self.entitys_show.xattrs {
+                            header.col(|ui| {
+                                ui.centered_and_justified(|ui| {
+                                    self.entitys_sort_header(
+                                        ui,
+                                        idx_tab,
+                                        tab,
+                                        name,
+                                        SortField::Xattr(name.clone()),
                                     );
                                 });
                             });
@@ -1022,17 +1756,31 @@ impl JujikView {
                                 if self.entitys_show.name {
                                     row.col(|ui| {
                                         ui.centered_and_justified(|ui| {
-                                            let name = if self.entitys_show.name_with_extension {
+                                            let mut name = if self.entitys_show.name_with_extension
+                                            {
                                                 entity.name_with_extension()
                                             } else {
                                                 entity.name()
                                             };
 
-                                            ui.label(
-                                                RichText::new(format!("{}", name))
-                                                    .color(self.style.text_color.into_color32())
-                                                    .size(self.style.text_size),
-                                            );
+                                            if let Some(link) = entity.link() {
+                                                name = format!("{} -> {}", name, link.text_str());
+                                            }
+
+                                            if entity.is_broken() {
+                                                ui.label(
+                                                    RichText::new(name)
+                                                        .color(Color32::RED)
+                                                        .strikethrough()
+                                                        .size(self.style.text_size),
+                                                );
+                                            } else {
+                                                ui.label(
+                                                    RichText::new(name)
+                                                        .color(self.style.text_color.into_color32())
+                                                        .size(self.style.text_size),
+                                                );
+                                            }
                                         });
                                     });
                                 }
@@ -1050,11 +1798,22 @@ impl JujikView {
                                         });
                                     });
                                 }
+                                if self.entitys_show.mime {
+                                    row.col(|ui| {
+                                        ui.centered_and_justified(|ui| {
+                                            ui.label(
+                                                RichText::new(entity.mime().mime())
+                                                    .color(self.style.text_color.into_color32())
+                                                    .size(self.style.text_size),
+                                            );
+                                        });
+                                    });
+                                }
                                 if self.entitys_show.permissions {
                                     row.col(|ui| {
                                         ui.centered_and_justified(|ui| {
                                             ui.label(
-                                                RichText::new(format!("{}", entity.permissions()))
+                                                RichText::new(entity.permissions_str())
                                                     .color(self.style.text_color.into_color32())
                                                     .size(self.style.text_size),
                                             );
@@ -1073,10 +1832,66 @@ impl JujikView {
                                     });
                                 }
                                 if self.entitys_show.size {
+                                    // Directories still being measured
+                                    let size = if self.entitys_show.dir_size
+                                        && entity.is_dir()
+                                        && entity.size().is_none()
+                                    {
+                                        "...".to_string()
+                                    } else {
+                                        self.entitys_format.size(entity.size())
+                                    };
+
+                                    row.col(|ui| {
+                                        ui.centered_and_justified(|ui| {
+                                            ui.label(
+                                                RichText::new(size)
+                                                    .color(self.style.text_color.into_color32())
+                                                    .size(self.style.text_size),
+                                            );
+                                        });
+                                    });
+                                }
+                                if self.entitys_show.allocated {
+                                    row.col(|ui| {
+                                        ui.centered_and_justified(|ui| {
+                                            ui.label(
+                                                RichText::new(
+                                                    self.entitys_format.size(entity.allocated()),
+                                                )
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                            );
+                                        });
+                                    });
+                                }
+                                if self.entitys_show.links {
+                                    row.col(|ui| {
+                                        ui.centered_and_justified(|ui| {
+                                            ui.label(
+                                                RichText::new(entity.links().to_string())
+                                                    .color(self.style.text_color.into_color32())
+                                                    .size(self.style.text_size),
+                                            );
+                                        });
+                                    });
+                                }
+                                if self.entitys_show.inode {
+                                    row.col(|ui| {
+                                        ui.centered_and_justified(|ui| {
+                                            ui.label(
+                                                RichText::new(entity.inode().to_string())
+                                                    .color(self.style.text_color.into_color32())
+                                                    .size(self.style.text_size),
+                                            );
+                                        });
+                                    });
+                                }
+                                if self.entitys_show.device {
                                     row.col(|ui| {
                                         ui.centered_and_justified(|ui| {
                                             ui.label(
-                                                RichText::new(format!("{}", entity.size()))
+                                                RichText::new(entity.device_str())
                                                     .color(self.style.text_color.into_color32())
                                                     .size(self.style.text_size),
                                             );
@@ -1089,7 +1904,7 @@ impl JujikView {
                                             ui.label(
                                                 RichText::new(format!(
                                                     "{}",
-                                                    entity.modification().date_str()
+                                                    self.entitys_format.date(entity.modification())
                                                 ))
                                                 .color(self.style.text_color.into_color32())
                                                 .size(self.style.text_size),
@@ -1103,7 +1918,7 @@ impl JujikView {
                                             ui.label(
                                                 RichText::new(format!(
                                                     "{}",
-                                                    entity.creation().date_str()
+                                                    self.entitys_format.date(entity.creation())
                                                 ))
                                                 .color(self.style.text_color.into_color32())
                                                 .size(self.style.text_size),
@@ -1111,16 +1926,58 @@ impl JujikView {
                                         });
                                     });
                                 }
-                                row.col(|ui| {});
-
-                                self.entity_context_menu(
-                                    &row.response(),
-                                    idx_tab,
-                                    &tab,
-                                    idx_entity,
-                                    entity,
-                                );
-
+                                if self.entitys_show.date_access {
+                                    row.col(|ui| {
+                                        ui.centered_and_justified(|ui| {
+                                            ui.label(
+                                                RichText::new(
+                                                    self.entitys_format.date(entity.access()),
+                                                )
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                            );
+                                        });
+                                    });
+                                }
+                                if self.entitys_show.date_change {
+                                    row.col(|ui| {
+                                        ui.centered_and_justified(|ui| {
+                                            ui.label(
+                                                RichText::new(
+                                                    self.entitys_format.date(entity.change()),
+                                                )
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                            );
+                                        });
+                                    });
+                                }
+                                for name in &self.entitys_show.xattrs {
+                                    row.col(|ui| {
+                                        ui.centered_and_justified(|ui| {
+                                            ui.label(
+                                                RichText::new(
+                                                    entity
+                                                        .xattr(name)
+                                                        .map(|value| EntityXattrs::display(value))
+                                                        .unwrap_or_default(),
+                                                )
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                            );
+                                        });
+                                    });
+                                }
+                                row.col(|ui| {});
+
+                                self.entity_context_menu(
+                                    &row.response(),
+                                    idx_tab,
+                                    &tab,
+                                    idx_entity,
+                                    entity,
+                                );
+
                                 responses.push(Some(row.response()));
                             });
                         }
@@ -1141,6 +1998,10 @@ impl JujikView {
                             self.entity_change_owners(ctx);
                         }
 
+                        if self.entity_info.change_acl.show {
+                            self.entity_change_acl(ctx);
+                        }
+
                         if self.entitys_sortby_info.show {
                             self.entitys_sortby_info(ctx, idx_tab, tab);
                         }
@@ -1155,7 +2016,7 @@ impl JujikView {
                 }
             }
 
-            self.selection_entity_move(ctx, tab.path());
+            self.selection_entity_move(ctx, idx_tab, tab);
         }
     }
 
@@ -1289,6 +2150,16 @@ impl JujikView {
                 self.entitys_sortby_info
                     .direction
                     .clone_from(&self.entitys_sortby_info.sortby.direction);
+                (
+                    self.entitys_sortby_info.then,
+                    self.entitys_sortby_info.then_direction,
+                ) = match self.entitys_sortby_info.sortby.then.clone() {
+                    Some((field, direction)) => (Some(field), direction),
+                    None => (None, SortDirection::default()),
+                };
+                self.entitys_sortby_info.natural = self.entitys_sortby_info.sortby.natural;
+                self.entitys_sortby_info.ignore_case = self.entitys_sortby_info.sortby.ignore_case;
+                self.entitys_sortby_info.dirs_first = self.entitys_sortby_info.sortby.dirs_first;
 
                 self.entitys_sortby_info.show = true;
             }
@@ -1379,6 +2250,7 @@ impl JujikView {
                                     self.tabs.clone(),
                                     self.current_tab_idx,
                                     self.entitys_show.clone(),
+                                    self.entitys_format.clone(),
                                 )))
                                 .inspect_err(JujikError::handle_err);
 
@@ -1446,6 +2318,34 @@ impl JujikView {
             );
 
             if path.clicked() {}
+
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                let mut filter = tab.filter().clone();
+
+                ui.checkbox(
+                    &mut filter.ignore,
+                    RichText::new("Ignore files")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+                ui.checkbox(
+                    &mut filter.show_hidden,
+                    RichText::new("Hidden")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+
+                if filter.ne(tab.filter()) {
+                    let _ = self
+                        .controller
+                        .send(Command::ChangeTabFilter(
+                            self.current_tab_idx,
+                            tab.clone(),
+                            filter,
+                        ))
+                        .inspect_err(JujikError::handle_err);
+                }
+            });
         });
     }
 
@@ -1481,6 +2381,58 @@ impl JujikView {
         }
     }
 
+    // Hex dump of the beginning, as `xxd` shows it
+    fn view_binary(&mut self, ui: &mut Ui, entity: &Entity) {
+        match entity.head(BINARY_VIEW_LEN) {
+            Ok(head) => {
+                let dump = head
+                    .chunks(16)
+                    .enumerate()
+                    .map(|(idx, line)| {
+                        format!(
+                            "{:08x}  {:<47}  {}",
+                            idx * 16,
+                            line.iter()
+                                .map(|b| format!("{:02x}", b))
+                                .collect::<Vec<String>>()
+                                .join(" "),
+                            line.iter()
+                                .map(|b| if b.is_ascii_graphic() || *b == b' ' {
+                                    *b as char
+                                } else {
+                                    '.'
+                                })
+                                .collect::<String>()
+                        )
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n");
+
+                ScrollArea::both().show(ui, |ui| {
+                    ui.label(
+                        RichText::new(entity.mime().mime())
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    );
+                    ui.label(
+                        RichText::new(dump)
+                            .monospace()
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    );
+                });
+            }
+            Err(err) => {
+                self.message.show = true;
+                self.message.value = format!(
+                    "Can not read Entity {} content\n{}",
+                    entity.name_with_extension(),
+                    err
+                );
+            }
+        }
+    }
+
     fn edit_text_bar(&mut self, ui: &mut Ui, entity: &Entity) {
         ui.horizontal(|ui| {
             let save = ui.button(
@@ -1504,6 +2456,7 @@ impl JujikView {
                         self.tabs.clone(),
                         self.current_tab_idx,
                         self.entitys_show.clone(),
+                        self.entitys_format.clone(),
                     )))
                     .inspect_err(JujikError::handle_err);
 
@@ -1540,17 +2493,53 @@ impl JujikView {
                     .size(self.style.text_size),
             );
 
+            let progress = finder.progress();
+
+            let stop = ui.add_enabled(
+                progress.running,
+                Button::new(
+                    RichText::new("Stop")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                ),
+            );
+
             ui.label(
                 RichText::new(format!("{}", tab.path_str()))
                     .color(self.style.text_color.into_color32())
                     .size(self.style.text_size),
             );
 
+            ui.label(
+                RichText::new(format!(
+                    "{} Directories: {} Found: {}",
+                    if progress.running {
+                        "Searching..."
+                    } else {
+                        "Done."
+                    },
+                    progress.dirs,
+                    progress.matches
+                ))
+                .color(self.style.text_color.into_color32())
+                .size(self.style.text_size),
+            );
+
             if change.clicked() {
+                self.finder_info
+                    .set_parameters(finder.parameters(), &self.entitys_format);
+                self.finder_info.update = true;
                 self.finder_info.show = true;
                 self.finder_info.idx_tab = self.current_tab_idx;
                 self.finder_info.tab = tab.clone();
             }
+
+            if stop.clicked() {
+                let _ = self
+                    .controller
+                    .send(Command::StopFind(self.current_tab_idx, tab.clone()))
+                    .inspect_err(JujikError::handle_err);
+            }
         });
     }
 
@@ -1587,6 +2576,14 @@ impl JujikView {
                 .column(Column::remainder())
                 .column(Column::remainder())
                 .column(Column::remainder())
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .columns(Column::remainder(), self.entitys_show.xattrs.len())
                 .header(30.0, |mut header| {
                     header.col(|ui| {});
                     if self.entitys_show.filekind {
@@ -1622,6 +2619,17 @@ impl JujikView {
                             });
                         });
                     }
+                    if self.entitys_show.mime {
+                        header.col(|ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(
+                                    RichText::new("Type")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                            });
+                        });
+                    }
                     if self.entitys_show.permissions {
                         header.col(|ui| {
                             ui.centered_and_justified(|ui| {
@@ -1655,6 +2663,50 @@ impl JujikView {
                             });
                         });
                     }
+                    if self.entitys_show.allocated {
+                        header.col(|ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(
+                                    RichText::new("Allocated")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                            });
+                        });
+                    }
+                    if self.entitys_show.links {
+                        header.col(|ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(
+                                    RichText::new("Links")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                            });
+                        });
+                    }
+                    if self.entitys_show.inode {
+                        header.col(|ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(
+                                    RichText::new("Inode")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                            });
+                        });
+                    }
+                    if self.entitys_show.device {
+                        header.col(|ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(
+                                    RichText::new("Device")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                            });
+                        });
+                    }
                     if self.entitys_show.date_modification {
                         header.col(|ui| {
                             ui.centered_and_justified(|ui| {
@@ -1677,6 +2729,39 @@ impl JujikView {
                             });
                         });
                     }
+                    if self.entitys_show.date_access {
+                        header.col(|ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(
+                                    RichText::new("Access Date")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                            });
+                        });
+                    }
+                    if self.entitys_show.date_change {
+                        header.col(|ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(
+                                    RichText::new("Change Date")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                            });
+                        });
+                    }
+                    for name in &self.entitys_show.xattrs {
+                        header.col(|ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(
+                                    RichText::new(name)
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                            });
+                        });
+                    }
                 })
                 .body(|mut body| {
                     for (idx_entity, entity) in finder.entitys().iter().enumerate() {
@@ -1723,11 +2808,22 @@ impl JujikView {
                                     });
                                 });
                             }
+                            if self.entitys_show.mime {
+                                row.col(|ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(
+                                            RichText::new(entity.mime().mime())
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                        );
+                                    });
+                                });
+                            }
                             if self.entitys_show.permissions {
                                 row.col(|ui| {
                                     ui.centered_and_justified(|ui| {
                                         ui.label(
-                                            RichText::new(format!("{}", entity.permissions()))
+                                            RichText::new(entity.permissions_str())
                                                 .color(self.style.text_color.into_color32())
                                                 .size(self.style.text_size),
                                         );
@@ -1749,7 +2845,53 @@ impl JujikView {
                                 row.col(|ui| {
                                     ui.centered_and_justified(|ui| {
                                         ui.label(
-                                            RichText::new(format!("{}", entity.size()))
+                                            RichText::new(self.entitys_format.size(entity.size()))
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                        );
+                                    });
+                                });
+                            }
+                            if self.entitys_show.allocated {
+                                row.col(|ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(
+                                            RichText::new(
+                                                self.entitys_format.size(entity.allocated()),
+                                            )
+                                            .color(self.style.text_color.into_color32())
+                                            .size(self.style.text_size),
+                                        );
+                                    });
+                                });
+                            }
+                            if self.entitys_show.links {
+                                row.col(|ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(
+                                            RichText::new(entity.links().to_string())
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                        );
+                                    });
+                                });
+                            }
+                            if self.entitys_show.inode {
+                                row.col(|ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(
+                                            RichText::new(entity.inode().to_string())
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                        );
+                                    });
+                                });
+                            }
+                            if self.entitys_show.device {
+                                row.col(|ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(
+                                            RichText::new(entity.device_str())
                                                 .color(self.style.text_color.into_color32())
                                                 .size(self.style.text_size),
                                         );
@@ -1762,7 +2904,7 @@ impl JujikView {
                                         ui.label(
                                             RichText::new(format!(
                                                 "{}",
-                                                entity.modification().date_str()
+                                                self.entitys_format.date(entity.modification())
                                             ))
                                             .color(self.style.text_color.into_color32())
                                             .size(self.style.text_size),
@@ -1776,7 +2918,7 @@ impl JujikView {
                                         ui.label(
                                             RichText::new(format!(
                                                 "{}",
-                                                entity.creation().date_str()
+                                                self.entitys_format.date(entity.creation())
                                             ))
                                             .color(self.style.text_color.into_color32())
                                             .size(self.style.text_size),
@@ -1784,6 +2926,48 @@ impl JujikView {
                                     });
                                 });
                             }
+                            if self.entitys_show.date_access {
+                                row.col(|ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(
+                                            RichText::new(
+                                                self.entitys_format.date(entity.access()),
+                                            )
+                                            .color(self.style.text_color.into_color32())
+                                            .size(self.style.text_size),
+                                        );
+                                    });
+                                });
+                            }
+                            if self.entitys_show.date_change {
+                                row.col(|ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(
+                                            RichText::new(
+                                                self.entitys_format.date(entity.change()),
+                                            )
+                                            .color(self.style.text_color.into_color32())
+                                            .size(self.style.text_size),
+                                        );
+                                    });
+                                });
+                            }
+                            for name in &self.entitys_show.xattrs {
+                                row.col(|ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(
+                                            RichText::new(
+                                                entity
+                                                    .xattr(name)
+                                                    .map(|value| EntityXattrs::display(value))
+                                                    .unwrap_or_default(),
+                                            )
+                                            .color(self.style.text_color.into_color32())
+                                            .size(self.style.text_size),
+                                        );
+                                    });
+                                });
+                            }
 
                             self.entity_context_menu(
                                 &row.response(),
@@ -1812,6 +2996,10 @@ impl JujikView {
                     if self.entity_info.change_owners.show {
                         self.entity_change_owners(ctx);
                     }
+
+                    if self.entity_info.change_acl.show {
+                        self.entity_change_acl(ctx);
+                    }
                 });
         });
 
@@ -1823,7 +3011,7 @@ impl JujikView {
             }
         }
 
-        self.selection_entity_move(ctx, tab.path());
+        self.selection_entity_move(ctx, idx_tab, tab);
     }
 
     fn finder_info(&mut self, ctx: &Context, new_tab: bool) {
@@ -2074,7 +3262,10 @@ impl JujikView {
                         );
                     },
                     |ui| {
-                        ui.add(TextEdit::singleline(&mut self.finder_info.change_size.0));
+                        ui.add(
+                            TextEdit::singleline(&mut self.finder_info.change_size.0)
+                                .hint_text("1.5 MiB, 200 kB"),
+                        );
                     },
                 );
 
@@ -2088,7 +3279,10 @@ impl JujikView {
                         );
                     },
                     |ui| {
-                        ui.add(TextEdit::singleline(&mut self.finder_info.change_size.1));
+                        ui.add(
+                            TextEdit::singleline(&mut self.finder_info.change_size.1)
+                                .hint_text("1.5 MiB, 200 kB"),
+                        );
                     },
                 );
 
@@ -2142,38 +3336,29 @@ impl JujikView {
                     ui,
                     |ui| {
                         ui.label(
-                            RichText::new("Date creation from:")
+                            RichText::new("Filter:")
                                 .color(self.style.text_color.into_color32())
                                 .size(self.style.text_size),
                         );
                     },
                     |ui| {
-                        ui.add(
-                            DatePickerButton::new(&mut self.finder_info.change_date_creation.0)
-                                .id_salt(
-                                    format!("from {}", self.finder_info.date_creation.0.date_str())
-                                        .as_str(),
-                                ),
+                        ui.checkbox(
+                            &mut self.finder_info.filter.ignore,
+                            RichText::new("Skip ignored")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
                         );
-                    },
-                );
-
-                Sides::new().show(
-                    ui,
-                    |ui| {
-                        ui.label(
-                            RichText::new("Date creation to:")
+                        ui.checkbox(
+                            &mut self.finder_info.filter.show_hidden,
+                            RichText::new("Hidden")
                                 .color(self.style.text_color.into_color32())
                                 .size(self.style.text_size),
                         );
-                    },
-                    |ui| {
-                        ui.add(
-                            DatePickerButton::new(&mut self.finder_info.change_date_creation.1)
-                                .id_salt(
-                                    format!("from {}", self.finder_info.date_creation.1.date_str())
-                                        .as_str(),
-                                ),
+                        ui.checkbox(
+                            &mut self.finder_info.same_filesystem,
+                            RichText::new("Same filesystem")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
                         );
                     },
                 );
@@ -2192,7 +3377,18 @@ impl JujikView {
                             )
                             .clicked()
                         {
-                            //TODO DO PARAMETERS CHANGE
+                            match self.finder_info.parameters(&self.entitys_format) {
+                                Ok(parameters) => {
+                                    self.finder_info.parameters = parameters;
+                                }
+                                Err(err) => {
+                                    self.message.show = true;
+                                    self.message.value = format!("{}", err);
+
+                                    return;
+                                }
+                            }
+
                             if new_tab {
                                 let _ = self
                                     .controller
@@ -2349,6 +3545,7 @@ impl JujikView {
                                     self.tabs.clone(),
                                     self.current_tab_idx,
                                     self.entitys_show.clone(),
+                                    self.entitys_format.clone(),
                                 )))
                                 .inspect_err(JujikError::handle_err);
 
@@ -2546,6 +3743,7 @@ impl JujikView {
                                         self.tabs.clone(),
                                         self.current_tab_idx,
                                         self.entitys_show.clone(),
+                                        self.entitys_format.clone(),
                                     )))
                                     .inspect_err(JujikError::handle_err);
 
@@ -2600,256 +3798,1701 @@ impl JujikView {
     }
 }
 
-// Entity
+// Trash
 impl JujikView {
-    fn entity_context_menu(
-        &mut self,
-        response: &Response,
-        idx_tab: usize,
-        tab: &Tab,
-        idx_entity: usize,
-        entity: &Entity,
-    ) {
-        response.context_menu(|ui| {
-            if entity.is_dir() {
-                let open = ui.button(
-                    RichText::new("Open")
+    fn trash_bar(&mut self, ui: &mut Ui, tab: &Tab, trash_entitys: &[TrashEntity]) {
+        ui.horizontal(|ui| {
+            let selection: Vec<TrashEntity> = self.trash_info.selection.iter().cloned().collect();
+
+            let restore = ui.add_enabled(
+                !selection.is_empty(),
+                Button::new(
+                    RichText::new("Restore")
                         .color(self.style.text_color.into_color32())
                         .size(self.style.text_size),
-                );
+                ),
+            );
 
-                let open_new_tab = ui.button(
-                    RichText::new("Open in new Tab")
+            let purge = ui.add_enabled(
+                !selection.is_empty(),
+                Button::new(
+                    RichText::new("Purge")
                         .color(self.style.text_color.into_color32())
                         .size(self.style.text_size),
-                );
+                ),
+            );
 
-                if open.clicked() {
-                    let _ = self
-                        .controller
-                        .send(Command::ChangeTabDirectory(
-                            self.current_tab_idx,
-                            tab.clone(),
-                            Some(entity.path()),
-                        ))
-                        .inspect_err(JujikError::handle_err);
+            let empty = ui.add_enabled(
+                !trash_entitys.is_empty(),
+                Button::new(
+                    RichText::new("Empty")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                ),
+            );
 
-                    ui.close_menu();
-                }
+            ui.label(
+                RichText::new(format!("Items: {}", trash_entitys.len()))
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
 
-                if open_new_tab.clicked() {
-                    let _ = self
-                        .controller
-                        .send(Command::CreateEntitys(entity.path()))
-                        .inspect_err(JujikError::handle_err);
+            if restore.clicked() {
+                let _ = self
+                    .controller
+                    .send(Command::RestoreEntitys(
+                        self.current_tab_idx,
+                        tab.clone(),
+                        selection.clone(),
+                    ))
+                    .inspect_err(JujikError::handle_err);
 
-                    ui.close_menu();
-                }
-            } else {
-                let view = ui.button(
-                    RichText::new("View")
-                        .color(self.style.text_color.into_color32())
-                        .size(self.style.text_size),
-                );
+                self.trash_info.selection.clear();
+            }
 
-                let edit = ui.button(
-                    RichText::new("Edit")
-                        .color(self.style.text_color.into_color32())
-                        .size(self.style.text_size),
-                );
+            if purge.clicked() {
+                self.trash_info.show = true;
+                self.trash_info.idx_tab = self.current_tab_idx;
+                self.trash_info.tab = tab.clone();
+                self.trash_info.purge = selection;
+            }
 
-                if view.clicked() {
-                    let _ = self
-                        .controller
-                        .send(Command::CreateView(entity.path()))
-                        .inspect_err(JujikError::handle_err);
+            if empty.clicked() {
+                self.trash_info.show = true;
+                self.trash_info.idx_tab = self.current_tab_idx;
+                self.trash_info.tab = tab.clone();
+                self.trash_info.purge = trash_entitys.to_vec();
+            }
+        });
+    }
 
-                    ui.close_menu();
-                }
+    fn trash(
+        &mut self,
+        ctx: &Context,
+        ui: &mut Ui,
+        idx_tab: usize,
+        tab: &Tab,
+        trash_entitys: &[TrashEntity],
+    ) {
+        let mut responses: Vec<Response> = Vec::new();
 
-                if edit.clicked() {
-                    if entity.is_file() {
-                        let _ = self
-                            .controller
-                            .send(Command::CreateEditor(entity.path()))
-                            .inspect_err(JujikError::handle_err);
+        ScrollArea::horizontal().show(ui, |ui| {
+            TableBuilder::new(ui)
+                .resizable(true)
+                .cell_layout(Layout::left_to_right(Align::Center))
+                .sense(Sense::click())
+                .striped(true)
+                .column(Column::exact(40.0))
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .header(30.0, |mut header| {
+                    header.col(|_ui| {});
+                    for text in ["Name", "Original Path", "Deletion Date", "Size"] {
+                        header.col(|ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(
+                                    RichText::new(text)
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                            });
+                        });
+                    }
+                })
+                .body(|mut body| {
+                    for trash_entity in trash_entitys {
+                        body.row(40.0, |mut row| {
+                            row.set_selected(self.trash_info.selection.contains(trash_entity));
+
+                            row.col(|_ui| {});
+                            for text in [
+                                trash_entity.name(),
+                                trash_entity.original_str(),
+                                self.entitys_format.date(trash_entity.deletion()),
+                                self.entitys_format.size(trash_entity.entity().size()),
+                            ] {
+                                row.col(|ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(
+                                            RichText::new(text)
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                        );
+                                    });
+                                });
+                            }
+
+                            self.trash_context_menu(&row.response(), idx_tab, tab, trash_entity);
+
+                            responses.push(row.response());
+                        });
                     }
+                });
+        });
 
-                    ui.close_menu();
+        for (response, trash_entity) in responses.iter().zip(trash_entitys) {
+            if response.clicked() {
+                if !ui.input(|i| i.modifiers.ctrl) {
+                    self.trash_info.selection.clear();
                 }
-            }
 
-            let select = ui.button(
-                RichText::new("Select")
-                    .color(self.style.text_color.into_color32())
-                    .size(self.style.text_size),
-            );
+                if !self.trash_info.selection.remove(trash_entity) {
+                    self.trash_info.selection.insert(trash_entity.clone());
+                }
+            }
+        }
 
-            let copy = ui.button(
-                RichText::new("Copy")
-                    .color(self.style.text_color.into_color32())
-                    .size(self.style.text_size),
-            );
+        if self.trash_info.show {
+            self.trash_purge(ctx);
+        }
+    }
 
-            let cut = ui.button(
-                RichText::new("Cut")
+    fn trash_context_menu(
+        &mut self,
+        response: &Response,
+        idx_tab: usize,
+        tab: &Tab,
+        trash_entity: &TrashEntity,
+    ) {
+        response.context_menu(|ui| {
+            let restore = ui.button(
+                RichText::new("Restore")
                     .color(self.style.text_color.into_color32())
                     .size(self.style.text_size),
             );
 
-            let create_pin = ui.button(
-                RichText::new("Create Pin")
+            let purge = ui.button(
+                RichText::new("Purge")
                     .color(self.style.text_color.into_color32())
                     .size(self.style.text_size),
             );
 
-            let delete = ui.button(
-                RichText::new("Delete")
-                    .color(self.style.text_color.into_color32())
-                    .size(self.style.text_size),
-            );
+            let trash_entitys = if self.trash_info.selection.contains(trash_entity) {
+                self.trash_info.selection.iter().cloned().collect()
+            } else {
+                vec![trash_entity.clone()]
+            };
 
-            let info = ui.button(
-                RichText::new("Info")
-                    .color(self.style.text_color.into_color32())
-                    .size(self.style.text_size),
-            );
+            if restore.clicked() {
+                let _ = self
+                    .controller
+                    .send(Command::RestoreEntitys(
+                        idx_tab,
+                        tab.clone(),
+                        trash_entitys.clone(),
+                    ))
+                    .inspect_err(JujikError::handle_err);
 
-            if select.clicked() {
-                if self.entitys_selection.entitys.contains(entity) {
-                    self.entitys_selection.entitys.remove(entity);
-                } else {
-                    self.entitys_selection.entitys.insert(entity.clone());
-                }
+                self.trash_info.selection.clear();
 
                 ui.close_menu();
             }
 
-            if copy.clicked() {
-                self.entitys_selection.copy();
+            if purge.clicked() {
+                self.trash_info.show = true;
+                self.trash_info.idx_tab = idx_tab;
+                self.trash_info.tab = tab.clone();
+                self.trash_info.purge = trash_entitys;
 
                 ui.close_menu();
             }
+        });
+    }
 
-            if cut.clicked() {
-                self.entitys_selection.cut();
+    fn trash_purge(&mut self, ctx: &Context) {
+        let modal = Modal::new(Id::new("Trash Purge")).show(ctx, |ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "Are you sure to permanently delete: {}",
+                        self.trash_info
+                            .purge
+                            .iter()
+                            .map(|t| t.name())
+                            .collect::<Vec<String>>()
+                            .join(" ")
+                    ))
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+                );
 
-                ui.close_menu();
-            }
+                ui.separator();
 
-            if create_pin.clicked() {
-                if entity.is_dir() {
-                    let _ = self
-                        .controller
-                        .send(Command::CreatePin(entity.path()))
-                        .inspect_err(JujikError::handle_err);
-                } else {
+                let (mut yes, mut no) = (false, false);
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        yes = ui
+                            .button(
+                                RichText::new("Yes")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .clicked();
+                    },
+                    |ui| {
+                        no = ui
+                            .button(
+                                RichText::new("No")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .clicked();
+                    },
+                );
+
+                if yes {
                     let _ = self
                         .controller
-                        .send(Command::CreatePin(entity.path_dir()))
+                        .send(Command::PurgeEntitys(
+                            self.trash_info.idx_tab,
+                            self.trash_info.tab.clone(),
+                            std::mem::take(&mut self.trash_info.purge),
+                        ))
                         .inspect_err(JujikError::handle_err);
+
+                    self.trash_info.selection.clear();
                 }
 
-                ui.close_menu();
-            }
+                if yes || no {
+                    self.trash_info.show = false;
+                }
+            })
+        });
 
-            if delete.clicked() {
-                self.entitys_delete.show = (true, true);
-                self.entitys_delete.idx_tab = idx_tab;
-                self.entitys_delete.tab = tab.clone();
-                self.entitys_delete.idx_entity = idx_entity;
-                self.entitys_delete.entitys = self.entitys_selection.entitys_vec();
+        if modal.backdrop_response.clicked() {
+            self.trash_info.show = false;
+        }
+    }
+}
 
-                ui.close_menu();
+// Usage
+impl JujikView {
+    fn usage_bar(&mut self, ui: &mut Ui, tab: &Tab, usage: &EntitysUsage) {
+        ui.horizontal(|ui| {
+            let up = ui.button(
+                RichText::new("Up")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let progress = usage.progress();
+
+            let stop = ui.add_enabled(
+                progress.running,
+                Button::new(
+                    RichText::new("Stop")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                ),
+            );
+
+            let rescan = ui.add_enabled(
+                !progress.running,
+                Button::new(
+                    RichText::new("Rescan")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                ),
+            );
+
+            let mut same_filesystem = usage.same_filesystem();
+            let same_filesystem_changed = ui
+                .add_enabled(
+                    !progress.running,
+                    Checkbox::new(
+                        &mut same_filesystem,
+                        RichText::new("Same filesystem")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    ),
+                )
+                .changed();
+
+            ui.label(
+                RichText::new(tab.path_str())
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            ui.label(
+                RichText::new(format!(
+                    "{} Directories: {} Files: {} Total: {}",
+                    if progress.running {
+                        "Scanning..."
+                    } else {
+                        "Done."
+                    },
+                    progress.dirs,
+                    progress.files,
+                    self.entitys_format.size(&EntitySize::new(progress.total))
+                ))
+                .color(self.style.text_color.into_color32())
+                .size(self.style.text_size),
+            );
+
+            if up.clicked() {
+                self.entitys_selection.entitys.clear();
+
+                let _ = self
+                    .controller
+                    .send(Command::ChangeTabDirectory(
+                        self.current_tab_idx,
+                        tab.clone(),
+                        None,
+                    ))
+                    .inspect_err(JujikError::handle_err);
             }
 
-            if info.clicked() {
-                self.entity_info.show = true;
-                self.entity_info.idx_tab = idx_tab;
-                self.entity_info.tab = tab.clone();
-                self.entity_info.idx_entity = idx_entity;
-                self.entity_info.entity = entity.clone();
-                self.entity_info.path = entity.path_dir_str();
-                self.entity_info.name = entity.name();
-                self.entity_info.extension = entity.extension_str();
-                self.entity_info.kind = entity.kind().clone();
-                self.entity_info.permissions = entity.permissions().clone();
-                self.entity_info.owners = entity.owners().clone();
-                self.entity_info.size = entity.size().clone();
-                self.entity_info.modification = entity.modification().clone();
-                self.entity_info.creation = entity.creation().clone();
+            if stop.clicked() {
+                let _ = self
+                    .controller
+                    .send(Command::StopUsage(self.current_tab_idx, tab.clone()))
+                    .inspect_err(JujikError::handle_err);
+            }
 
-                ui.close_menu();
+            if rescan.clicked() {
+                let _ = self
+                    .controller
+                    .send(Command::ChangeTabDirectory(
+                        self.current_tab_idx,
+                        tab.clone(),
+                        Some(usage.path()),
+                    ))
+                    .inspect_err(JujikError::handle_err);
+            }
+
+            // Scanned again with the mount points in or out
+            if same_filesystem_changed {
+                let mut tab = tab.clone();
+
+                if let Some(usage) = tab.usage_mut() {
+                    usage.set_same_filesystem(same_filesystem);
+                }
+
+                let _ = self
+                    .controller
+                    .send(Command::ChangeTabDirectory(
+                        self.current_tab_idx,
+                        tab,
+                        Some(usage.path()),
+                    ))
+                    .inspect_err(JujikError::handle_err);
             }
         });
     }
 
-    fn entity_delete(&mut self, ctx: &Context) {
-        let modal = Modal::new(Id::new("Entity Delete: {}")).show(ctx, |ui| {
+    fn usage(
+        &mut self,
+        ctx: &Context,
+        ui: &mut Ui,
+        idx_tab: usize,
+        tab: &Tab,
+        usage: &EntitysUsage,
+    ) {
+        let entitys = usage.entitys();
+        let total = usage.progress().total.max(1);
+
+        self.usage_treemap(ui, idx_tab, tab, &entitys);
+
+        ui.separator();
+
+        let mut responses: Vec<Response> = Vec::new();
+
+        ScrollArea::horizontal().show(ui, |ui| {
+            TableBuilder::new(ui)
+                .resizable(true)
+                .cell_layout(Layout::left_to_right(Align::Center))
+                .sense(Sense::click())
+                .striped(true)
+                .column(Column::exact(40.0))
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .header(30.0, |mut header| {
+                    header.col(|_ui| {});
+                    for text in ["Name", "Size", "Usage"] {
+                        header.col(|ui| {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(
+                                    RichText::new(text)
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                );
+                            });
+                        });
+                    }
+                })
+                .body(|mut body| {
+                    for (idx_entity, entity) in entitys.iter().enumerate() {
+                        body.row(40.0, |mut row| {
+                            row.set_selected(self.entitys_selection.entitys.contains(entity));
+
+                            row.col(|_ui| {});
+                            for text in [
+                                entity.name_with_extension(),
+                                self.entitys_format.size(entity.size()),
+                            ] {
+                                row.col(|ui| {
+                                    ui.centered_and_justified(|ui| {
+                                        ui.label(
+                                            RichText::new(text)
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                        );
+                                    });
+                                });
+                            }
+                            row.col(|ui| {
+                                let fraction = entity.size().size_byte() as f32 / total as f32;
+
+                                ui.add(
+                                    ProgressBar::new(fraction)
+                                        .fill(self.style.primary_color.into_color32())
+                                        .text(
+                                            RichText::new(format!("{:.1}%", fraction * 100.0))
+                                                .color(self.style.text_color.into_color32())
+                                                .size(self.style.text_size),
+                                        ),
+                                );
+                            });
+
+                            self.entity_context_menu(
+                                &row.response(),
+                                idx_tab,
+                                tab,
+                                idx_entity,
+                                entity,
+                            );
+
+                            responses.push(row.response());
+                        });
+                    }
+                });
+        });
+
+        for (idx, (response, entity)) in responses.iter().zip(&entitys).enumerate() {
+            self.toogle_selection_entity(ui, response, idx, entity, &entitys);
+
+            if response.double_clicked() && entity.is_dir() {
+                self.usage_drill_down(tab, entity);
+            }
+        }
+
+        if self.entitys_delete.show.0 & self.entitys_delete.show.1 {
+            self.entity_delete(ctx);
+        }
+
+        if self.entity_info.show {
+            self.entity_info(ctx);
+        }
+
+        self.selection_entity_move(ctx, idx_tab, tab);
+    }
+
+    // Directories open on click, files are selected
+    fn usage_treemap(&mut self, ui: &mut Ui, idx_tab: usize, tab: &Tab, entitys: &[Entity]) {
+        let size = Vec2::new(ui.available_width(), ui.available_height() * 0.5);
+        let (response, painter) = ui.allocate_painter(size, Sense::hover());
+        let area = response.rect;
+
+        let sizes: Vec<u64> = entitys.iter().map(|e| e.size().size_byte()).collect();
+        let cells = EntitysUsage::treemap(
+            &sizes,
+            [area.min.x, area.min.y, area.width(), area.height()],
+        );
+
+        for (idx_entity, (entity, [x, y, width, height])) in entitys.iter().zip(cells).enumerate() {
+            if width < 1.0 || height < 1.0 {
+                continue;
+            }
+
+            let cell = Rect::from_min_size(Pos2::new(x, y), Vec2::new(width, height));
+            let fill = if self.entitys_selection.entitys.contains(entity) {
+                self.style.selection_color.into_color32()
+            } else if entity.is_dir() {
+                self.style.primary_color.into_color32()
+            } else {
+                self.style.primary_color.into_color32().gamma_multiply(0.6)
+            };
+
+            painter.rect_filled(cell.shrink(1.0), 2.0, fill);
+            painter.with_clip_rect(cell).text(
+                cell.center(),
+                Align2::CENTER_CENTER,
+                entity.name_with_extension(),
+                FontId::proportional(self.style.text_size),
+                self.style.text_color.into_color32(),
+            );
+
+            let response = ui
+                .interact(cell, Id::new(("Usage Treemap", idx_entity)), Sense::click())
+                .on_hover_text(format!(
+                    "{} {}",
+                    entity.name_with_extension(),
+                    self.entitys_format.size(entity.size())
+                ));
+
+            if response.clicked() {
+                if entity.is_dir() {
+                    self.usage_drill_down(tab, entity);
+                } else {
+                    self.toogle_selection_entity(
+                        ui,
+                        &response,
+                        idx_entity,
+                        entity,
+                        &entitys.to_vec(),
+                    );
+                }
+            }
+
+            self.entity_context_menu(&response, idx_tab, tab, idx_entity, entity);
+        }
+    }
+
+    fn usage_drill_down(&mut self, tab: &Tab, entity: &Entity) {
+        self.entitys_selection.entitys.clear();
+
+        let _ = self
+            .controller
+            .send(Command::ChangeTabDirectory(
+                self.current_tab_idx,
+                tab.clone(),
+                Some(entity.path()),
+            ))
+            .inspect_err(JujikError::handle_err);
+    }
+}
+
+// Job
+impl JujikView {
+    fn jobs(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new("Jobs")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let clear = ui.add_enabled(
+                self.jobs_info.jobs.iter().any(|j| j.is_finished()),
+                Button::new(
+                    RichText::new("Clear finished")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                ),
+            );
+
+            if clear.clicked() {
+                self.jobs_info.jobs.retain(|j| !j.is_finished());
+            }
+        });
+
+        ui.separator();
+
+        ScrollArea::vertical().show(ui, |ui| {
+            for job in &self.jobs_info.jobs {
+                self.job(ui, job);
+            }
+        });
+    }
+
+    fn job(&self, ui: &mut Ui, job: &Job) {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(format!("{} #{}: {}", job.kind, job.id, job.state))
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            ui.add(
+                ProgressBar::new(job.fraction())
+                    .desired_width(200.0)
+                    .show_percentage(),
+            );
+
+            ui.label(
+                RichText::new(format!(
+                    "Files: {}/{} Bytes: {}/{}",
+                    job.files.0, job.files.1, job.bytes.0, job.bytes.1
+                ))
+                .color(self.style.text_color.into_color32())
+                .size(self.style.text_size),
+            );
+
+            if let Some(eta) = job.eta
+                && !job.is_finished()
+            {
+                ui.label(
+                    RichText::new(format!("ETA: {}s", eta.as_secs()))
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+            }
+
+            let paused = job.state == JobState::Paused;
+
+            let pause = ui.add_enabled(
+                !job.is_finished(),
+                Button::new(
+                    RichText::new(if paused { "Resume" } else { "Pause" })
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                ),
+            );
+
+            let cancel = ui.add_enabled(
+                !job.is_finished(),
+                Button::new(
+                    RichText::new("Cancel")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                ),
+            );
+
+            if pause.clicked() {
+                let _ = self
+                    .controller
+                    .send(if paused {
+                        Command::ResumeJob(job.id)
+                    } else {
+                        Command::PauseJob(job.id)
+                    })
+                    .inspect_err(JujikError::handle_err);
+            }
+
+            if cancel.clicked() {
+                let _ = self
+                    .controller
+                    .send(Command::CancelJob(job.id))
+                    .inspect_err(JujikError::handle_err);
+            }
+        });
+
+        ui.label(
+            RichText::new(if job.errors.is_empty() {
+                job.current.to_string_lossy().to_string()
+            } else {
+                job.errors.clone()
+            })
+            .color(self.style.text_color.into_color32())
+            .size(self.style.text_size),
+        );
+
+        ui.separator();
+    }
+}
+
+// Entity
+impl JujikView {
+    fn entity_context_menu(
+        &mut self,
+        response: &Response,
+        idx_tab: usize,
+        tab: &Tab,
+        idx_entity: usize,
+        entity: &Entity,
+    ) {
+        response.context_menu(|ui| {
+            if entity.points_to_dir() {
+                let open = ui.button(
+                    RichText::new("Open")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+
+                let open_new_tab = ui.button(
+                    RichText::new("Open in new Tab")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+
+                // The scan does not follow links
+                let usage = ui.add_enabled(
+                    entity.is_dir(),
+                    Button::new(
+                        RichText::new("Disk usage")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    ),
+                );
+
+                if open.clicked() {
+                    let _ = self
+                        .controller
+                        .send(Command::ChangeTabDirectory(
+                            self.current_tab_idx,
+                            tab.clone(),
+                            Some(entity.path()),
+                        ))
+                        .inspect_err(JujikError::handle_err);
+
+                    ui.close_menu();
+                }
+
+                if open_new_tab.clicked() {
+                    let _ = self
+                        .controller
+                        .send(Command::CreateEntitys(entity.path()))
+                        .inspect_err(JujikError::handle_err);
+
+                    ui.close_menu();
+                }
+
+                if usage.clicked() {
+                    let _ = self
+                        .controller
+                        .send(Command::CreateUsage(entity.path()))
+                        .inspect_err(JujikError::handle_err);
+
+                    ui.close_menu();
+                }
+            } else {
+                let view = ui.button(
+                    RichText::new("View")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+
+                // Unsniffed types come from the name, the editor tells once it reads the content
+                let edit = ui.add_enabled(
+                    entity.mime().is_text() || !entity.mime().is_sniffed(),
+                    Button::new(
+                        RichText::new("Edit")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    ),
+                );
+
+                if view.clicked() {
+                    let _ = self
+                        .controller
+                        .send(Command::CreateView(entity.path()))
+                        .inspect_err(JujikError::handle_err);
+
+                    ui.close_menu();
+                }
+
+                if edit.clicked() {
+                    if entity.is_file() {
+                        let _ = self
+                            .controller
+                            .send(Command::CreateEditor(entity.path()))
+                            .inspect_err(JujikError::handle_err);
+                    }
+
+                    ui.close_menu();
+                }
+            }
+
+            let select = ui.button(
+                RichText::new("Select")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let copy = ui.button(
+                RichText::new("Copy")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let cut = ui.button(
+                RichText::new("Cut")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let create_pin = ui.button(
+                RichText::new("Create Pin")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let rename = ui.button(
+                RichText::new("Rename")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let chmod = ui.button(
+                RichText::new("Permissions")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let chown = ui.button(
+                RichText::new("Owners")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let delete = ui.button(
+                RichText::new("Delete")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let info = ui.button(
+                RichText::new("Info")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            if select.clicked() {
+                if self.entitys_selection.entitys.contains(entity) {
+                    self.entitys_selection.entitys.remove(entity);
+                } else {
+                    self.entitys_selection.entitys.insert(entity.clone());
+                }
+
+                ui.close_menu();
+            }
+
+            if copy.clicked() {
+                self.entitys_selection.copy();
+
+                ui.close_menu();
+            }
+
+            if cut.clicked() {
+                self.entitys_selection.cut();
+
+                ui.close_menu();
+            }
+
+            if create_pin.clicked() {
+                if entity.points_to_dir() {
+                    let _ = self
+                        .controller
+                        .send(Command::CreatePin(entity.path()))
+                        .inspect_err(JujikError::handle_err);
+                } else {
+                    let _ = self
+                        .controller
+                        .send(Command::CreatePin(entity.path_dir()))
+                        .inspect_err(JujikError::handle_err);
+                }
+
+                ui.close_menu();
+            }
+
+            if rename.clicked() {
+                // In the order of the tab so the counter follows what is shown
+                let mut entitys: Vec<Entity> = tab
+                    .entitys()
+                    .or_else(|| tab.finder().map(|f| f.entitys()))
+                    .or_else(|| tab.usage().map(|u| u.entitys()))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|e| self.entitys_selection.entitys.contains(e))
+                    .collect();
+
+                if entitys.is_empty() {
+                    entitys.push(entity.clone());
+                }
+
+                self.entitys_rename = EntitysRenameInfo {
+                    show: true,
+                    idx_tab,
+                    tab: tab.clone(),
+                    entitys,
+                    parameters: RenameParameters::default(),
+                    extension: (false, String::new()),
+                };
+
+                ui.close_menu();
+            }
+
+            if chmod.clicked() {
+                let mut entitys = self.entitys_selection.entitys_vec();
+
+                if entitys.is_empty() {
+                    entitys.push(entity.clone());
+                }
+
+                self.entitys_chmod = EntitysChmodInfo {
+                    show: true,
+                    idx_tab,
+                    tab: tab.clone(),
+                    entitys,
+                    expression: String::new(),
+                    recursive: false,
+                };
+
+                ui.close_menu();
+            }
+
+            if chown.clicked() {
+                let mut entitys = self.entitys_selection.entitys_vec();
+
+                if entitys.is_empty() {
+                    entitys.push(entity.clone());
+                }
+
+                self.entitys_chown = EntitysChownInfo {
+                    show: true,
+                    idx_tab,
+                    tab: tab.clone(),
+                    entitys,
+                    users: EntityOwners::users(),
+                    groups: EntityOwners::groups(),
+                    uid: (true, entity.owners().uid()),
+                    gid: (true, entity.owners().gid()),
+                    recursive: false,
+                    follow: false,
+                };
+
+                ui.close_menu();
+            }
+
+            if delete.clicked() {
+                self.entitys_delete.show = (true, true);
+                self.entitys_delete.permanent = ui.input(|i| i.modifiers.shift);
+                self.entitys_delete.idx_tab = idx_tab;
+                self.entitys_delete.tab = tab.clone();
+                self.entitys_delete.idx_entity = idx_entity;
+                self.entitys_delete.entitys = self.entitys_selection.entitys_vec();
+
+                ui.close_menu();
+            }
+
+            if info.clicked() {
+                self.entity_info.show = true;
+                self.entity_info.idx_tab = idx_tab;
+                self.entity_info.tab = tab.clone();
+                self.entity_info.idx_entity = idx_entity;
+                // One entity, its content and attributes can be read right away
+                self.entity_info.entity = entity.clone().with_details(EntityDetails {
+                    mime: true,
+                    xattrs: true,
+                });
+                self.entity_info.path = entity.path_dir_str();
+                self.entity_info.name = entity.name();
+                self.entity_info.extension = entity.extension_str();
+                self.entity_info.kind = entity.kind().clone();
+                self.entity_info.link = entity
+                    .link()
+                    .as_ref()
+                    .map(|l| l.text_str())
+                    .unwrap_or_default();
+                self.entity_info.permissions = entity.permissions().clone();
+                self.entity_info.acl = EntityAcl::new(&entity.path()).unwrap_or_default();
+                self.entity_info.owners = entity.owners().clone();
+                self.entity_info.size = entity.size().clone();
+                self.entity_info.modification = entity.modification().clone();
+                self.entity_info.creation = entity.creation().clone();
+                self.entity_info.access = entity.access().clone();
+                self.entity_info.change = entity.change().clone();
+                self.entity_info.xattrs = self
+                    .entity_info
+                    .entity
+                    .xattrs()
+                    .iter()
+                    .filter(|(name, _)| !EntityXattrs::is_acl(name))
+                    .map(|(name, value)| {
+                        let hex = EntityXattrs::text(value).is_none();
+
+                        (name.clone(), EntityXattrs::value_str(value, hex), hex)
+                    })
+                    .collect();
+                self.entity_info.new_xattr = Default::default();
+
+                ui.close_menu();
+            }
+        });
+    }
+
+    fn entity_delete(&mut self, ctx: &Context) {
+        let modal = Modal::new(Id::new("Entity Delete: {}")).show(ctx, |ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "{}: {}",
+                        if self.entitys_delete.permanent {
+                            "Are you sure to permanently delete"
+                        } else {
+                            "Move to trash"
+                        },
+                        self.entitys_delete
+                            .entitys
+                            .clone()
+                            .iter()
+                            .map(|e| e.name_with_extension())
+                            .collect::<Vec<String>>()
+                            .join(" ")
+                    ))
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+                );
+
+                ui.separator();
+
+                let (show_0, show_1) = &mut self.entitys_delete.show;
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        if ui
+                            .button(
+                                RichText::new("Yes")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .clicked()
+                        {
+                            let idx_tab = self.entitys_delete.idx_tab;
+                            let tab = self.entitys_delete.tab.clone();
+                            let entitys = self.entitys_delete.entitys.clone();
+
+                            let _ = self
+                                .controller
+                                .send(if self.entitys_delete.permanent {
+                                    Command::RemoveEntitys(idx_tab, tab, entitys)
+                                } else {
+                                    Command::DeleteEntitys(idx_tab, tab, entitys)
+                                })
+                                .inspect_err(JujikError::handle_err);
+
+                            *show_0 = false;
+                        }
+                    },
+                    |ui| {
+                        if ui
+                            .button(
+                                RichText::new("No")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .clicked()
+                        {
+                            *show_1 = false;
+                        }
+                    },
+                );
+            })
+        });
+
+        if modal.backdrop_response.clicked() {
+            self.entitys_delete.show = (false, false);
+        }
+    }
+
+    fn entitys_conflict(&mut self, ctx: &Context) {
+        let modal = Modal::new(Id::new("Entitys Conflict")).show(ctx, |ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(
+                    RichText::new("Already exist:")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+
+                ui.separator();
+
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for pathbuf in &self.entitys_conflict.conflicts {
+                        ui.label(
+                            RichText::new(pathbuf.to_string_lossy())
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    }
+                });
+
+                ui.separator();
+
+                let mut policy = None;
+
+                ui.horizontal(|ui| {
+                    for (text, conflict_policy) in [
+                        ("Overwrite", ConflictPolicy::Overwrite),
+                        ("Skip", ConflictPolicy::Skip),
+                        ("Rename", ConflictPolicy::Rename),
+                    ] {
+                        if ui
+                            .button(
+                                RichText::new(text)
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .clicked()
+                        {
+                            policy = Some(conflict_policy);
+                        }
+                    }
+
+                    if ui
+                        .button(
+                            RichText::new("Cancel")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        )
+                        .clicked()
+                    {
+                        self.entitys_conflict.show = false;
+                    }
+                });
+
+                if let Some(policy) = policy {
+                    let conflict = std::mem::take(&mut self.entitys_conflict);
+
+                    let command = match conflict.kind {
+                        TransferKind::Copy => Command::CopyEntitys(
+                            conflict.idx_tab,
+                            conflict.tab,
+                            conflict.idx_entity,
+                            conflict.entitys,
+                            conflict.pathbuf,
+                            policy,
+                        ),
+                        TransferKind::Move => Command::MoveEntitys(
+                            conflict.idx_tab,
+                            conflict.tab,
+                            conflict.idx_entity,
+                            conflict.entitys,
+                            conflict.pathbuf,
+                            policy,
+                        ),
+                    };
+
+                    let _ = self
+                        .controller
+                        .send(command)
+                        .inspect_err(JujikError::handle_err);
+                }
+            })
+        });
+
+        if modal.backdrop_response.clicked() {
+            self.entitys_conflict.show = false;
+        }
+    }
+
+    fn entitys_rename(&mut self, ctx: &Context) {
+        let modal = Modal::new(Id::new("Entitys Rename")).show(ctx, |ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(
+                    RichText::new(format!("Rename: {}", self.entitys_rename.entitys.len()))
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Search:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.text_edit_singleline(&mut self.entitys_rename.parameters.search);
+                        ui.checkbox(
+                            &mut self.entitys_rename.parameters.regex,
+                            RichText::new("Regex")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                );
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Replace:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.text_edit_singleline(&mut self.entitys_rename.parameters.replace);
+                    },
+                );
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Counter:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.add(
+                            DragValue::new(&mut self.entitys_rename.parameters.step)
+                                .prefix("Step: "),
+                        );
+                        ui.add(
+                            DragValue::new(&mut self.entitys_rename.parameters.start)
+                                .prefix("Start: "),
+                        );
+                    },
+                );
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Case:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ComboBox::from_id_salt("Rename Case")
+                            .selected_text(self.entitys_rename.parameters.case.to_string())
+                            .show_ui(ui, |ui| {
+                                for case in [
+                                    RenameCase::Keep,
+                                    RenameCase::Lower,
+                                    RenameCase::Upper,
+                                    RenameCase::Title,
+                                ] {
+                                    ui.selectable_value(
+                                        &mut self.entitys_rename.parameters.case,
+                                        case,
+                                        case.to_string(),
+                                    );
+                                }
+                            });
+                    },
+                );
+
+                let extension = self.entitys_rename.extension.0;
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.checkbox(
+                            &mut self.entitys_rename.extension.0,
+                            RichText::new("Extension:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.add_enabled(
+                            extension,
+                            TextEdit::singleline(&mut self.entitys_rename.extension.1),
+                        );
+                    },
+                );
+
+                ui.label(
+                    RichText::new("Tokens: {n} {n:03} {date} {date:%Y%m%d} {name} {ext}")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+
+                ui.separator();
+
+                let mut parameters = self.entitys_rename.parameters.clone();
+                parameters.extension = if self.entitys_rename.extension.0 {
+                    Some(self.entitys_rename.extension.1.clone())
+                } else {
+                    None
+                };
+
+                let previews = EntitysRename::new(parameters.clone())
+                    .map(|rename| rename.preview(&self.entitys_rename.entitys));
+
+                match &previews {
+                    Ok(previews) => self.entitys_rename_preview(ui, previews),
+                    Err(err) => {
+                        ui.label(
+                            RichText::new(err.to_string())
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    }
+                }
+
+                ui.separator();
+
+                let ready = previews
+                    .as_ref()
+                    .is_ok_and(|previews| previews.iter().all(|p| p.conflict.is_none()));
+                let mut rename = false;
+                let mut cancel = false;
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        cancel = ui
+                            .button(
+                                RichText::new("Cancel")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .clicked();
+
+                        rename = ui
+                            .add_enabled(
+                                ready,
+                                Button::new(
+                                    RichText::new("Rename")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                ),
+                            )
+                            .clicked();
+                    },
+                );
+
+                if rename {
+                    let _ = self
+                        .controller
+                        .send(Command::RenameEntitys(
+                            self.entitys_rename.idx_tab,
+                            self.entitys_rename.tab.clone(),
+                            self.entitys_rename.entitys.clone(),
+                            parameters,
+                        ))
+                        .inspect_err(JujikError::handle_err);
+
+                    self.entitys_selection.entitys.clear();
+                }
+
+                if rename || cancel {
+                    self.entitys_rename.show = false;
+                }
+            })
+        });
+
+        if modal.backdrop_response.clicked() {
+            self.entitys_rename.show = false;
+        }
+    }
+
+    fn entitys_chmod(&mut self, ctx: &Context) {
+        let modal = Modal::new(Id::new("Entitys Chmod")).show(ctx, |ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(
+                    RichText::new(format!("Permissions: {}", self.entitys_chmod.entitys.len()))
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Expression:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.checkbox(
+                            &mut self.entitys_chmod.recursive,
+                            RichText::new("Recursive")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                        ui.add(
+                            TextEdit::singleline(&mut self.entitys_chmod.expression)
+                                .hint_text("u+x,go-w"),
+                        );
+                    },
+                );
+
+                ui.label(
+                    RichText::new(
+                        "Octal 0750 or [ugoa][+-=][rwxXst], X: directories and executables",
+                    )
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size * 0.8),
+                );
+
+                ui.separator();
+
+                let expression = EntityPermissionsExpression::parse(&self.entitys_chmod.expression);
+
+                match &expression {
+                    Ok(expression) => {
+                        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                            for entity in &self.entitys_chmod.entitys {
+                                let after = EntityPermissions::new(
+                                    expression
+                                        .apply(entity.permissions().mode(), entity.points_to_dir()),
+                                );
+
+                                ui.label(
+                                    RichText::new(format!(
+                                        "{}: {} -> {}",
+                                        entity.name_with_extension(),
+                                        entity.permissions().symbolic(),
+                                        after.symbolic()
+                                    ))
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                                );
+                            }
+                        });
+                    }
+                    Err(err) => {
+                        ui.label(
+                            RichText::new(err.to_string())
+                                .color(Color32::RED)
+                                .size(self.style.text_size),
+                        );
+                    }
+                }
+
+                ui.separator();
+
+                let mut apply = false;
+                let mut cancel = false;
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        cancel = ui
+                            .button(
+                                RichText::new("Cancel")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .clicked();
+
+                        apply = ui
+                            .add_enabled(
+                                expression.is_ok(),
+                                Button::new(
+                                    RichText::new("Apply")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                ),
+                            )
+                            .clicked();
+                    },
+                );
+
+                if apply && let Ok(expression) = expression {
+                    let _ = self
+                        .controller
+                        .send(Command::ChangeEntitysPermissions(
+                            self.entitys_chmod.idx_tab,
+                            self.entitys_chmod.tab.clone(),
+                            self.entitys_chmod.entitys.clone(),
+                            expression,
+                            self.entitys_chmod.recursive,
+                        ))
+                        .inspect_err(JujikError::handle_err);
+
+                    self.entitys_selection.entitys.clear();
+                }
+
+                if apply || cancel {
+                    self.entitys_chmod.show = false;
+                }
+            })
+        });
+
+        if modal.backdrop_response.clicked() {
+            self.entitys_chmod.show = false;
+        }
+    }
+
+    fn entitys_chown(&mut self, ctx: &Context) {
+        let modal = Modal::new(Id::new("Entitys Chown")).show(ctx, |ui| {
             ui.vertical_centered_justified(|ui| {
                 ui.label(
-                    RichText::new(format!(
-                        "Are you sure to delete: {}",
-                        self.entitys_delete
-                            .entitys
-                            .clone()
-                            .iter()
-                            .map(|e| e.name_with_extension())
-                            .collect::<Vec<String>>()
-                            .join(" ")
-                    ))
-                    .color(self.style.text_color.into_color32())
-                    .size(self.style.text_size),
+                    RichText::new(format!("Owners: {}", self.entitys_chown.entitys.len()))
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
                 );
 
                 ui.separator();
 
-                let (show_0, show_1) = &mut self.entitys_delete.show;
+                let info = &mut self.entitys_chown;
 
-                Sides::new().show(
-                    ui,
-                    |ui| {
-                        if ui
-                            .button(
-                                RichText::new("Yes")
+                for (label, ids, id) in [
+                    ("User", &info.users, &mut info.uid),
+                    ("Group", &info.groups, &mut info.gid),
+                ] {
+                    let enabled = id.0;
+
+                    Sides::new().show(
+                        ui,
+                        |ui| {
+                            ui.checkbox(
+                                &mut id.0,
+                                RichText::new(format!("{}:", label))
                                     .color(self.style.text_color.into_color32())
                                     .size(self.style.text_size),
-                            )
-                            .clicked()
-                        {
-                            let _ = self
-                                .controller
-                                .send(Command::DeleteEntitys(
-                                    self.entitys_delete.idx_tab,
-                                    self.entitys_delete.tab.clone(),
-                                    self.entitys_delete.entitys.clone(),
-                                ))
-                                .inspect_err(JujikError::handle_err);
+                            );
+                        },
+                        |ui| {
+                            ui.add_enabled(enabled, DragValue::new(&mut id.1));
+
+                            let selected = ids
+                                .iter()
+                                .find(|(i, _)| *i == id.1)
+                                .map(|(_, name)| name.clone())
+                                .unwrap_or("No name".to_string());
+
+                            ui.add_enabled_ui(enabled, |ui| {
+                                ComboBox::from_id_salt(label)
+                                    .selected_text(selected)
+                                    .height(300.0)
+                                    .show_ui(ui, |ui| {
+                                        for (i, name) in ids.iter() {
+                                            ui.selectable_value(
+                                                &mut id.1,
+                                                *i,
+                                                format!("{} ({})", name, i),
+                                            );
+                                        }
+                                    });
+                            });
+                        },
+                    );
+                }
 
-                            *show_0 = false;
-                        }
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        ui.checkbox(
+                            &mut info.follow,
+                            RichText::new("Follow symlinks")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                        ui.checkbox(
+                            &mut info.recursive,
+                            RichText::new("Recursive")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
                     },
+                );
+
+                ui.separator();
+
+                let ready = info.uid.0 || info.gid.0;
+                let mut apply = false;
+                let mut cancel = false;
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
                     |ui| {
-                        if ui
+                        cancel = ui
                             .button(
-                                RichText::new("No")
+                                RichText::new("Cancel")
                                     .color(self.style.text_color.into_color32())
                                     .size(self.style.text_size),
                             )
-                            .clicked()
-                        {
-                            *show_1 = false;
-                        }
+                            .clicked();
+
+                        apply = ui
+                            .add_enabled(
+                                ready,
+                                Button::new(
+                                    RichText::new("Apply")
+                                        .color(self.style.text_color.into_color32())
+                                        .size(self.style.text_size),
+                                ),
+                            )
+                            .clicked();
                     },
                 );
+
+                if apply {
+                    let ids = (
+                        info.uid.0.then_some(info.uid.1),
+                        info.gid.0.then_some(info.gid.1),
+                    );
+
+                    let _ = self
+                        .controller
+                        .send(Command::ChangeEntitysOwners(
+                            info.idx_tab,
+                            info.tab.clone(),
+                            info.entitys.clone(),
+                            ids,
+                            info.recursive,
+                            info.follow,
+                        ))
+                        .inspect_err(JujikError::handle_err);
+
+                    self.entitys_selection.entitys.clear();
+                }
+
+                if apply || cancel {
+                    self.entitys_chown.show = false;
+                }
             })
         });
 
         if modal.backdrop_response.clicked() {
-            self.entitys_delete.show = (false, false);
+            self.entitys_chown.show = false;
         }
     }
 
+    fn entitys_rename_preview(&self, ui: &mut Ui, previews: &[RenamePreview]) {
+        ui.push_id("Rename Preview", |ui| {
+            TableBuilder::new(ui)
+                .cell_layout(Layout::left_to_right(Align::Center))
+                .striped(true)
+                .max_scroll_height(300.0)
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .column(Column::remainder())
+                .header(30.0, |mut header| {
+                    for text in ["Name", "New Name", "Conflict"] {
+                        header.col(|ui| {
+                            ui.label(
+                                RichText::new(text)
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(20.0, previews.len(), |mut row| {
+                        let preview = &previews[row.index()];
+
+                        for pathbuf in [&preview.source, &preview.destination] {
+                            row.col(|ui| {
+                                ui.label(
+                                    RichText::new(
+                                        pathbuf.file_name().unwrap_or_default().to_string_lossy(),
+                                    )
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                                );
+                            });
+                        }
+
+                        row.col(|ui| {
+                            ui.label(
+                                RichText::new(preview.conflict.clone().unwrap_or_default())
+                                    .color(Color32::RED)
+                                    .size(self.style.text_size),
+                            );
+                        });
+                    });
+                });
+        });
+    }
+
     fn entity_create(&mut self, ctx: &Context) {
         let modal = Modal::new(Id::new("Entity Create")).show(ctx, |ui| {
             ui.vertical_centered_justified(|ui| {
@@ -2925,6 +5568,7 @@ impl JujikView {
                                     self.tabs.clone(),
                                     self.current_tab_idx,
                                     self.entitys_show.clone(),
+                                    self.entitys_format.clone(),
                                 )))
                                 .inspect_err(JujikError::handle_err);
 
@@ -3031,6 +5675,72 @@ impl JujikView {
                     },
                 );
 
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Type:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.add(
+                            Label::new(
+                                RichText::new(self.entity_info.entity.mime().mime())
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .selectable(true),
+                        );
+                    },
+                );
+
+                if let Some(link) = self.entity_info.entity.link().clone() {
+                    Sides::new().show(
+                        ui,
+                        |ui| {
+                            ui.label(
+                                RichText::new("Link:")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                        },
+                        |ui| {
+                            ui.text_edit_singleline(&mut self.entity_info.link);
+                        },
+                    );
+
+                    Sides::new().show(
+                        ui,
+                        |ui| {
+                            ui.label(
+                                RichText::new("Target:")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+                        },
+                        |ui| {
+                            let target = if link.exists() {
+                                RichText::new(format!(
+                                    "{} ({:?})",
+                                    link.target().to_string_lossy(),
+                                    link.target_kind()
+                                ))
+                                .color(self.style.text_color.into_color32())
+                            } else {
+                                RichText::new(format!(
+                                    "{} (broken)",
+                                    link.target().to_string_lossy()
+                                ))
+                                .color(Color32::RED)
+                            };
+
+                            ui.add(Label::new(target.size(self.style.text_size)).selectable(true));
+                        },
+                    );
+                }
+
                 Sides::new().show(
                     ui,
                     |ui| {
@@ -3102,6 +5812,109 @@ impl JujikView {
                                         EntityPermissionsKind::Read,
                                     ),
                                 );
+                                self.entity_info.change_permissions.special = (
+                                    self.entity_info
+                                        .permissions
+                                        .has_special(EntityPermissionsSpecial::Setuid),
+                                    self.entity_info
+                                        .permissions
+                                        .has_special(EntityPermissionsSpecial::Setgid),
+                                    self.entity_info
+                                        .permissions
+                                        .has_special(EntityPermissionsSpecial::Sticky),
+                                );
+                            }
+                        });
+                    },
+                );
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("ACL:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        let entries = self
+                            .entity_info
+                            .acl
+                            .access
+                            .iter()
+                            .map(|e| e.to_string())
+                            .chain(
+                                self.entity_info
+                                    .acl
+                                    .default
+                                    .iter()
+                                    .map(|e| format!("default:{}", e)),
+                            )
+                            .collect::<Vec<String>>()
+                            .join("\n");
+
+                        let acl = ui.add(
+                            Label::new(
+                                RichText::new(entries)
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .selectable(true),
+                        );
+
+                        acl.context_menu(|ui| {
+                            let change = ui.button(
+                                RichText::new("Change")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+
+                            if change.clicked() {
+                                self.entity_info.change_acl.show = true;
+                                self.entity_info.change_acl.acl = self.entity_info.acl.clone();
+
+                                ui.close_menu();
+                            }
+                        });
+                    },
+                );
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Owners:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        let owners = ui.add(
+                            Label::new(
+                                RichText::new(format!("{}", self.entity_info.owners))
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .selectable(true),
+                        );
+
+                        owners.context_menu(|ui| {
+                            let change = ui.button(
+                                RichText::new("Change")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            );
+
+                            if change.clicked() {
+                                self.entity_info.change_owners.show = true;
+
+                                self.entity_info.change_owners.uid = self.entity_info.owners.uid();
+                                self.entity_info.change_owners.gid = self.entity_info.owners.gid();
+                                self.entity_info.change_owners.username =
+                                    self.entity_info.owners.username();
+                                self.entity_info.change_owners.groupname =
+                                    self.entity_info.owners.groupname();
                             }
                         });
                     },
@@ -3111,39 +5924,74 @@ impl JujikView {
                     ui,
                     |ui| {
                         ui.label(
-                            RichText::new("Owners:")
+                            RichText::new("Size:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.label(
+                            RichText::new(self.entitys_format.size(&self.entity_info.size))
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                );
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Allocated size:")
                                 .color(self.style.text_color.into_color32())
                                 .size(self.style.text_size),
                         );
                     },
                     |ui| {
-                        let owners = ui.add(
-                            Label::new(
-                                RichText::new(format!("{}", self.entity_info.owners))
-                                    .color(self.style.text_color.into_color32())
-                                    .size(self.style.text_size),
+                        ui.label(
+                            RichText::new(
+                                self.entitys_format
+                                    .size(self.entity_info.entity.allocated()),
                             )
-                            .selectable(true),
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
                         );
+                    },
+                );
 
-                        owners.context_menu(|ui| {
-                            let change = ui.button(
-                                RichText::new("Change")
-                                    .color(self.style.text_color.into_color32())
-                                    .size(self.style.text_size),
-                            );
-
-                            if change.clicked() {
-                                self.entity_info.change_owners.show = true;
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Hard links:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.label(
+                            RichText::new(self.entity_info.entity.links().to_string())
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                );
 
-                                self.entity_info.change_owners.uid = self.entity_info.owners.uid();
-                                self.entity_info.change_owners.gid = self.entity_info.owners.gid();
-                                self.entity_info.change_owners.username =
-                                    self.entity_info.owners.username();
-                                self.entity_info.change_owners.groupname =
-                                    self.entity_info.owners.groupname();
-                            }
-                        });
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Inode:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.label(
+                            RichText::new(self.entity_info.entity.inode().to_string())
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
                     },
                 );
 
@@ -3151,14 +5999,14 @@ impl JujikView {
                     ui,
                     |ui| {
                         ui.label(
-                            RichText::new("Size:")
+                            RichText::new("Device:")
                                 .color(self.style.text_color.into_color32())
                                 .size(self.style.text_size),
                         );
                     },
                     |ui| {
                         ui.label(
-                            RichText::new(format!("{}", self.entity_info.size))
+                            RichText::new(self.entity_info.entity.device_str())
                                 .color(self.style.text_color.into_color32())
                                 .size(self.style.text_size),
                         );
@@ -3176,7 +6024,7 @@ impl JujikView {
                     },
                     |ui| {
                         ui.label(
-                            RichText::new(format!("{}", self.entity_info.modification.date_str()))
+                            RichText::new(self.entitys_format.date(&self.entity_info.modification))
                                 .color(self.style.text_color.into_color32())
                                 .size(self.style.text_size),
                         );
@@ -3194,7 +6042,43 @@ impl JujikView {
                     },
                     |ui| {
                         ui.label(
-                            RichText::new(format!("{}", self.entity_info.creation.date_str()))
+                            RichText::new(self.entitys_format.date(&self.entity_info.creation))
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                );
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Date access:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.label(
+                            RichText::new(self.entitys_format.date(&self.entity_info.access))
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                );
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Date change:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.label(
+                            RichText::new(self.entitys_format.date(&self.entity_info.change))
                                 .color(self.style.text_color.into_color32())
                                 .size(self.style.text_size),
                         );
@@ -3203,6 +6087,10 @@ impl JujikView {
 
                 ui.separator();
 
+                self.entity_xattrs(ui);
+
+                ui.separator();
+
                 Sides::new().show(
                     ui,
                     |_ui| {},
@@ -3223,6 +6111,7 @@ impl JujikView {
                                     self.tabs.clone(),
                                     self.current_tab_idx,
                                     self.entitys_show.clone(),
+                                    self.entitys_format.clone(),
                                 )))
                                 .inspect_err(JujikError::handle_err);
 
@@ -3240,6 +6129,7 @@ impl JujikView {
                                         self.entity_info.idx_entity,
                                         vec![self.entity_info.entity.clone()],
                                         PathBuf::from(self.entity_info.path.clone()),
+                                        ConflictPolicy::Ask,
                                     ))
                                     .inspect_err(JujikError::handle_err);
                             }
@@ -3275,6 +6165,81 @@ impl JujikView {
                                     .inspect_err(JujikError::handle_err);
                             }
 
+                            if !self.entity_info.acl.access.is_empty()
+                                && EntityAcl::new(&self.entity_info.entity.path())
+                                    .is_ok_and(|acl| acl.ne(&self.entity_info.acl))
+                            {
+                                let _ = self
+                                    .controller
+                                    .send(Command::ChangeEntityAcl(
+                                        self.entity_info.idx_tab,
+                                        self.entity_info.tab.clone(),
+                                        self.entity_info.idx_entity,
+                                        self.entity_info.entity.clone(),
+                                        self.entity_info.acl.clone(),
+                                    ))
+                                    .inspect_err(JujikError::handle_err);
+                            }
+
+                            let before: Vec<EntityXattr> = self
+                                .entity_info
+                                .entity
+                                .xattrs()
+                                .iter()
+                                .filter(|(name, _)| !EntityXattrs::is_acl(name))
+                                .cloned()
+                                .collect();
+                            // Values shown as they were are kept byte for byte
+                            let after = self
+                                .entity_info
+                                .xattrs
+                                .iter()
+                                .map(|(name, value, hex)| {
+                                    match self.entity_info.entity.xattr(name) {
+                                        Some(original)
+                                            if EntityXattrs::value_str(original, *hex)
+                                                .eq(value) =>
+                                        {
+                                            Ok((name.clone(), original.clone()))
+                                        }
+                                        _ => EntityXattrs::parse_value(value, *hex)
+                                            .map(|value| (name.clone(), value)),
+                                    }
+                                })
+                                .collect::<Result<Vec<EntityXattr>, JujikError>>();
+
+                            match after.map(|after| EntityXattrs::diff(&before, &after)) {
+                                Ok(changes) if !changes.is_empty() => {
+                                    let _ = self
+                                        .controller
+                                        .send(Command::ChangeEntityXattrs(
+                                            self.entity_info.idx_tab,
+                                            self.entity_info.tab.clone(),
+                                            self.entity_info.idx_entity,
+                                            self.entity_info.entity.clone(),
+                                            changes,
+                                        ))
+                                        .inspect_err(JujikError::handle_err);
+                                }
+                                Ok(_) => {}
+                                Err(err) => JujikError::handle_err(&err),
+                            }
+
+                            if let Some(link) = self.entity_info.entity.link()
+                                && link.text_str().ne(&self.entity_info.link)
+                            {
+                                let _ = self
+                                    .controller
+                                    .send(Command::ChangeEntityLink(
+                                        self.entity_info.idx_tab,
+                                        self.entity_info.tab.clone(),
+                                        self.entity_info.idx_entity,
+                                        self.entity_info.entity.clone(),
+                                        PathBuf::from(self.entity_info.link.clone()),
+                                    ))
+                                    .inspect_err(JujikError::handle_err);
+                            }
+
                             if self
                                 .entity_info
                                 .entity
@@ -3427,6 +6392,37 @@ impl JujikView {
                     },
                 );
 
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Special:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ui.checkbox(
+                            &mut self.entity_info.change_permissions.special.2,
+                            RichText::new("sticky")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                        ui.checkbox(
+                            &mut self.entity_info.change_permissions.special.1,
+                            RichText::new("setgid")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                        ui.checkbox(
+                            &mut self.entity_info.change_permissions.special.0,
+                            RichText::new("setuid")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                );
+
                 ui.separator();
 
                 Sides::new().show(
@@ -3449,6 +6445,7 @@ impl JujikView {
                                     self.tabs.clone(),
                                     self.current_tab_idx,
                                     self.entitys_show.clone(),
+                                    self.entitys_format.clone(),
                                 )))
                                 .inspect_err(JujikError::handle_err);
 
@@ -3536,40 +6533,345 @@ impl JujikView {
                                 );
                             }
 
-                            if self.entity_info.change_permissions.other.1 {
-                                self.entity_info.permissions.set(
-                                    EntityPermissionsCategory::Other,
-                                    EntityPermissionsKind::Write,
-                                );
-                            } else {
-                                self.entity_info.permissions.unset(
-                                    EntityPermissionsCategory::Other,
-                                    EntityPermissionsKind::Write,
-                                );
-                            }
+                            if self.entity_info.change_permissions.other.1 {
+                                self.entity_info.permissions.set(
+                                    EntityPermissionsCategory::Other,
+                                    EntityPermissionsKind::Write,
+                                );
+                            } else {
+                                self.entity_info.permissions.unset(
+                                    EntityPermissionsCategory::Other,
+                                    EntityPermissionsKind::Write,
+                                );
+                            }
+
+                            if self.entity_info.change_permissions.other.2 {
+                                self.entity_info.permissions.set(
+                                    EntityPermissionsCategory::Other,
+                                    EntityPermissionsKind::Read,
+                                );
+                            } else {
+                                self.entity_info.permissions.unset(
+                                    EntityPermissionsCategory::Other,
+                                    EntityPermissionsKind::Read,
+                                );
+                            }
+
+                            if self.entity_info.change_permissions.special.0 {
+                                self.entity_info
+                                    .permissions
+                                    .set_special(EntityPermissionsSpecial::Setuid);
+                            } else {
+                                self.entity_info
+                                    .permissions
+                                    .unset_special(EntityPermissionsSpecial::Setuid);
+                            }
+
+                            if self.entity_info.change_permissions.special.1 {
+                                self.entity_info
+                                    .permissions
+                                    .set_special(EntityPermissionsSpecial::Setgid);
+                            } else {
+                                self.entity_info
+                                    .permissions
+                                    .unset_special(EntityPermissionsSpecial::Setgid);
+                            }
+
+                            if self.entity_info.change_permissions.special.2 {
+                                self.entity_info
+                                    .permissions
+                                    .set_special(EntityPermissionsSpecial::Sticky);
+                            } else {
+                                self.entity_info
+                                    .permissions
+                                    .unset_special(EntityPermissionsSpecial::Sticky);
+                            }
+
+                            self.entity_info.change_permissions.show = false;
+                        }
+                    },
+                );
+            });
+        });
+
+        if modal.backdrop_response.clicked() {
+            self.entity_info.change_permissions.show = false;
+        }
+    }
+
+    fn entity_change_acl(&mut self, ctx: &Context) {
+        let mut acl = std::mem::take(&mut self.entity_info.change_acl.acl);
+        let mut save = false;
+
+        let modal = Modal::new(Id::new("Entity Change ACL")).show(ctx, |ui| {
+            ui.vertical_centered_justified(|ui| {
+                ui.label(
+                    RichText::new(format!("Change ACL: {}", self.entity_info.name))
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+
+                ui.separator();
+
+                self.entity_acl_entries(ui, "Access", &mut acl.access);
+
+                if self.entity_info.entity.is_dir() {
+                    let mut default = !acl.default.is_empty();
+
+                    ui.checkbox(
+                        &mut default,
+                        RichText::new("Default ACL")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    );
+
+                    if default && acl.default.is_empty() {
+                        acl.default = acl.access.clone();
+                    } else if !default {
+                        acl.default.clear();
+                    }
+
+                    if default {
+                        self.entity_acl_entries(ui, "Default", &mut acl.default);
+                    }
+                }
+
+                ui.separator();
+
+                Sides::new().show(
+                    ui,
+                    |_ui| {},
+                    |ui| {
+                        save = ui
+                            .button(
+                                RichText::new("Save")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .clicked();
+                    },
+                );
+            });
+        });
+
+        if save {
+            self.entity_info.acl = acl.clone();
+        }
+
+        if save || modal.backdrop_response.clicked() {
+            self.entity_info.change_acl.show = false;
+        }
+
+        self.entity_info.change_acl.acl = acl;
+    }
+
+    fn entity_xattrs(&mut self, ui: &mut Ui) {
+        let mut xattrs = std::mem::take(&mut self.entity_info.xattrs);
+
+        ui.label(
+            RichText::new("Xattrs:")
+                .color(self.style.text_color.into_color32())
+                .size(self.style.text_size),
+        );
+
+        let mut remove = None;
+
+        for (idx, (name, value, hex)) in xattrs.iter_mut().enumerate() {
+            ui.push_id(idx, |ui| {
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new(name.as_str())
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        if ui
+                            .button(
+                                RichText::new("Remove")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .clicked()
+                        {
+                            remove = Some(idx);
+                        }
+
+                        let mut as_hex = *hex;
 
-                            if self.entity_info.change_permissions.other.2 {
-                                self.entity_info.permissions.set(
-                                    EntityPermissionsCategory::Other,
-                                    EntityPermissionsKind::Read,
-                                );
-                            } else {
-                                self.entity_info.permissions.unset(
-                                    EntityPermissionsCategory::Other,
-                                    EntityPermissionsKind::Read,
-                                );
-                            }
+                        ui.checkbox(
+                            &mut as_hex,
+                            RichText::new("Hex")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
 
-                            self.entity_info.change_permissions.show = false;
+                        if as_hex && !*hex {
+                            *value = EntityXattrs::hex(value.as_bytes());
+                            *hex = true;
+                        } else if !as_hex
+                            && *hex
+                            && let Some(text) = EntityXattrs::parse_hex(value)
+                                .ok()
+                                .and_then(|bytes| EntityXattrs::text(&bytes))
+                        {
+                            // Binary values stay in hex
+                            *value = text;
+                            *hex = false;
                         }
+
+                        ui.add(TextEdit::singleline(value).desired_width(200.0));
                     },
                 );
             });
+        }
+
+        if let Some(idx) = remove {
+            xattrs.remove(idx);
+        }
+
+        let (name, value, hex) = &mut self.entity_info.new_xattr;
+
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(name)
+                    .hint_text("user.name")
+                    .desired_width(120.0),
+            );
+            ui.add(
+                TextEdit::singleline(value)
+                    .hint_text("value")
+                    .desired_width(160.0),
+            );
+            ui.checkbox(
+                hex,
+                RichText::new("Hex")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+
+            let new_name = name.trim().to_string();
+
+            if ui
+                .add_enabled(
+                    !new_name.is_empty()
+                        && !EntityXattrs::is_acl(&new_name)
+                        && !xattrs.iter().any(|(n, _, _)| n.eq(&new_name)),
+                    Button::new(
+                        RichText::new("Add")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    ),
+                )
+                .clicked()
+            {
+                xattrs.push((new_name, std::mem::take(value), *hex));
+                name.clear();
+            }
         });
 
-        if modal.backdrop_response.clicked() {
-            self.entity_info.change_permissions.show = false;
+        self.entity_info.xattrs = xattrs;
+    }
+
+    fn entity_acl_entries(&mut self, ui: &mut Ui, label: &str, entries: &mut Vec<AclEntry>) {
+        ui.label(
+            RichText::new(label)
+                .color(self.style.text_color.into_color32())
+                .size(self.style.text_size),
+        );
+
+        let mut remove = None;
+
+        for (idx, entry) in entries.iter_mut().enumerate() {
+            let (tag, named) = (entry.tag.to_string(), entry.is_named());
+
+            Sides::new().show(
+                ui,
+                |ui| {
+                    ui.label(
+                        RichText::new(tag)
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    );
+                },
+                |ui| {
+                    if named
+                        && ui
+                            .button(
+                                RichText::new("Remove")
+                                    .color(self.style.text_color.into_color32())
+                                    .size(self.style.text_size),
+                            )
+                            .clicked()
+                    {
+                        remove = Some(idx);
+                    }
+
+                    for (bit, text) in [(0o1, "execute"), (0o2, "write"), (0o4, "read")] {
+                        let mut set = entry.permissions & bit != 0;
+
+                        ui.checkbox(
+                            &mut set,
+                            RichText::new(text)
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+
+                        if set {
+                            entry.permissions |= bit;
+                        } else {
+                            entry.permissions &= !bit;
+                        }
+                    }
+                },
+            );
+        }
+
+        if let Some(idx) = remove {
+            entries.remove(idx);
         }
+
+        let change_acl = &mut self.entity_info.change_acl;
+
+        ui.push_id(label, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(&mut change_acl.uid));
+
+                if ui
+                    .button(
+                        RichText::new("Add user")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    )
+                    .clicked()
+                    && !entries
+                        .iter()
+                        .any(|e| e.tag == AclTag::User(change_acl.uid))
+                {
+                    entries.push(AclEntry::new(AclTag::User(change_acl.uid), 0o4));
+                    entries.sort_by_key(|e| e.tag);
+                }
+
+                ui.add(DragValue::new(&mut change_acl.gid));
+
+                if ui
+                    .button(
+                        RichText::new("Add group")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    )
+                    .clicked()
+                    && !entries
+                        .iter()
+                        .any(|e| e.tag == AclTag::Group(change_acl.gid))
+                {
+                    entries.push(AclEntry::new(AclTag::Group(change_acl.gid), 0o4));
+                    entries.sort_by_key(|e| e.tag);
+                }
+            });
+        });
     }
 
     fn entity_change_owners(&mut self, ctx: &Context) {
@@ -3646,6 +6948,7 @@ impl JujikView {
                                         self.tabs.clone(),
                                         self.current_tab_idx,
                                         self.entitys_show.clone(),
+                                        self.entitys_format.clone(),
                                     )))
                                     .inspect_err(JujikError::handle_err);
 
@@ -3733,9 +7036,27 @@ impl JujikView {
         }
     }
 
-    fn selection_entity_move(&mut self, ctx: &Context, pathbuf: PathBuf) {
+    fn selection_entity_move(&mut self, ctx: &Context, idx_tab: usize, tab: &Tab) {
         let events = ctx.input(|i| i.events.clone());
 
+        if let Some(Event::Key { modifiers, .. }) = events.iter().find(|e| {
+            matches!(
+                e,
+                Event::Key {
+                    key: Key::Delete,
+                    pressed: true,
+                    ..
+                }
+            )
+        }) && !self.entitys_selection.entitys.is_empty()
+        {
+            self.entitys_delete.show = (true, true);
+            self.entitys_delete.permanent = modifiers.shift;
+            self.entitys_delete.idx_tab = idx_tab;
+            self.entitys_delete.tab = tab.clone();
+            self.entitys_delete.entitys = self.entitys_selection.entitys_vec();
+        }
+
         if events.iter().any(|e| {
             matches!(
                 e,
@@ -3773,7 +7094,132 @@ impl JujikView {
             )
         }) {
             self.entitys_selection
-                .paste(self.controller.clone(), pathbuf);
+                .paste(self.controller.clone(), tab.path());
+        }
+    }
+
+    // Click sorts by the column, shift click makes it the secondary key
+    fn entitys_sort_header(
+        &self,
+        ui: &mut Ui,
+        idx_tab: usize,
+        tab: &Tab,
+        text: &str,
+        field: SortField,
+    ) {
+        let sortby = tab.sortby();
+        let mut new_sortby = sortby.clone();
+
+        let mark = match &sortby.then {
+            _ if sortby.field == field => match sortby.direction {
+                SortDirection::Ascending => " ▲",
+                SortDirection::Descending => " ▼",
+            },
+            Some((then, SortDirection::Ascending)) if *then == field => " △",
+            Some((then, SortDirection::Descending)) if *then == field => " ▽",
+            _ => "",
+        };
+
+        let response = ui.add(
+            Label::new(
+                RichText::new(format!("{}{}", text, mark))
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            )
+            .sense(Sense::click()),
+        );
+
+        if response.clicked() {
+            if ui.input(|i| i.modifiers.shift) {
+                new_sortby.then_by_field(field.clone());
+            } else {
+                new_sortby.sort_by_field(field.clone());
+            }
+        }
+
+        response.context_menu(|ui| {
+            for (text, direction) in [
+                ("Sort ascending", SortDirection::Ascending),
+                ("Sort descending", SortDirection::Descending),
+            ] {
+                if ui
+                    .button(
+                        RichText::new(text)
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    )
+                    .clicked()
+                {
+                    new_sortby.sort_by_field(field.clone());
+                    new_sortby.direction = direction;
+
+                    ui.close_menu();
+                }
+            }
+
+            if ui
+                .add_enabled(
+                    sortby.field != field,
+                    Button::new(
+                        RichText::new("Then by")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    ),
+                )
+                .clicked()
+            {
+                new_sortby.then_by_field(field.clone());
+
+                ui.close_menu();
+            }
+
+            if ui
+                .add_enabled(
+                    sortby.then.is_some(),
+                    Button::new(
+                        RichText::new("Clear then by")
+                            .color(self.style.text_color.into_color32())
+                            .size(self.style.text_size),
+                    ),
+                )
+                .clicked()
+            {
+                new_sortby.then = None;
+
+                ui.close_menu();
+            }
+
+            ui.separator();
+
+            ui.checkbox(
+                &mut new_sortby.natural,
+                RichText::new("Natural order")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+            ui.checkbox(
+                &mut new_sortby.ignore_case,
+                RichText::new("Ignore case")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+            ui.checkbox(
+                &mut new_sortby.dirs_first,
+                RichText::new("Directories first")
+                    .color(self.style.text_color.into_color32())
+                    .size(self.style.text_size),
+            );
+        });
+
+        if new_sortby != sortby {
+            let _ = self
+                .controller
+                .send(Command::ChangeEntitysSortBy(
+                    idx_tab,
+                    tab.clone(),
+                    new_sortby,
+                ))
+                .inspect_err(JujikError::handle_err);
         }
     }
 
@@ -3800,42 +7246,75 @@ impl JujikView {
                     |ui| {
                         ComboBox::from_id_salt("Sort Field")
                             .selected_text(format!("{:?}", self.entitys_sortby_info.field))
+                            .show_ui(ui, |ui| {
+                                for field in SortField::fields(&self.entitys_show.xattrs) {
+                                    let text = field.to_string();
+                                    ui.selectable_value(
+                                        &mut self.entitys_sortby_info.field,
+                                        field,
+                                        text,
+                                    );
+                                }
+                            });
+                    },
+                );
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Direction:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ComboBox::from_id_salt("Sort Direction")
+                            .selected_text(format!("{:?}", self.entitys_sortby_info.direction))
                             .show_ui(ui, |ui| {
                                 ui.selectable_value(
-                                    &mut self.entitys_sortby_info.field,
-                                    SortField::Name,
-                                    "Name",
-                                );
-                                ui.selectable_value(
-                                    &mut self.entitys_sortby_info.field,
-                                    SortField::Extension,
-                                    "Extension",
-                                );
-                                ui.selectable_value(
-                                    &mut self.entitys_sortby_info.field,
-                                    SortField::Permissions,
-                                    "Permissions",
-                                );
-                                ui.selectable_value(
-                                    &mut self.entitys_sortby_info.field,
-                                    SortField::Owners,
-                                    "Owners",
-                                );
-                                ui.selectable_value(
-                                    &mut self.entitys_sortby_info.field,
-                                    SortField::Size,
-                                    "Size",
+                                    &mut self.entitys_sortby_info.direction,
+                                    SortDirection::Ascending,
+                                    "Ascending",
                                 );
                                 ui.selectable_value(
-                                    &mut self.entitys_sortby_info.field,
-                                    SortField::Modification,
-                                    "Modification",
+                                    &mut self.entitys_sortby_info.direction,
+                                    SortDirection::Descending,
+                                    "Descending",
                                 );
+                            });
+                    },
+                );
+
+                Sides::new().show(
+                    ui,
+                    |ui| {
+                        ui.label(
+                            RichText::new("Then by:")
+                                .color(self.style.text_color.into_color32())
+                                .size(self.style.text_size),
+                        );
+                    },
+                    |ui| {
+                        ComboBox::from_id_salt("Sort Then Field")
+                            .selected_text(match &self.entitys_sortby_info.then {
+                                Some(field) => format!("{:?}", field),
+                                None => "None".to_string(),
+                            })
+                            .show_ui(ui, |ui| {
                                 ui.selectable_value(
-                                    &mut self.entitys_sortby_info.field,
-                                    SortField::Creation,
-                                    "Creation",
+                                    &mut self.entitys_sortby_info.then,
+                                    None,
+                                    "None",
                                 );
+                                for field in SortField::fields(&self.entitys_show.xattrs) {
+                                    let text = field.to_string();
+                                    ui.selectable_value(
+                                        &mut self.entitys_sortby_info.then,
+                                        Some(field),
+                                        text,
+                                    );
+                                }
                             });
                     },
                 );
@@ -3844,22 +7323,22 @@ impl JujikView {
                     ui,
                     |ui| {
                         ui.label(
-                            RichText::new("Direction:")
+                            RichText::new("Then direction:")
                                 .color(self.style.text_color.into_color32())
                                 .size(self.style.text_size),
                         );
                     },
                     |ui| {
-                        ComboBox::from_id_salt("Sort Direction")
-                            .selected_text(format!("{:?}", self.entitys_sortby_info.direction))
+                        ComboBox::from_id_salt("Sort Then Direction")
+                            .selected_text(format!("{:?}", self.entitys_sortby_info.then_direction))
                             .show_ui(ui, |ui| {
                                 ui.selectable_value(
-                                    &mut self.entitys_sortby_info.direction,
+                                    &mut self.entitys_sortby_info.then_direction,
                                     SortDirection::Ascending,
                                     "Ascending",
                                 );
                                 ui.selectable_value(
-                                    &mut self.entitys_sortby_info.direction,
+                                    &mut self.entitys_sortby_info.then_direction,
                                     SortDirection::Descending,
                                     "Descending",
                                 );
@@ -3867,6 +7346,25 @@ impl JujikView {
                     },
                 );
 
+                ui.checkbox(
+                    &mut self.entitys_sortby_info.natural,
+                    RichText::new("Natural order")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+                ui.checkbox(
+                    &mut self.entitys_sortby_info.ignore_case,
+                    RichText::new("Ignore case")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+                ui.checkbox(
+                    &mut self.entitys_sortby_info.dirs_first,
+                    RichText::new("Directories first")
+                        .color(self.style.text_color.into_color32())
+                        .size(self.style.text_size),
+                );
+
                 ui.separator();
 
                 Sides::new().show(
@@ -3881,23 +7379,22 @@ impl JujikView {
                             )
                             .clicked()
                         {
-                            if self
-                                .entitys_sortby_info
-                                .field
-                                .ne(&self.entitys_sortby_info.sortby.field)
-                                || self
-                                    .entitys_sortby_info
-                                    .direction
-                                    .ne(&self.entitys_sortby_info.sortby.direction)
-                            {
-                                self.entitys_sortby_info
-                                    .sortby
-                                    .field
-                                    .clone_from(&self.entitys_sortby_info.field);
-                                self.entitys_sortby_info
-                                    .sortby
-                                    .direction
-                                    .clone_from(&self.entitys_sortby_info.direction);
+                            let info = &self.entitys_sortby_info;
+                            let sortby = SortBy {
+                                field: info.field.clone(),
+                                direction: info.direction.clone(),
+                                then: info
+                                    .then
+                                    .clone()
+                                    .filter(|then| *then != info.field)
+                                    .map(|then| (then, info.then_direction.clone())),
+                                natural: info.natural,
+                                ignore_case: info.ignore_case,
+                                dirs_first: info.dirs_first,
+                            };
+
+                            if sortby != info.sortby {
+                                self.entitys_sortby_info.sortby = sortby;
 
                                 let _ = self
                                     .controller
@@ -4017,6 +7514,74 @@ impl JujikView {
     }
 }
 
+impl FinderInfo {
+    // Sizes are written and read back in the units the entitys are shown with
+    fn set_parameters(&mut self, parameters: FindParameters, format: &EntitysFormat) {
+        self.regex.clone_from(&parameters.regex);
+        self.path = parameters.path.to_string_lossy().to_string();
+        self.name.clone_from(&parameters.name);
+        self.extension.clone_from(&parameters.extension);
+        self.kind.clone_from(&parameters.kind);
+        self.permissions.clone_from(&parameters.permissions);
+        self.owners.clone_from(&parameters.owners);
+        self.size.clone_from(&parameters.size);
+        self.change_size = (
+            format.size_exact(&parameters.size.0),
+            format.size_exact(&parameters.size.1),
+        );
+        self.date_modification.clone_from(&parameters.date);
+        self.change_date_modification = (
+            Self::naive_date(&parameters.date.0),
+            Self::naive_date(&parameters.date.1),
+        );
+        self.filter.clone_from(&parameters.filter);
+        self.same_filesystem = parameters.same_filesystem;
+        self.parameters = parameters;
+    }
+
+    fn parameters(&self, format: &EntitysFormat) -> Result<FindParameters, JujikError> {
+        Ok(FindParameters {
+            regex: self.regex.clone(),
+            path: PathBuf::from(self.path.clone()),
+            name: self.name.clone(),
+            extension: self.extension.clone(),
+            kind: self.kind.clone(),
+            permissions: self.permissions.clone(),
+            owners: self.owners.clone(),
+            size: (
+                format.parse_size(self.change_size.0.as_str())?,
+                format.parse_size(self.change_size.1.as_str())?,
+            ),
+            date: (
+                Self::entity_date(self.change_date_modification.0, NaiveTime::MIN),
+                Self::entity_date(
+                    self.change_date_modification.1,
+                    NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999).unwrap_or_default(),
+                ),
+            ),
+            filter: self.filter.clone(),
+            same_filesystem: self.same_filesystem,
+        })
+    }
+
+    fn naive_date(date: &EntityDate) -> NaiveDate {
+        if date.is_none() {
+            NaiveDate::default()
+        } else {
+            date.date().date_naive()
+        }
+    }
+
+    // The default date of the picker stands for an unset bound
+    fn entity_date(date: NaiveDate, time: NaiveTime) -> EntityDate {
+        if date == NaiveDate::default() {
+            EntityDate::default()
+        } else {
+            EntityDate::from_datetime(date.and_time(time).and_utc())
+        }
+    }
+}
+
 impl EntitysSelection {
     fn entitys_vec(&self) -> Vec<Entity> {
         self.entitys.clone().into_iter().collect()
@@ -4040,6 +7605,7 @@ impl EntitysSelection {
                         0,
                         entitys.clone(),
                         pathbuf.clone(),
+                        ConflictPolicy::Ask,
                     ))
                     .inspect_err(JujikError::handle_err);
             }
@@ -4051,6 +7617,7 @@ impl EntitysSelection {
                         0,
                         entitys.clone(),
                         pathbuf.clone(),
+                        ConflictPolicy::Ask,
                     ))
                     .inspect_err(JujikError::handle_err);
             }
@@ -4079,6 +7646,21 @@ impl JujikColor {
     }
 }
 
+impl EntitysShowColumn {
+    pub fn dir_size(&self) -> bool {
+        self.dir_size
+    }
+
+    /// Details the shown columns need read.
+    pub fn details(&self) -> EntityDetails {
+        EntityDetails {
+            mime: self.mime,
+            // the permissions show a "+" for entitys with an ACL
+            xattrs: self.permissions || !self.xattrs.is_empty(),
+        }
+    }
+}
+
 impl Default for EntitysShowColumn {
     fn default() -> Self {
         Self {
@@ -4091,6 +7673,15 @@ impl Default for EntitysShowColumn {
             size: true,
             date_modification: true,
             date_creation: true,
+            date_access: false,
+            date_change: false,
+            mime: false,
+            dir_size: false,
+            allocated: false,
+            links: false,
+            inode: false,
+            device: false,
+            xattrs: Vec::new(),
         }
     }
 }